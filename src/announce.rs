@@ -0,0 +1,134 @@
+//! Announcements for champions new to the roster: a Markdown post with the champion's portrait
+//! and splash next to it, ready to share.
+
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::Context, Result};
+
+use crate::{
+    cdragon::{asset_url, CDragon, Champion, ChampionSummary},
+    http,
+    whatis::normalize,
+};
+
+/// The champions in `after` that weren't in `before`.
+pub fn new_champions<'a>(
+    before: &[ChampionSummary],
+    after: &'a [ChampionSummary],
+) -> Vec<&'a ChampionSummary> {
+    let known: HashSet<u64> = before.iter().map(|summary| summary.id).collect();
+    after
+        .iter()
+        .filter(|summary| !known.contains(&summary.id))
+        .collect()
+}
+
+/// The file names of the assets next to the post, with the extension of the original.
+fn asset_name(stem: &str, path: &str) -> String {
+    match Path::new(path).extension() {
+        Some(ext) => format!("{stem}.{}", ext.to_string_lossy()),
+        None => stem.to_string(),
+    }
+}
+
+/// The announcement post, referring to the assets written by [`CDragon::write_announcement`].
+pub fn markdown(champion: &Champion) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# New champion: {}, {}\n",
+        champion.name, champion.title
+    );
+    if let Some(base) = champion.skins.first() {
+        let splash = asset_name("splash", &base.splash_path);
+        let _ = writeln!(out, "![{}](assets/{splash})\n", champion.name);
+    }
+    let portrait = asset_name("portrait", &champion.square_portrait_path);
+    let _ = writeln!(out, "![](assets/{portrait}) **{}**\n", champion.name);
+    let _ = writeln!(out, "- Roles: {}", champion.roles.join(", "));
+    let _ = writeln!(
+        out,
+        "- Difficulty: {}/3, {} damage",
+        champion.tactical_info.difficulty,
+        champion
+            .tactical_info
+            .damage_type
+            .trim_start_matches('k')
+            .to_lowercase()
+    );
+    let _ = writeln!(out, "- Skins at launch: {}", champion.skins.len());
+    if !champion.spells.is_empty() {
+        let _ = writeln!(out, "\n## Abilities\n");
+        for spell in &champion.spells {
+            let _ = writeln!(
+                out,
+                "- **{}**: {}",
+                spell.spell_key.to_uppercase(),
+                spell.name
+            );
+        }
+    }
+    out
+}
+
+impl CDragon {
+    /// Writes an announcement for `champion` into `dir`: announcement.md, with its portrait and
+    /// base splash in an assets folder beside it. Returns the path of the post.
+    pub async fn write_announcement(&self, champion: &Champion, dir: &Path) -> Result<PathBuf> {
+        let assets = dir.join("assets");
+        fs::create_dir_all(&assets)?;
+        let mut wanted = vec![("portrait", normalize(&champion.square_portrait_path))];
+        if let Some(base) = champion.skins.first() {
+            wanted.push(("splash", base.splash_path.clone()));
+        }
+        for (stem, path) in wanted {
+            let bytes = http::get(&self.http_client, &asset_url(&path))
+                .await
+                .with_context(|| format!("couldn't download the {stem} of {}", champion.name))?;
+            fs::write(assets.join(asset_name(stem, &path)), bytes)?;
+        }
+        let post = dir.join("announcement.md");
+        fs::write(&post, markdown(champion))?;
+        Ok(post)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn announce_new_champions() -> Result<()> {
+        let summary = |id: u64, name: &str| ChampionSummary {
+            id,
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let before = [summary(1, "Annie"), summary(2, "Olaf")];
+        let after = [
+            summary(1, "Annie"),
+            summary(2, "Olaf"),
+            summary(999, "Newbie"),
+        ];
+        let new: Vec<&str> = new_champions(&before, &after)
+            .iter()
+            .map(|summary| summary.name.as_str())
+            .collect();
+        assert_eq!(new, ["Newbie"]);
+
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let post = markdown(&annie);
+        assert!(post.starts_with("# New champion: Annie, the Dark Child\n"));
+        assert!(post.contains("![Annie](assets/splash.jpg)"));
+        assert!(post.contains("![](assets/portrait.png) **Annie**"));
+        assert!(post.contains("- Difficulty: 1/3, magic damage"));
+        assert!(post.contains("- **R**: Summon: Tibbers"));
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+//! Groups the roster into archetypes by running k-means over the
+//! [playstyle features](crate::similarity::features).
+
+use std::{collections::HashMap, fmt::Write};
+
+use serde::Serialize;
+
+use crate::{cdragon::Champion, similarity::features};
+
+const MAX_ITERATIONS: usize = 100;
+
+/// Named points in feature space used to label clusters. A cluster takes the name of the
+/// prototype nearest its centroid.
+const PROTOTYPES: [(&str, [f64; 7]); 6] = [
+    ("Assassins", [3., 1., 1., 3., 1., 2., 1.5]),
+    ("Juggernauts", [3., 3., 2., 1., 1., 2., 1.2]),
+    ("Tanks", [1., 3., 3., 1., 2., 2., 1.5]),
+    ("Enchanters", [1., 1., 2., 1., 3., 2., 2.4]),
+    ("Mages", [3., 1., 2., 1., 1., 2., 2.7]),
+    ("Marksmen", [3., 1., 1., 2., 1., 2., 0.6]),
+];
+
+#[derive(Debug, Serialize)]
+pub struct Cluster {
+    pub label: String,
+    pub centroid: [f64; 7],
+    pub champions: Vec<String>,
+}
+
+fn distance(a: &[f64; 7], b: &[f64; 7]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+fn nearest(point: &[f64; 7], centroids: &[[f64; 7]]) -> usize {
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|a, b| distance(point, a.1).total_cmp(&distance(point, b.1)))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Clusters the roster into `k` groups, largest first.
+///
+/// The centroids are seeded deterministically with farthest point selection, starting from the
+/// lowest champion id, so the same roster always gives the same archetypes.
+pub fn cluster(champions: &HashMap<u64, Champion>, k: usize) -> Vec<Cluster> {
+    let mut roster: Vec<&Champion> = champions.values().collect();
+    roster.sort_by_key(|champ| champ.id);
+    let points: Vec<[f64; 7]> = roster.iter().map(|champ| features(champ)).collect();
+    let k = k.min(points.len());
+    if k == 0 {
+        return vec![];
+    }
+
+    let mut centroids = vec![points[0]];
+    while centroids.len() < k {
+        let farthest = points
+            .iter()
+            .max_by(|a, b| {
+                let da = distance(a, &centroids[nearest(a, &centroids)]);
+                let db = distance(b, &centroids[nearest(b, &centroids)]);
+                da.total_cmp(&db)
+            })
+            .unwrap();
+        centroids.push(*farthest);
+    }
+
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<usize> = points.iter().map(|p| nearest(p, &centroids)).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+        for (i, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&[f64; 7]> = points
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, a)| **a == i)
+                .map(|(p, _)| p)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            for (d, value) in centroid.iter_mut().enumerate() {
+                *value = members.iter().map(|p| p[d]).sum::<f64>() / members.len() as f64;
+            }
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = centroids
+        .iter()
+        .enumerate()
+        .map(|(i, centroid)| Cluster {
+            label: String::new(),
+            centroid: *centroid,
+            champions: roster
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, a)| **a == i)
+                .map(|(champ, _)| champ.name.clone())
+                .collect(),
+        })
+        .filter(|cluster| !cluster.champions.is_empty())
+        .collect();
+    clusters.sort_by_key(|cluster| std::cmp::Reverse(cluster.champions.len()));
+    label(&mut clusters);
+    clusters
+}
+
+/// Names each cluster after its nearest prototype, numbering repeats.
+fn label(clusters: &mut [Cluster]) {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for cluster in clusters {
+        let (name, _) = PROTOTYPES
+            .iter()
+            .min_by(|a, b| {
+                distance(&cluster.centroid, &a.1).total_cmp(&distance(&cluster.centroid, &b.1))
+            })
+            .unwrap();
+        let count = seen.entry(name).or_default();
+        *count += 1;
+        cluster.label = match *count {
+            1 => name.to_string(),
+            n => format!("{name} {n}"),
+        };
+    }
+}
+
+/// Renders the clusters as a list of champions grouped under their archetype.
+pub fn render(clusters: &[Cluster]) -> String {
+    let mut out = String::new();
+    for cluster in clusters {
+        let _ = writeln!(out, "{} ({})", cluster.label, cluster.champions.len());
+        let _ = writeln!(out, "  {}", cluster.champions.join(", "));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cdragon::{PlaystyleInfo, TactialInfo};
+
+    fn champ(id: u64, name: &str, playstyle: [u64; 5]) -> Champion {
+        let [damage, durability, crowd_control, mobility, utility] = playstyle;
+        Champion {
+            id,
+            name: name.to_string(),
+            playstyle_info: PlaystyleInfo {
+                damage,
+                durability,
+                crowd_control,
+                mobility,
+                utility,
+            },
+            tactical_info: TactialInfo {
+                style: 5,
+                difficulty: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn separates_obvious_groups() {
+        let champions: HashMap<u64, Champion> = [
+            champ(1, "Zed", [3, 1, 1, 3, 1]),
+            champ(2, "Talon", [3, 1, 1, 3, 1]),
+            champ(3, "Malphite", [1, 3, 3, 1, 2]),
+            champ(4, "Ornn", [1, 3, 3, 1, 2]),
+            champ(5, "Rammus", [1, 3, 3, 1, 2]),
+        ]
+        .into_iter()
+        .map(|champ| (champ.id, champ))
+        .collect();
+        let clusters = cluster(&champions, 2);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].label, "Tanks");
+        assert_eq!(clusters[0].champions, ["Malphite", "Ornn", "Rammus"]);
+        assert_eq!(clusters[1].label, "Assassins");
+        assert_eq!(clusters[1].champions, ["Zed", "Talon"]);
+    }
+}
@@ -0,0 +1,106 @@
+//! Arena, the client's "Cherry" game mode, and its augments from cherry-augments.json.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+    lock::CacheLock,
+};
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum ArenaRarity {
+    #[serde(rename = "kSilver")]
+    Silver,
+    #[serde(rename = "kGold")]
+    Gold,
+    #[serde(rename = "kPrismatic")]
+    Prismatic,
+    /// A rarity blitzadex doesn't know yet
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ArenaAugment {
+    pub id: u64,
+    #[serde(rename = "nameTRA")]
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(
+        rename = "augmentSmallIconPath",
+        default,
+        deserialize_with = "deserialize_asset_path"
+    )]
+    pub icon_path: String,
+    pub rarity: ArenaRarity,
+}
+
+/// The augments players can pick, without the unnamed ones left over from removed augments.
+fn pickable(augments: Vec<ArenaAugment>) -> Vec<ArenaAugment> {
+    augments
+        .into_iter()
+        .filter(|augment| !augment.name.is_empty())
+        .collect()
+}
+
+impl CDragon {
+    /// Fetches the Arena augments into the cache. Like [`CDragon::update_tft`], they aren't part
+    /// of [`CDragon::update`].
+    pub async fn update_arena(&self) -> Result<()> {
+        let _lock = CacheLock::acquire(&self.cache_dir, self.wait_for_lock).await?;
+        let augments = self
+            .fetch_arena_augments()
+            .await
+            .with_context(|| "failed to update Arena augments")?;
+        self.cache_versioned(
+            &augments,
+            CacheFile::ArenaAugments,
+            &self.data_version().await?,
+        )
+        .with_context(|| "failed to cache the updated Arena augments")?;
+        Ok(())
+    }
+
+    /// Loads the cached Arena augments, fetching them when they aren't cached yet.
+    pub async fn arena_augments(&self) -> Result<Vec<ArenaAugment>> {
+        if let Ok(augments) = self.load_obj(CacheFile::ArenaAugments) {
+            return Ok(augments);
+        }
+        let augments = self.fetch_arena_augments().await?;
+        self.cache_obj(&augments, CacheFile::ArenaAugments)?;
+        Ok(augments)
+    }
+
+    pub async fn fetch_arena_augments(&self) -> Result<Vec<ArenaAugment>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/cherry-augments.json"))
+            .await
+            .with_context(|| "couldn't fetch the Arena augments")?;
+        Ok(pickable(parse_json(&bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn augments() -> Result<()> {
+        let augments: Vec<ArenaAugment> =
+            serde_json::from_str(&std::fs::read_to_string("testing/cherry-augments.json")?)?;
+        assert_eq!(augments[3].rarity, ArenaRarity::Other);
+        let augments = pickable(augments);
+        assert_eq!(augments.len(), 3);
+        let waltz = &augments[1];
+        assert_eq!(waltz.name, "Blade Waltz");
+        assert_eq!(waltz.rarity, ArenaRarity::Gold);
+        assert_eq!(
+            waltz.icon_path,
+            "assets/ux/cherry/augments/icons/bladewaltz_small.png"
+        );
+        assert!(augments[0].rarity > augments[2].rarity);
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+//! A content addressable store for downloaded assets.
+//!
+//! Every asset is stored once under the SHA-256 of its bytes in `objects/`, and `index.json` maps
+//! the [Normalized Path](crate::cdragon::Skin) of each asset to its hash. Skins that share a
+//! splash art only take up space once, corruption can be detected by rehashing, and a snapshot of
+//! the whole asset set is just a copy of the index.
+//!
+//! Puts are batched: the index is only written by [`AssetStore::flush`], or when the store is
+//! dropped. Flushing merges into the index on disk, so stores opened side by side don't drop each
+//! other's assets.
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, create_dir_all},
+    path::{Path, PathBuf},
+};
+
+use color_eyre::{eyre::Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::cdragon::write_atomically;
+
+const OBJECTS: &str = "objects";
+const SNAPSHOTS: &str = "snapshots";
+const INDEX: &str = "index.json";
+
+#[derive(Debug)]
+pub struct AssetStore {
+    root: PathBuf,
+    /// Normalized asset path to the hash of its contents
+    index: BTreeMap<String, String>,
+    /// The entries put since the index was last written
+    pending: BTreeMap<String, String>,
+}
+
+impl AssetStore {
+    /// Opens the store rooted at `root`, which is created on the first write.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let index = read_index(&root)?;
+        Ok(Self {
+            root,
+            index,
+            pending: BTreeMap::new(),
+        })
+    }
+
+    pub fn hash_of(&self, asset_path: &str) -> Option<&str> {
+        self.index.get(asset_path).map(String::as_str)
+    }
+
+    /// Where the contents of an asset are stored, if it has been downloaded.
+    pub fn get(&self, asset_path: &str) -> Option<PathBuf> {
+        self.hash_of(asset_path).map(|hash| self.object_path(hash))
+    }
+
+    /// Every indexed asset path along with its hash.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.index
+            .iter()
+            .map(|(path, hash)| (path.as_str(), hash.as_str()))
+    }
+
+    /// Stores `bytes` as the contents of `asset_path` and returns their hash.
+    ///
+    /// The bytes are only written when no intact object already has the same contents, so an
+    /// object cut short by an interrupted write is replaced. The index isn't written, see
+    /// [`AssetStore::flush`].
+    pub fn put(&mut self, asset_path: &str, bytes: &[u8]) -> Result<String> {
+        let hash = format!("{:x}", Sha256::digest(bytes));
+        let object_path = self.object_path(&hash);
+        if !is_intact(&object_path, &hash) {
+            create_dir_all(object_path.parent().unwrap())?;
+            write_atomically(&object_path, bytes).with_context(|| "couldn't write the asset")?;
+        }
+        self.index.insert(asset_path.to_string(), hash.clone());
+        self.pending.insert(asset_path.to_string(), hash.clone());
+        Ok(hash)
+    }
+
+    /// Writes the assets put since the last flush to the index.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut index = read_index(&self.root)?;
+        index.append(&mut self.pending);
+        create_dir_all(&self.root)?;
+        write_atomically(
+            &self.root.join(INDEX),
+            &serde_json::to_string_pretty(&index)?,
+        )?;
+        self.index = index;
+        Ok(())
+    }
+
+    /// Rehashes every stored object and returns the asset paths whose contents are missing or
+    /// no longer match their hash.
+    pub fn verify(&self) -> Result<Vec<&str>> {
+        let mut corrupt = vec![];
+        for (path, hash) in &self.index {
+            if !is_intact(&self.object_path(hash), hash) {
+                corrupt.push(path.as_str());
+            }
+        }
+        Ok(corrupt)
+    }
+
+    /// Records the current set of assets under `name`. Objects are never deleted, so this only
+    /// needs to copy the index.
+    pub fn snapshot(&self, name: &str) -> Result<PathBuf> {
+        let dir = self.root.join(SNAPSHOTS);
+        create_dir_all(&dir)?;
+        let path = dir.join(format!("{name}.json"));
+        write_atomically(&path, &serde_json::to_string_pretty(&self.index)?)?;
+        Ok(path)
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.root.join(OBJECTS).join(&hash[..2]).join(hash)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for AssetStore {
+    fn drop(&mut self) {
+        // Callers that need to know whether the index was written flush it themselves
+        let _ = self.flush();
+    }
+}
+
+/// Whether the object at `path` exists and still hashes to `hash`.
+fn is_intact(path: &Path, hash: &str) -> bool {
+    fs::read(path)
+        .map(|bytes| format!("{:x}", Sha256::digest(bytes)) == hash)
+        .unwrap_or(false)
+}
+
+fn read_index(root: &Path) -> Result<BTreeMap<String, String>> {
+    let index_path = root.join(INDEX);
+    if !index_path.try_exists().unwrap_or(false) {
+        return Ok(BTreeMap::new());
+    }
+    let ser = fs::read_to_string(&index_path)?;
+    serde_json::from_str(&ser).with_context(|| "failed to read the asset index")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn deduplicates_and_verifies() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut store = AssetStore::open(dir.path())?;
+        let first = store.put("assets/a.jpg", b"splash")?;
+        let second = store.put("assets/b.jpg", b"splash")?;
+        assert_eq!(first, second);
+        assert!(store.verify()?.is_empty());
+        assert!(AssetStore::open(dir.path())?.get("assets/a.jpg").is_none());
+
+        let mut beside = AssetStore::open(dir.path())?;
+        beside.put("assets/c.jpg", b"tile")?;
+        beside.flush()?;
+        drop(store);
+        let reopened = AssetStore::open(dir.path())?;
+        assert_eq!(reopened.hash_of("assets/b.jpg"), Some(first.as_str()));
+        assert!(reopened.get("assets/c.jpg").is_some());
+
+        fs::write(reopened.get("assets/a.jpg").unwrap(), b"corrupt")?;
+        assert_eq!(reopened.verify()?, ["assets/a.jpg", "assets/b.jpg"]);
+        Ok(())
+    }
+
+    #[test]
+    fn rewrites_a_truncated_object() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut store = AssetStore::open(dir.path())?;
+        let hash = store.put("assets/a.jpg", b"splash")?;
+        // What a download interrupted halfway through used to leave behind
+        let object = store.get("assets/a.jpg").unwrap();
+        fs::write(&object, b"spl")?;
+        assert_eq!(store.verify()?, ["assets/a.jpg"]);
+
+        assert_eq!(store.put("assets/a.jpg", b"splash")?, hash);
+        assert_eq!(fs::read(object)?, b"splash");
+        assert!(store.verify()?.is_empty());
+        Ok(())
+    }
+}
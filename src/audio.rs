@@ -0,0 +1,154 @@
+//! Champion audio, which the game data ships as Ogg Vorbis and the champion data refers to as
+//! [`AudioAsset`]s. Platforms without Ogg support can get MP3 or WAV copies instead, transcoded
+//! by `ffmpeg`, which has to be on the `PATH`. Copies are kept in the
+//! [`AssetStore`](crate::assets::AssetStore) next to the originals.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use strum::Display;
+
+pub use blitzadex_types::AudioAsset;
+
+use crate::cdragon::CDragon;
+
+/// Under the asset store, the transcoded copies
+const DERIVED: &str = "derived/audio";
+
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[strum(serialize_all = "lowercase")]
+pub enum AudioFormat {
+    /// As published, no transcoding
+    #[default]
+    Ogg,
+    Mp3,
+    Wav,
+}
+
+impl AudioFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Ogg => "ogg",
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+        }
+    }
+
+    /// Where the copy of the audio at `asset_path` goes in the asset store, the original's path
+    /// for Ogg.
+    pub fn store_path(&self, asset_path: &str) -> String {
+        if *self == Self::Ogg {
+            return asset_path.to_string();
+        }
+        let stem = asset_path
+            .rsplit_once('.')
+            .map_or(asset_path, |(stem, _)| stem);
+        format!("{DERIVED}/{stem}.{}", self.extension())
+    }
+}
+
+/// The arguments making ffmpeg read from stdin and write `format` to stdout.
+fn ffmpeg_args(format: AudioFormat) -> Vec<&'static str> {
+    let muxer = match format {
+        AudioFormat::Ogg => "ogg",
+        AudioFormat::Mp3 => "mp3",
+        AudioFormat::Wav => "wav",
+    };
+    vec![
+        "-hide_banner",
+        "-loglevel",
+        "error",
+        "-i",
+        "pipe:0",
+        "-f",
+        muxer,
+        "pipe:1",
+    ]
+}
+
+/// Transcodes Ogg audio to `format` with ffmpeg. Ogg comes back as is.
+pub fn transcode(ogg: &[u8], format: AudioFormat) -> Result<Vec<u8>> {
+    if format == AudioFormat::Ogg {
+        return Ok(ogg.to_vec());
+    }
+    let mut ffmpeg = Command::new("ffmpeg")
+        .args(ffmpeg_args(format))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| "couldn't run ffmpeg, install it to transcode audio")?;
+    let mut stdin = ffmpeg.stdin.take().expect("stdin is piped");
+    let input = ogg.to_vec();
+    // Written from another thread so a full stdout pipe can't block the write
+    let writer = thread::spawn(move || stdin.write_all(&input));
+    let output = ffmpeg.wait_with_output()?;
+    writer
+        .join()
+        .map_err(|_| eyre!("couldn't write to ffmpeg"))?
+        .with_context(|| "couldn't write to ffmpeg")?;
+    if !output.status.success() {
+        return Err(eyre!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+impl CDragon {
+    /// The audio of `asset` in `format`, transcoded from the
+    /// [stored asset](CDragon::stored_asset) the first time.
+    pub async fn audio(&self, asset: &AudioAsset, format: AudioFormat) -> Result<Vec<u8>> {
+        let asset_path = &asset.path;
+        let path = format.store_path(asset_path);
+        if let Some(stored) = self.asset_store()?.get(&path) {
+            return Ok(std::fs::read(stored)?);
+        }
+        let ogg = self.stored_asset(asset_path).await?;
+        let bytes =
+            transcode(&ogg, format).with_context(|| format!("couldn't transcode {asset_path}"))?;
+        let mut store = self.asset_store()?;
+        store.put(&path, &bytes)?;
+        store.flush()?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn formats() -> Result<()> {
+        let path = "v1/champion-choose-vo/1.ogg";
+        assert_eq!(AudioFormat::Ogg.store_path(path), path);
+        assert_eq!(
+            AudioFormat::Mp3.store_path(path),
+            "derived/audio/v1/champion-choose-vo/1.mp3"
+        );
+        let args = ffmpeg_args(AudioFormat::Wav);
+        assert_eq!(args[args.len() - 3..], ["-f", "wav", "pipe:1"]);
+        assert_eq!(transcode(b"OggS", AudioFormat::Ogg)?, b"OggS");
+
+        let annie: crate::cdragon::Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let choose = &annie.choose_vo_path;
+        assert_eq!(choose.path, path);
+        assert_eq!(choose.content_type(), "audio/ogg");
+        assert!(choose
+            .url()
+            .ends_with("/global/default/v1/champion-choose-vo/1.ogg"));
+        assert_eq!(serde_json::to_string(choose)?, format!("\"{path}\""));
+        assert!(AudioAsset::default().is_empty());
+        Ok(())
+    }
+}
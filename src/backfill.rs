@@ -0,0 +1,233 @@
+//! Filling the per-patch cache, see [`crate::patches`], with catalogs of older patches so the
+//! game data can be compared across them.
+//!
+//! Backfilling is slow on purpose: requests are sent one at a time and at most one per
+//! [`RateLimit`] delay, on top of the backing off [`crate::http`] does. It's also resumable.
+//! Catalogs already cached for a patch are skipped, and a patch's champions are kept a file each
+//! under `champions/` until all of them are fetched, so an interrupted backfill picks up where it
+//! stopped.
+
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Report, Result,
+};
+use reqwest::StatusCode;
+use serde_json::{Map, Value};
+use strum::Display;
+
+use crate::{
+    cdragon::{parse_json, write_atomically, CDragon, CacheFile},
+    http,
+    patch::patch_numbers,
+    patches::Branch,
+    status::upstream_entry,
+    update::GAME_DATA_V1,
+};
+
+const RAW_ROOT: &str = "https://raw.communitydragon.org";
+/// Where a patch's champions are kept while they're being fetched
+const PARTIAL_CHAMPIONS: &str = "champions";
+
+/// Spaces requests out.
+#[derive(Debug)]
+pub struct RateLimit {
+    delay: Duration,
+    last: Option<Instant>,
+}
+
+impl RateLimit {
+    pub fn new(delay: Duration) -> Self {
+        RateLimit { delay, last: None }
+    }
+
+    /// Waits until the delay has passed since the last request.
+    pub async fn wait(&mut self) {
+        if let Some(elapsed) = self.last.map(|last| last.elapsed()) {
+            if elapsed < self.delay {
+                tokio::time::sleep(self.delay - elapsed).await;
+            }
+        }
+        self.last = Some(Instant::now());
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Outcome {
+    #[strum(to_string = "fetched")]
+    Fetched,
+    #[strum(to_string = "already cached")]
+    Cached,
+    /// The patch doesn't have the catalog, like arena augments from before Arena
+    #[strum(to_string = "not in this patch")]
+    Missing,
+}
+
+/// Where `dir` of [`upstream_entry`] is in the branch of `patch`.
+fn branch_url(patch: &str, dir: &str) -> String {
+    format!("{RAW_ROOT}/{}", dir.replacen("latest", patch, 1))
+}
+
+/// Whether `catalog` is a file on CommunityDragon, or champions, rather than something
+/// blitzadex puts together itself.
+pub fn can_backfill(catalog: CacheFile) -> bool {
+    !matches!(catalog, CacheFile::Plugins | CacheFile::TftAugments)
+}
+
+/// The patches from `from` to `to`, or to the latest one, oldest first.
+pub fn between(branches: &[Branch], from: &str, to: Option<&str>) -> Result<Vec<String>> {
+    let first = patch_numbers(from);
+    let last = to.map(patch_numbers);
+    if last.as_ref().is_some_and(|last| *last < first) {
+        return Err(eyre!("{from} comes after {}", to.unwrap_or_default()));
+    }
+    let patches: Vec<String> = branches
+        .iter()
+        .filter(|branch| {
+            let numbers = patch_numbers(&branch.patch);
+            numbers >= first && last.as_ref().is_none_or(|last| numbers <= *last)
+        })
+        .map(|branch| branch.patch.clone())
+        .collect();
+    match patches.is_empty() {
+        true => Err(eyre!(
+            "CommunityDragon has no patches from {from} to {}",
+            to.unwrap_or("the latest")
+        )),
+        false => Ok(patches),
+    }
+}
+
+fn is_not_found(err: &Report) -> bool {
+    err.chain()
+        .filter_map(|err| err.downcast_ref::<reqwest::Error>())
+        .any(|err| err.status() == Some(StatusCode::NOT_FOUND))
+}
+
+impl CDragon {
+    /// Caches `catalog` of an older `patch`, unless it already is.
+    pub async fn backfill(
+        &self,
+        patch: &str,
+        catalog: CacheFile,
+        limit: &mut RateLimit,
+    ) -> Result<Outcome> {
+        if !can_backfill(catalog) {
+            return Err(eyre!("{catalog} can't be backfilled"));
+        }
+        if self.load_patch_obj::<Value>(patch, catalog).is_ok() {
+            return Ok(Outcome::Cached);
+        }
+        let fetched = match catalog {
+            CacheFile::Champions => self.backfill_champions(patch, limit).await,
+            _ => {
+                let (dir, name) = upstream_entry(catalog);
+                self.fetch_value(&format!("{}/{name}", branch_url(patch, dir)), limit)
+                    .await
+            }
+        };
+        match fetched {
+            Ok(document) => {
+                self.cache_patch_obj(patch, &document, catalog)?;
+                if catalog == CacheFile::Champions {
+                    fs::remove_dir_all(self.patch_dir(patch).join(PARTIAL_CHAMPIONS)).ok();
+                }
+                Ok(Outcome::Fetched)
+            }
+            Err(err) if is_not_found(&err) => Ok(Outcome::Missing),
+            Err(err) => Err(err.wrap_err(format!("couldn't backfill {catalog} of {patch}"))),
+        }
+    }
+
+    async fn fetch_value(&self, url: &str, limit: &mut RateLimit) -> Result<Value> {
+        limit.wait().await;
+        let bytes = http::get(&self.http_client, url).await?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    /// Every champion of `patch` keyed by id, like the latest [`CacheFile::Champions`] but as
+    /// CommunityDragon has them.
+    async fn backfill_champions(&self, patch: &str, limit: &mut RateLimit) -> Result<Value> {
+        let v1 = branch_url(patch, GAME_DATA_V1);
+        let summaries = self
+            .fetch_value(&format!("{v1}/champion-summary.json"), limit)
+            .await?;
+        // Skipping the placeholder "None" champion, -1
+        let ids: Vec<i64> = summaries
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|summary| summary["id"].as_i64())
+            .filter(|id| *id > 0)
+            .collect();
+
+        let partial = self.patch_dir(patch).join(PARTIAL_CHAMPIONS);
+        fs::create_dir_all(&partial)?;
+        let mut champions = Map::new();
+        for id in ids {
+            let path = partial.join(format!("{id}.json"));
+            let champion = match fs::read(&path) {
+                Ok(bytes) => parse_json(&bytes)?,
+                Err(_) => {
+                    let champion = self
+                        .fetch_value(&format!("{v1}/champions/{id}.json"), limit)
+                        .await
+                        .with_context(|| format!("couldn't fetch champion {id}"))?;
+                    write_atomically(&path, &serde_json::to_string(&champion)?)?;
+                    champion
+                }
+            };
+            champions.insert(id.to_string(), champion);
+        }
+        Ok(Value::Object(champions))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{listing::DirEntry, patches::branches};
+
+    #[tokio::test]
+    async fn picks_patches_and_resumes() -> Result<()> {
+        let root: Vec<DirEntry> =
+            serde_json::from_str(&fs::read_to_string("testing/branches.json")?)?;
+        let branches = branches(root, &[], None);
+        assert_eq!(
+            between(&branches, "13.2", Some("14.9"))?,
+            ["13.2", "13.10", "14.9"]
+        );
+        assert_eq!(between(&branches, "14.10", None)?, ["14.10", "15.1"]);
+        assert!(between(&branches, "14.10", Some("13.1")).is_err());
+        assert!(between(&branches, "16.1", None).is_err());
+
+        assert_eq!(
+            branch_url("14.9", GAME_DATA_V1),
+            format!("{RAW_ROOT}/14.9/plugins/rcp-be-lol-game-data/global/default/v1")
+        );
+        assert!(!can_backfill(CacheFile::TftAugments));
+
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        let mut limit = RateLimit::new(Duration::from_millis(20));
+        cdrag.cache_patch_obj("14.9", &Vec::<Value>::new(), CacheFile::Items)?;
+        assert_eq!(
+            cdrag.backfill("14.9", CacheFile::Items, &mut limit).await?,
+            Outcome::Cached
+        );
+        assert!(cdrag
+            .backfill("14.9", CacheFile::Plugins, &mut limit)
+            .await
+            .is_err());
+
+        let start = Instant::now();
+        limit.wait().await;
+        limit.wait().await;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! A champion's detail card, everything worth knowing at a glance.
+//!
+//! ```text
+//! #001 Annie, the Dark Child
+//! mage, support · Magic damage · difficulty ■□□
+//!
+//! Dangerous, yet disarmingly precocious, Annie is a child mage with immense pyromantic
+//! power. ...
+//!
+//! damage        ■■■
+//! ...
+//! ```
+
+use std::fmt::Write;
+
+use crate::{
+    cdragon::{asset_url, Champion},
+    whatis::normalize,
+};
+
+/// How wide the bio is wrapped
+const WIDTH: usize = 88;
+/// Playstyle ratings and the difficulty go from 1 to this
+const MAX_RATING: u64 = 3;
+
+pub(crate) fn rating(value: u64) -> String {
+    let filled = value.min(MAX_RATING) as usize;
+    format!(
+        "{}{}",
+        "■".repeat(filled),
+        "□".repeat(MAX_RATING as usize - filled)
+    )
+}
+
+/// Breaks `text` into lines of at most `width` characters, at spaces.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Renders `champion`'s card, headed by its dex `number`.
+pub fn render(champion: &Champion, number: usize) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "#{number:03} {}, {}", champion.name, champion.title);
+    let tactical = &champion.tactical_info;
+    let _ = writeln!(
+        out,
+        "{} · {} damage · difficulty {}",
+        champion.roles.join(", "),
+        tactical.damage_type.trim_start_matches('k'),
+        rating(tactical.difficulty)
+    );
+    if !champion.short_bio.is_empty() {
+        let _ = writeln!(out);
+        for line in wrap(&champion.short_bio, WIDTH) {
+            let _ = writeln!(out, "{line}");
+        }
+    }
+
+    let _ = writeln!(out);
+    let play = &champion.playstyle_info;
+    let ratings = [
+        ("damage", play.damage),
+        ("durability", play.durability),
+        ("crowd control", play.crowd_control),
+        ("mobility", play.mobility),
+        ("utility", play.utility),
+    ];
+    for (label, value) in ratings {
+        let _ = writeln!(out, "{label:<14}{}", rating(value));
+    }
+
+    let _ = writeln!(out);
+    let mut assets = vec![(
+        "portrait",
+        asset_url(&normalize(&champion.square_portrait_path)),
+    )];
+    if let Some(base) = champion.skins.iter().find(|skin| skin.is_base) {
+        assets.push(("splash", asset_url(&base.splash_path)));
+        assets.push(("tile", asset_url(&base.tile_path)));
+    }
+    if !champion.choose_vo_path.is_empty() {
+        assets.push(("choose vo", champion.choose_vo_path.url()));
+    }
+    for (label, url) in assets {
+        let _ = writeln!(out, "{label:<14}{url}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color_eyre::Result;
+
+    #[test]
+    fn annie_card() -> Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let card = render(&annie, 1);
+        let lines: Vec<&str> = card.lines().collect();
+        assert_eq!(lines[0], "#001 Annie, the Dark Child");
+        assert_eq!(lines[1], "mage, support · Magic damage · difficulty ■□□");
+        assert!(lines[3].starts_with("Dangerous, yet disarmingly precocious"));
+        let bio: Vec<&str> = lines[3..]
+            .iter()
+            .take_while(|line| !line.is_empty())
+            .copied()
+            .collect();
+        assert!(bio.len() > 1);
+        assert!(bio.iter().all(|line| line.chars().count() <= WIDTH));
+        assert!(card.contains(&format!(
+            "damage        {}",
+            rating(annie.playstyle_info.damage)
+        )));
+        assert!(card.contains("/global/default/v1/champion-icons/1.png"));
+        assert!(card.contains("annie_splash_centered_0.jpg"));
+        assert_eq!(wrap("a bb ccc", 4), ["a bb", "ccc"]);
+        Ok(())
+    }
+}
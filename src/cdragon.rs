@@ -2,9 +2,9 @@ use std::{
     collections::HashMap,
     fmt::Display,
     fs::{self, create_dir_all, File},
-    io::{self, BufReader},
-    path::PathBuf,
-    u64,
+    future::Future,
+    io::BufReader,
+    path::{Path, PathBuf},
 };
 
 use chrono::{DateTime, Utc};
@@ -12,17 +12,35 @@ use color_eyre::{
     eyre::{eyre, Context, ContextCompat},
     Result,
 };
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use strum::Display;
+use strum::{Display, EnumIter};
 use tokio::task::JoinHandle;
 
-const GAME_DATA_URL: &str =
-    "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default";
+pub(crate) use blitzadex_types::paths::{
+    deserialize_asset_path, deserialize_optional_asset_path, normalize_asset_path, GAME_DATA_URL,
+};
+pub use blitzadex_types::{
+    paths::asset_url, plugin::mtime_format, Champion, ChampionSummary, Chroma, Passive,
+    PlaystyleInfo, Plugin, PluginName, Rarity, RegionalDescription, RegionalRarity, Skin, SkinLine,
+    SkinType, Spell, TactialInfo,
+};
+
+use crate::{
+    assets::AssetStore, community::Overlay, http, lock::CacheLock, names::fold, profile::Profile,
+    progress,
+};
+
 const V1: &str = "v1";
+/// Ability preview videos are hosted by Riot rather than CommunityDragon
+pub const ABILITY_VIDEO_URL: &str = "https://d28xe8vt774jo5.cloudfront.net";
 const ASSETS: &str = "assets";
+/// A gzipped champion-summary.json, minus the "None" champion, to answer from before the first
+/// fetch
+#[cfg(feature = "embedded")]
+const EMBEDDED_SUMMARIES: &[u8] = include_bytes!("../data/champion-summary.json.gz");
 
-#[derive(Debug, Default, Display)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, Serialize)]
 pub enum Status {
     #[default]
     Uninitialized,
@@ -30,10 +48,41 @@ pub enum Status {
     UpToDate,
 }
 
-#[derive(Debug)]
-enum CacheFile {
+/// The catalogs blitzadex keeps in its cache directory, displayed as their file names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum CacheFile {
     Plugins,
     Champions,
+    ChampionSummaries,
+    ContentMetadata,
+    TftTeamPlanner,
+    Challenges,
+    SummonerBanners,
+    Items,
+    Perks,
+    PerkStyles,
+    SummonerSpells,
+    Skins,
+    SkinLines,
+    Universes,
+    WardSkins,
+    SummonerIcons,
+    SummonerEmotes,
+    SummonerTrophies,
+    Regalia,
+    Queues,
+    Maps,
+    Loot,
+    Statstones,
+    MissionAssets,
+    TftChampions,
+    TftTraits,
+    TftItems,
+    TftAugments,
+    TftCompanions,
+    TftMapSkins,
+    ArenaAugments,
 }
 
 impl Display for CacheFile {
@@ -41,6 +90,57 @@ impl Display for CacheFile {
         let s = match self {
             Self::Plugins => "plugins.json",
             Self::Champions => "champions.json",
+            Self::ChampionSummaries => "champion-summary.json",
+            Self::ContentMetadata => "content-metadata.json",
+            Self::TftTeamPlanner => "tftchampions-teamplanner.json",
+            Self::Challenges => "challenges.json",
+            Self::SummonerBanners => "summoner-banners.json",
+            Self::Items => "items.json",
+            Self::Perks => "perks.json",
+            Self::PerkStyles => "perkstyles.json",
+            Self::SummonerSpells => "summoner-spells.json",
+            Self::Skins => "skins.json",
+            Self::SkinLines => "skinlines.json",
+            Self::Universes => "universes.json",
+            Self::WardSkins => "ward-skins.json",
+            Self::SummonerIcons => "summoner-icons.json",
+            Self::SummonerEmotes => "summoner-emotes.json",
+            Self::SummonerTrophies => "summoner-trophies.json",
+            Self::Regalia => "regalia.json",
+            Self::Queues => "queues.json",
+            Self::Maps => "maps.json",
+            Self::Loot => "loot.json",
+            Self::Statstones => "statstones.json",
+            Self::MissionAssets => "mission-assets.json",
+            Self::TftChampions => "tftchampions.json",
+            Self::TftTraits => "tfttraits.json",
+            Self::TftItems => "tftitems.json",
+            // Split out of tftitems.json, CDragon doesn't have a file of its own for them
+            Self::TftAugments => "tftaugments.json",
+            Self::TftCompanions => "companions.json",
+            Self::TftMapSkins => "tftmapskins.json",
+            Self::ArenaAugments => "cherry-augments.json",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Catalogs serialize as their file names too
+impl Serialize for CacheFile {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Debug)]
+enum DataFile {
+    Profile,
+}
+
+impl Display for DataFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Profile => "profile.json",
         };
         f.write_str(s)
     }
@@ -48,45 +148,160 @@ impl Display for CacheFile {
 
 #[derive(Debug, Default)]
 pub struct CDragon {
-    http_client: reqwest::Client,
-    cache_dir: PathBuf,
-    data_dir: PathBuf,
-    config_dir: PathBuf,
+    pub(crate) http_client: reqwest::Client,
+    pub(crate) cache_dir: PathBuf,
+    pub(crate) data_dir: PathBuf,
+    pub(crate) config_dir: PathBuf,
     status: Status,
     pub plugins: Vec<Plugin>,
     pub champions: HashMap<u64, Champion>,
+    /// Whether [`CDragon::update`] and unpacking a pack wait for another process's update to
+    /// finish instead of failing
+    pub wait_for_lock: bool,
 }
 
 impl CDragon {
     pub async fn new() -> color_eyre::Result<Self> {
+        let mut cdrag = Self::lazy();
+        // Deserializing the champions dominates startup, so the catalogs are read in parallel
+        let (plugins, (champions, overlay)) = rayon::join(
+            || cdrag.load_obj(CacheFile::Plugins),
+            || {
+                rayon::join(
+                    || cdrag.load_obj(CacheFile::Champions),
+                    || cdrag.community(),
+                )
+            },
+        );
+        cdrag.plugins = match plugins {
+            Ok(plugins) => plugins,
+            Err(_) => {
+                let plugins = cdrag.fetch_plugins().await?;
+                cdrag.cache_obj(&plugins, CacheFile::Plugins)?;
+                plugins
+            }
+        };
+        cdrag.champions = match champions {
+            Ok(champions) => champions,
+            Err(_) => {
+                let champions = cdrag.fetch_all_champions().await?;
+                cdrag.cache_versioned(
+                    &champions,
+                    CacheFile::Champions,
+                    &cdrag.data_version().await?,
+                )?;
+                champions
+            }
+        };
+        let overlay = overlay?;
+        for champ in cdrag.champions.values_mut() {
+            champ.community = overlay.get(&champ.alias).cloned().unwrap_or_default();
+        }
+        Ok(cdrag)
+    }
+
+    /// Creates a [`CDragon`] without loading any of the cached catalogs.
+    ///
+    /// Used by the paths that need to start fast, e.g. [`CDragon::champion_summaries`] only reads
+    /// the small summary index instead of every champion's details.
+    pub fn lazy() -> Self {
         let proj_dirs = directories::ProjectDirs::from("", "", "blitzadex")
             .with_context(|| "failed to find the project directory")
             .unwrap();
         let cache_dir = proj_dirs.cache_dir().to_path_buf();
         let data_dir = proj_dirs.data_dir().to_path_buf();
         let config_dir = proj_dirs.config_dir().to_path_buf();
-        let mut cdrag = Self {
+        Self {
             status: Status::Uninitialized,
             http_client: reqwest::Client::new(),
             cache_dir,
             data_dir,
             config_dir,
             ..Default::default()
-        };
-        cdrag.plugins = cdrag
-            .load_obj(CacheFile::Plugins)
-            .unwrap_or(cdrag.fetch_plugins().await?);
-        cdrag.champions = cdrag
-            .load_obj(CacheFile::Champions)
-            .unwrap_or(cdrag.fetch_all_champions().await?);
-        Ok(cdrag)
+        }
+    }
+
+    /// Creates a [`CDragon`] that keeps its cache, data and config directories under `root`
+    /// instead of the user's project directories. Like [`CDragon::lazy`], nothing is loaded.
+    pub fn in_dir(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            cache_dir: root.join("cache"),
+            data_dir: root.join("data"),
+            config_dir: root.join("config"),
+            ..Self::lazy()
+        }
+    }
+
+    /// Loads the cached champion summary index, fetching it when it isn't cached yet.
+    pub async fn champion_summaries(&self) -> color_eyre::Result<Vec<ChampionSummary>> {
+        if let Ok(summaries) = self.load_obj(CacheFile::ChampionSummaries) {
+            return Ok(summaries);
+        }
+        let summaries = self.fetch_champion_summaries().await?;
+        self.cache_obj(&summaries, CacheFile::ChampionSummaries)?;
+        Ok(summaries)
+    }
+
+    /// Like [`CDragon::champion_summaries`], but answers at once when nothing is cached yet and
+    /// blitzadex was built with the `embedded` feature, from the summaries built into the binary.
+    /// The current summaries are then fetched and cached in the background, which only finishes
+    /// if the returned handle is awaited.
+    pub async fn champion_summaries_now(
+        &self,
+    ) -> Result<(Vec<ChampionSummary>, Option<JoinHandle<Result<()>>>)> {
+        if let Ok(summaries) = self.load_obj(CacheFile::ChampionSummaries) {
+            return Ok((summaries, None));
+        }
+        #[cfg(feature = "embedded")]
+        {
+            let refresh = Self {
+                http_client: self.http_client.clone(),
+                cache_dir: self.cache_dir.clone(),
+                ..Self::default()
+            };
+            let handle = tokio::spawn(async move {
+                let summaries = refresh.fetch_champion_summaries().await?;
+                refresh.cache_obj(&summaries, CacheFile::ChampionSummaries)
+            });
+            Ok((embedded_champion_summaries()?, Some(handle)))
+        }
+        #[cfg(not(feature = "embedded"))]
+        Ok((self.champion_summaries().await?, None))
+    }
+
+    /// Loads the details of a single champion from the cache, fetching them when the champions
+    /// aren't cached yet.
+    pub async fn champion(&self, id: u64) -> color_eyre::Result<Champion> {
+        let champions: Result<HashMap<u64, Champion>> = self.load_obj(CacheFile::Champions);
+        match champions.ok().and_then(|mut champs| champs.remove(&id)) {
+            Some(champion) => Ok(champion),
+            None => self.fetch_champion(id).await,
+        }
     }
 
-    pub fn champion_by_name<'a, N: Into<String> + Copy>(&'a self, name: N) -> Option<&'a Champion> {
+    /// Finds a cached champion by its name, alias or community nickname, ignoring case.
+    pub fn champion_by_name<N: Into<String> + Copy>(&self, name: N) -> Option<&Champion> {
+        let name: String = name.into();
+        let folded = fold(&name);
         self.champions
             .iter()
-            .find(|champ| champ.1.name == name.into())
+            .find(|champ| {
+                fold(&champ.1.name) == folded
+                    || fold(&champ.1.alias) == folded
+                    || champ.1.community.has_nickname(&name)
+            })
             .map(|champ| champ.1)
+            .or_else(|| {
+                // Only read the other locales' names when it isn't an English one
+                let id = self.localized_names().ok()?.id_of(&name)?;
+                self.champions.get(&id)
+            })
+    }
+
+    /// The community metadata [`Overlay`], including the user's additions.
+    pub fn community(&self) -> Result<Overlay> {
+        Overlay::load(&self.config_dir)
     }
 
     pub fn clean_up(&self) -> color_eyre::Result<()> {
@@ -96,14 +311,17 @@ impl CDragon {
         Ok(())
     }
 
-    async fn cached_plugin_updated_date(&self, name: &PluginName) -> Option<DateTime<Utc>> {
+    pub(crate) async fn cached_plugin_updated_date(
+        &self,
+        name: &PluginName,
+    ) -> Option<DateTime<Utc>> {
         let plugins: Result<Vec<Plugin>, color_eyre::eyre::Error> =
             self.load_obj(CacheFile::Plugins);
         plugins.map_or(None, |plugs| {
             plugs
                 .iter()
                 .find(|plug| plug.name == *name)
-                .map_or(None, |p| Some(p.mtime))
+                .map(|p| p.mtime)
         })
     }
 
@@ -119,9 +337,9 @@ impl CDragon {
                         eyre!("failed to check when {plugin_name} was last updated: {e}")
                     })?;
                 if cached_date < fetched {
-                    return Ok(Status::OutOfDate);
+                    Ok(Status::OutOfDate)
                 } else {
-                    return Ok(Status::UpToDate);
+                    Ok(Status::UpToDate)
                 }
             }
         }
@@ -135,23 +353,27 @@ impl CDragon {
     /// [`file_name`] - the name of this cache file ending with '.json'
     ///
     /// # Examples
-    /// ```
+    /// ```ignore
     /// use cdragon::CDragon;
     ///
     /// let cdrag = CDragon::new().unwrap();
     /// let champions = cdrag.champions().await.unwrap();
     /// let _ = cdrag.save(&champions, "champions.json");
     /// ```
-    fn cache_obj(&self, obj: &impl Serialize, cache_file: CacheFile) -> color_eyre::Result<()> {
+    pub(crate) fn cache_obj(
+        &self,
+        obj: &impl Serialize,
+        cache_file: CacheFile,
+    ) -> color_eyre::Result<()> {
         let ser = serde_json::to_string_pretty(obj)?;
+        let _span = tracing::info_span!("cache").entered();
         let mut file_path = self.cache_dir.clone();
-        if file_path.try_exists().is_err()
-            || file_path.try_exists().is_ok_and(|exists| exists == false)
-        {
+        if file_path.try_exists().is_err() || file_path.try_exists().is_ok_and(|exists| !exists) {
             create_dir_all(&file_path)?;
         }
         file_path.push(cache_file.to_string());
-        fs::write(file_path, ser)?;
+        write_atomically(&file_path, ser)?;
+        progress::cached(cache_file);
         Ok(())
     }
 
@@ -161,22 +383,63 @@ impl CDragon {
     /// [`file_name`] - the name of the cache file to load ending with '.json'
     ///
     /// # Examples
-    /// ```
+    /// ```ignore
     /// use cdragon::CDragon;
     ///
     /// let cdrag = CDragon::new().unwrap();
     /// let champions = cdrag.load(CacheFile::Champions).unwrap();
     /// ```
-    fn load_obj<T>(&self, cache_file: CacheFile) -> color_eyre::Result<T>
+    pub(crate) fn load_obj<T>(&self, cache_file: CacheFile) -> color_eyre::Result<T>
     where
         for<'a> T: Deserialize<'a>,
     {
         let mut file_path = self.cache_dir.clone();
         file_path.push(cache_file.to_string());
+        // Much faster than serde_json::from_reader, which reads a byte at a time
+        let bytes = {
+            let _span = tracing::info_span!("cache").entered();
+            fs::read(file_path)?
+        };
+        let obj = parse_json(&bytes)?;
+        Ok(obj)
+    }
+
+    /// Looks up a value in a cached catalog by [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901),
+    /// e.g. `"/1/skins/3/name"` in [`CacheFile::Champions`] for the name of Annie's fourth skin.
+    ///
+    /// The catalog is read as plain JSON rather than into the typed models. It's cached as
+    /// blitzadex serialized it, though, so fields the models drop when fetching aren't there.
+    pub fn get_path(&self, catalog: CacheFile, pointer: &str) -> Result<Value> {
+        let mut document: Value = self
+            .load_obj(catalog)
+            .with_context(|| format!("{catalog} isn't cached"))?;
+        document
+            .pointer_mut(pointer)
+            .map(Value::take)
+            .ok_or_else(|| eyre!("nothing at {pointer} in {catalog}"))
+    }
+
+    /// Loads the user's [`Profile`] from $HOME/.local/share/blitzadex/profile.json.
+    ///
+    /// A missing profile is not an error, a fresh [`Profile`] is returned instead.
+    pub fn profile(&self) -> color_eyre::Result<Profile> {
+        let file_path = self.data_dir.join(DataFile::Profile.to_string());
+        if !file_path.try_exists().unwrap_or(false) {
+            return Ok(Profile::default());
+        }
         let file = File::open(file_path)?;
         let reader = BufReader::new(file);
-        let obj = serde_json::from_reader(reader)?;
-        Ok(obj)
+        let profile =
+            serde_json::from_reader(reader).with_context(|| "failed to read the profile")?;
+        Ok(profile)
+    }
+
+    /// Saves the user's [`Profile`] to $HOME/.local/share/blitzadex/profile.json.
+    pub fn save_profile(&self, profile: &Profile) -> color_eyre::Result<()> {
+        let ser = serde_json::to_string_pretty(profile)?;
+        create_dir_all(&self.data_dir)?;
+        fs::write(self.data_dir.join(DataFile::Profile.to_string()), ser)?;
+        Ok(())
     }
 
     /// Fetches the latest CDragon data, and updates the [`CDragon.status`] to
@@ -185,8 +448,14 @@ impl CDragon {
     /// The fetched data is stored in fields of the [`CDragon`] struct. Currently
     /// only the [`Plugin`]s and [`Champion`]s are stored.
     ///
+    /// Catalogs other than the plugins keep a history of what changed, see [`crate::history`].
     ///
+    /// Beyond the plugins, patch version and champions, only the catalogs already in the cache are
+    /// refreshed, the others are fetched when first used. One of them failing to update doesn't
+    /// stop the others, its cached copy is kept and the update ends with an error naming it.
     pub async fn update(&mut self) -> color_eyre::Result<()> {
+        let _lock = CacheLock::acquire(&self.cache_dir, self.wait_for_lock).await?;
+        progress::started();
         let plugins = self
             .fetch_plugins()
             .await
@@ -195,31 +464,174 @@ impl CDragon {
             .with_context(|| "failed to cache the updated plugins")?;
         self.plugins = plugins;
 
+        let patch = self
+            .fetch_patch_version()
+            .await
+            .with_context(|| "failed to update the patch version")?;
+        let version = patch.patch().to_string();
+        self.cache_versioned(&patch, CacheFile::ContentMetadata, &version)
+            .with_context(|| "failed to cache the updated patch version")?;
+
+        let summaries = self
+            .fetch_champion_summaries()
+            .await
+            .with_context(|| "failed to update champion summaries")?;
+        self.cache_versioned(&summaries, CacheFile::ChampionSummaries, &version)
+            .with_context(|| "failed to cache the updated champion summaries")?;
+
         let champions = self
             .fetch_all_champions()
             .await
             .with_context(|| "failed to update champions")?;
-        self.cache_obj(&champions, CacheFile::Champions)
+        self.cache_versioned(&champions, CacheFile::Champions, &version)
             .with_context(|| "failed to cache the updated champions")?;
         self.champions = champions;
 
+        // The dry run lists these too, see `update::REFRESHED`
+        let mut failed = vec![];
+        self.refresh(CacheFile::Items, &version, self.fetch_items(), &mut failed)
+            .await;
+
+        self.refresh(CacheFile::Perks, &version, self.fetch_perks(), &mut failed)
+            .await;
+        self.refresh(
+            CacheFile::PerkStyles,
+            &version,
+            self.fetch_perk_styles(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(
+            CacheFile::SummonerSpells,
+            &version,
+            self.fetch_summoner_spells(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(CacheFile::Skins, &version, self.fetch_skins(), &mut failed)
+            .await;
+        self.refresh(
+            CacheFile::SkinLines,
+            &version,
+            self.fetch_skin_lines(),
+            &mut failed,
+        )
+        .await;
+        self.refresh(
+            CacheFile::Universes,
+            &version,
+            self.fetch_universes(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(
+            CacheFile::WardSkins,
+            &version,
+            self.fetch_ward_skins(),
+            &mut failed,
+        )
+        .await;
+        self.refresh(
+            CacheFile::SummonerIcons,
+            &version,
+            self.fetch_summoner_icons(),
+            &mut failed,
+        )
+        .await;
+        self.refresh(
+            CacheFile::Regalia,
+            &version,
+            self.fetch_regalia(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(
+            CacheFile::Queues,
+            &version,
+            self.fetch_queues(),
+            &mut failed,
+        )
+        .await;
+        self.refresh(CacheFile::Maps, &version, self.fetch_maps(), &mut failed)
+            .await;
+
+        self.refresh(CacheFile::Loot, &version, self.fetch_loot(), &mut failed)
+            .await;
+
+        self.refresh(
+            CacheFile::Statstones,
+            &version,
+            self.fetch_statstones(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(
+            CacheFile::MissionAssets,
+            &version,
+            self.fetch_mission_assets(),
+            &mut failed,
+        )
+        .await;
+
+        self.refresh(
+            CacheFile::Challenges,
+            &version,
+            self.fetch_challenges(),
+            &mut failed,
+        )
+        .await;
+
         self.status = Status::UpToDate;
+        progress::finished();
+        if !failed.is_empty() {
+            let failed: Vec<String> = failed.iter().map(ToString::to_string).collect();
+            return Err(eyre!(
+                "failed to update {}, the cached copies were kept",
+                failed.join(", ")
+            ));
+        }
         Ok(())
     }
 
+    pub(crate) fn is_cached(&self, catalog: CacheFile) -> bool {
+        self.cache_dir
+            .join(catalog.to_string())
+            .try_exists()
+            .unwrap_or(false)
+    }
+
+    /// Caches what `fetch` returns as `version` of `catalog`, if the catalog is cached at all.
+    /// A failure is reported and added to `failed` instead of ending the update.
+    async fn refresh<T: Serialize>(
+        &self,
+        catalog: CacheFile,
+        version: &str,
+        fetch: impl Future<Output = color_eyre::Result<T>>,
+        failed: &mut Vec<CacheFile>,
+    ) {
+        if !self.is_cached(catalog) {
+            return;
+        }
+        let res = match fetch.await {
+            Ok(obj) => self.cache_versioned(&obj, catalog, version),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = res {
+            progress::notice(format_args!(
+                "couldn't update {catalog}, keeping the cached copy: {err}"
+            ));
+            failed.push(catalog);
+        }
+    }
+
     /// Fetches the latest [`Plugin`]s from the CDragon API
     pub async fn fetch_plugins(&self) -> color_eyre::Result<Vec<Plugin>> {
-        let res = self
-            .http_client
-            .get(format!(
-                "https://raw.communitydragon.org/json/latest/plugins/"
-            ))
-            .send()
-            .await?
-            .text()
-            .await?;
-        let plugins: Vec<Plugin> = serde_json::from_str(&res)?;
-        Ok(plugins)
+        self.list_dir("latest/plugins").await
     }
 
     /// Checks when a specific [`Plugin`] was last updated via the CDragon API
@@ -243,32 +655,35 @@ impl CDragon {
             .ok_or(eyre!("couldn't find the when {name:?} was last updated"))
     }
 
-    pub async fn fetch_champion_ids(&self) -> color_eyre::Result<Vec<u64>> {
-        let res = self
-            .http_client
-            .get(format!("{GAME_DATA_URL}/{V1}/champion-summary.json"))
-            .send()
-            .await?
-            .text()
-            .await?;
-        let obj: Vec<Value> = serde_json::from_str(&res)?;
-        let champ_ids: Vec<u64> = obj
-            .iter()
+    /// Fetches the summary of every champion, skipping the placeholder "None" champion at the
+    /// start of the list.
+    pub async fn fetch_champion_summaries(&self) -> color_eyre::Result<Vec<ChampionSummary>> {
+        let res = http::get(
+            &self.http_client,
+            &format!("{GAME_DATA_URL}/{V1}/champion-summary.json"),
+        )
+        .await?;
+        let obj: Vec<Value> = parse_json(&res)?;
+        let summaries = obj
+            .into_iter()
             .skip(1)
-            .map(|v| v.get("id").unwrap().as_u64().unwrap())
-            .collect();
-        Ok(champ_ids)
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<ChampionSummary>, _>>()?;
+        Ok(summaries)
+    }
+
+    pub async fn fetch_champion_ids(&self) -> color_eyre::Result<Vec<u64>> {
+        let summaries = self.fetch_champion_summaries().await?;
+        Ok(summaries.iter().map(|summary| summary.id).collect())
     }
 
     pub async fn fetch_champion(&self, id: u64) -> color_eyre::Result<Champion> {
-        let res = self
-            .http_client
-            .get(format!("{GAME_DATA_URL}/{V1}/champions/{id}.json"))
-            .send()
-            .await?
-            .text()
-            .await?;
-        let champion = serde_json::from_str(&res)?;
+        let res = http::get(
+            &self.http_client,
+            &format!("{GAME_DATA_URL}/{V1}/champions/{id}.json"),
+        )
+        .await?;
+        let champion = parse_json(&res)?;
         Ok(champion)
     }
 
@@ -276,13 +691,12 @@ impl CDragon {
         http_client: reqwest::Client,
         id: u64,
     ) -> color_eyre::Result<Champion> {
-        let res = http_client
-            .get(format!("{GAME_DATA_URL}/{V1}/champions/{id}.json"))
-            .send()
-            .await?
-            .text()
-            .await?;
-        let champion = serde_json::from_str(&res)?;
+        let res = http::get(
+            &http_client,
+            &format!("{GAME_DATA_URL}/{V1}/champions/{id}.json"),
+        )
+        .await?;
+        let champion = parse_json(&res)?;
         Ok(champion)
     }
 
@@ -294,10 +708,12 @@ impl CDragon {
             let task = tokio::spawn(Self::fetch_champion_parallel(client, id));
             tasks.push(task);
         }
-        let mut champions = HashMap::with_capacity(tasks.len());
-        for task in tasks {
+        let total = tasks.len();
+        let mut champions = HashMap::with_capacity(total);
+        for (done, task) in tasks.into_iter().enumerate() {
             let champ = task.await??;
-            champions.insert(champ.id.clone(), champ);
+            champions.insert(champ.id, champ);
+            progress::champions(done + 1, total);
         }
         Ok(champions)
     }
@@ -308,22 +724,35 @@ impl CDragon {
         asset: &SkinAsset,
     ) -> color_eyre::Result<()> {
         let asset_path = self.skin_path_of(skin, asset)?;
-        let asset_url = format!("{GAME_DATA_URL}/{}", asset_path.to_str().unwrap());
-        let bytes = self
-            .http_client
-            .get(asset_url)
-            .send()
+        let asset_url = asset_url(asset_path.to_str().unwrap());
+        let bytes = http::get(&self.http_client, &asset_url)
             .await
-            .with_context(|| "couldn't download asset")?
-            .bytes()
-            .await?;
-        let file_path = self.data_dir.join(asset_path);
-        let mut file_dir = file_path.clone();
-        file_dir.pop();
-        create_dir_all(&file_dir)?;
-        let mut file = File::create(file_path).with_context(|| "couldn't create skin file")?;
-        io::copy(&mut bytes.as_ref(), &mut file).with_context(|| "couldn't copy bytes")?;
-        Ok(())
+            .with_context(|| "couldn't download asset")?;
+        let mut store = self.asset_store()?;
+        store
+            .put(asset_path.to_str().unwrap(), &bytes)
+            .with_context(|| "couldn't store the skin asset")?;
+        store.flush()
+    }
+
+    /// The bytes of the asset at a [Normalized Path](Skin), read from the [`AssetStore`] when
+    /// it's been downloaded before and downloaded into it otherwise.
+    pub async fn stored_asset(&self, asset_path: &str) -> color_eyre::Result<Vec<u8>> {
+        let mut store = self.asset_store()?;
+        if let Some(stored) = store.get(asset_path) {
+            return Ok(fs::read(stored)?);
+        }
+        let bytes = http::get(&self.http_client, &asset_url(asset_path))
+            .await
+            .with_context(|| format!("couldn't download {asset_path}"))?;
+        store.put(asset_path, &bytes)?;
+        store.flush()?;
+        Ok(bytes)
+    }
+
+    /// Opens the [`AssetStore`] holding every downloaded asset.
+    pub fn asset_store(&self) -> color_eyre::Result<AssetStore> {
+        AssetStore::open(self.data_dir.join(ASSETS))
     }
 
     pub fn skin_path_of(&self, skin: &Skin, asset: &SkinAsset) -> color_eyre::Result<PathBuf> {
@@ -332,268 +761,57 @@ impl CDragon {
             SkinAsset::Splash => &skin.splash_path,
             SkinAsset::LoadScreen => &skin.load_screen_path,
             SkinAsset::UncenteredSplash => &skin.uncentered_splash_path,
+            SkinAsset::VintageLoadScreen => skin
+                .load_screen_vintage_path
+                .as_ref()
+                .ok_or_else(|| eyre!("{} has no vintage loading screen", skin.name))?,
         };
         Ok(asset_path.into())
     }
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-struct TactialInfo {
-    style: u64,
-    difficulty: u64,
-    damage_type: String,
+/// Writes next to `path` then renames over it, so a reader never sees half of the file.
+pub(crate) fn write_atomically(path: &Path, contents: impl AsRef<[u8]>) -> color_eyre::Result<()> {
+    let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&temp_path, contents)?;
+    fs::rename(temp_path, path)?;
+    Ok(())
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-struct PlaystyleInfo {
-    damage: u64,
-    durability: u64,
-    crowd_control: u64,
-    mobility: u64,
-    utility: u64,
-}
-
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-pub enum Rarity {
-    KEpic,
-    KLegendary,
-    KMythic,
-    #[default]
-    KNoRarity,
-    KRare,
-    KTranscendent,
-    KUltimate,
-    KExalted,
-}
-
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-pub enum SkinType {
-    Ultimate,
-    #[default]
-    #[serde(other)]
-    None,
-}
-
-/// The information and asset paths for a [`Skin`]
-///
-///
-/// [`splash_path`] - [Normalized Path] to the splash art centered on the skin
-/// [`uncentered_splash_path`] - [Normalized Path] to normal splash art for the skin. May overlap
-/// with splash arts for skins in the same [`skin_lines`] because a single splash art includes all
-/// of the champions.
-/// tile_path
-/// load_screen_path
-///
-/// ## [Normalized Path]
-/// Paths returned by the Cdragon api's json cannot be used to directly navigate to an asset. To
-/// [Normalize] this path we strip the first two path parts and cast to lowercase.
-///
-/// This normalization will allow us to construct the actual path to the asset by doing the following:
-/// ```
-/// let cdragon = CDragon::new().await.unwrap();
-/// let akshan_skin_splash = cdragon.champions
-/// format!("{GAME_DATA_URL}/{ASSETS}/")
-/// ```
-///
-/// For example:
-///     From the Cdragon json:
-///     /lol-game-data/assets/**ASSETS**/Characters/Akshan/Skins/Base/Images/akshan_splash_uncentered_0.jpg
-///
-///     The path to the actual asset:
-///     https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/assets/characters/akshan/skins/base/images/akshan_splash_uncentered_0.jpg
-///
-///     [Normalized Path]:
-///     assets/characters/akshan/skins/base/images/akshan_splash_uncentered_0.jpg
-///
-///
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-pub struct Skin {
-    id: u64,
-    is_base: bool,
-    name: String,
-    #[serde(deserialize_with = "deserialize_asset_path")]
-    splash_path: String,
-    #[serde(deserialize_with = "deserialize_asset_path")]
-    uncentered_splash_path: String,
-    #[serde(deserialize_with = "deserialize_asset_path")]
-    tile_path: String,
-    #[serde(deserialize_with = "deserialize_asset_path")]
-    load_screen_path: String,
-    skin_type: SkinType,
-    rarity: Rarity,
-    is_legacy: bool,
-    #[serde(deserialize_with = "deserialize_skin_lines")]
-    skin_lines: Vec<u64>,
-    description: Option<String>,
+/// Deserializes JSON inside a "parse" span, see `--timing`.
+pub(crate) fn parse_json<T: DeserializeOwned>(bytes: &[u8]) -> serde_json::Result<T> {
+    let _span = tracing::info_span!("parse").entered();
+    serde_json::from_slice(bytes)
 }
 
+/// The image variants every skin can have. Each crop suits a different consumer, e.g. the
+/// centered splash for wallpapers and the tile for grids.
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[strum(serialize_all = "kebab-case")]
 pub enum SkinAsset {
+    /// Cropped around the skin's champion
+    #[default]
     Splash,
+    /// The full art, which may show other champions from the same skin line
     UncenteredSplash,
+    /// A small square crop
     Tile,
     LoadScreen,
+    /// The older style of loading screen, not every skin has one
+    VintageLoadScreen,
 }
 
-fn deserialize_asset_path<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let path = String::deserialize(deserializer)?
-        .replace("/lol-game-data/assets/ASSETS", ASSETS)
-        .to_lowercase();
-    Ok(path)
-}
-
-fn deserialize_skin_lines<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let mut res = vec![];
-    let value = serde_json::Value::deserialize(deserializer)?;
-    if value.to_string() == "null" {
-        return Ok(res);
-    }
-    // TODO: I don't love these errors, but I haven't quite figured out how to properly map them.
-    for j_struct in value.as_array().unwrap() {
-        let v = j_struct
-            .as_object()
-            .ok_or(serde::de::Error::custom("that's not an object"))?
-            .get("id")
-            .ok_or(serde::de::Error::missing_field("id"))?
-            .as_u64()
-            .ok_or(serde::de::Error::custom("that's not a u64"))?;
-        res.push(v);
-    }
-    Ok(res)
-}
-
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-pub struct SkinLine {
-    id: u64,
-    name: String,
-    description: String,
-}
+/// The champion summaries built into the binary, as of when it was built.
+#[cfg(feature = "embedded")]
+pub fn embedded_champion_summaries() -> Result<Vec<ChampionSummary>> {
+    use std::io::Read;
 
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "camelCase")]
-pub struct Champion {
-    id: u64,
-    name: String,
-    alias: String,
-    title: String,
-    short_bio: String,
-    tactical_info: TactialInfo,
-    playstyle_info: PlaystyleInfo,
-    square_portrait_path: String,
-    roles: Vec<String>,
-    skins: Vec<Skin>,
-}
-
-#[derive(Debug, Display, Default, Deserialize, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case")]
-pub enum PluginName {
-    #[default]
-    None,
-    RcpBeLolGameData,
-    RcpBeLolLicenseAgreement,
-    RcpBeSanitizer,
-    RcpFeAudio,
-    RcpFeCommonLibs,
-    RcpFeEmberLibs,
-    RcpFeLolCareerStats,
-    RcpFeLolChampSelect,
-    RcpFeLolChampionDetails,
-    RcpFeLolChampionStatistics,
-    RcpFeLolClash,
-    RcpFeLolCollections,
-    RcpFeLolEsportsSpectate,
-    RcpFeLolEventHub,
-    RcpFeLolEventShop,
-    RcpFeLolHighlights,
-    RcpFeLolHonor,
-    RcpFeLolKickout,
-    RcpFeLolL10n,
-    RcpFeLolLeagues,
-    RcpFeLolLockAndLoad,
-    RcpFeLolLoot,
-    RcpFeLolMatchHistory,
-    RcpFeLolNavigation,
-    RcpFeLolNewPlayerExperience,
-    RcpFeLolNpeRewards,
-    RcpFeLolParties,
-    RcpFeLolPaw,
-    RcpFeLolPft,
-    RcpFeLolPostgame,
-    RcpFeLolPremadeVoice,
-    RcpFeLolProfiles,
-    RcpFeLolSettings,
-    RcpFeLolSharedComponents,
-    RcpFeLolSkinsPicker,
-    RcpFeLolSocial,
-    RcpFeLolStartup,
-    RcpFeLolStaticAssets,
-    RcpFeLolStore,
-    RcpFeLolTft,
-    RcpFeLolTftTeamPlanner,
-    RcpFeLolTftTroves,
-    RcpFeLolTypekit,
-    RcpFeLolUikit,
-    RcpFeLolYourshop,
-    RcpFePluginRunner,
-    #[serde(other)]
-    PluginManifest,
-}
-
-#[derive(Display, Debug, Serialize, Deserialize)]
-enum PluginType {
-    #[serde(rename = "file")]
-    File,
-    #[serde(rename = "directory")]
-    Directory,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Plugin {
-    name: PluginName,
-    #[serde(rename = "type")]
-    ty: PluginType,
-    #[serde(with = "mtime_format")]
-    mtime: DateTime<Utc>,
-    size: Option<i32>,
-}
-
-impl Plugin {
-    pub fn updated_since(&self, date: DateTime<Utc>) -> bool {
-        self.mtime > date
-    }
-}
-
-mod mtime_format {
-    use chrono::{DateTime, NaiveDateTime, Utc};
-    use serde::{self, Deserialize, Deserializer, Serializer};
-
-    const FORMAT: &'static str = "%a, %d %b %Y %H:%M:%S %Z";
-
-    pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let s = format!("{}", date.format(FORMAT));
-        serializer.serialize_str(&s)
-    }
-
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        let dt = NaiveDateTime::parse_from_str(&s, FORMAT).map_err(serde::de::Error::custom)?;
-        Ok(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
-    }
+    let mut json = vec![];
+    flate2::read::GzDecoder::new(EMBEDDED_SUMMARIES)
+        .read_to_end(&mut json)
+        .with_context(|| "the embedded champion summaries are corrupt")?;
+    Ok(parse_json(&json)?)
 }
 
 #[cfg(test)]
@@ -614,7 +832,7 @@ mod test {
     #[tokio::test]
     async fn get_champ_ids() {
         let res = CDragon::default().fetch_champion_ids().await;
-        assert!(res.is_ok_and(|ids| ids.len() > 0))
+        assert!(res.is_ok_and(|ids| !ids.is_empty()))
     }
 
     #[tokio::test]
@@ -623,6 +841,60 @@ mod test {
         assert!(res.is_ok_and(|annie| annie.name == "Annie" && annie.playstyle_info.damage == 3))
     }
 
+    #[cfg(feature = "embedded")]
+    #[tokio::test]
+    async fn embedded_summaries() -> color_eyre::Result<()> {
+        let summaries = embedded_champion_summaries()?;
+        assert!(summaries.iter().any(|summary| summary.is_named("annie")));
+        assert!(!summaries.iter().any(|summary| summary.is_named("none")));
+
+        let dir = tempfile::tempdir()?;
+        let (now, refresh) = CDragon::in_dir(dir.path()).champion_summaries_now().await?;
+        assert_eq!(now, summaries);
+        assert!(refresh.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn json_pointer() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        assert!(cdrag.get_path(CacheFile::Champions, "/1/name").is_err());
+        let annie: Champion = serde_json::from_str(&fs::read_to_string("testing/annie.json")?)?;
+        cdrag.cache_obj(&HashMap::from([(annie.id, annie)]), CacheFile::Champions)?;
+        assert_eq!(
+            cdrag.get_path(CacheFile::Champions, "/1/skins/1/name")?,
+            "Goth Annie"
+        );
+        assert_eq!(cdrag.get_path(CacheFile::Champions, "/1/id")?, 1);
+        assert!(cdrag.get_path(CacheFile::Champions, "/1/skins/99").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn skin_variants() -> color_eyre::Result<()> {
+        let annie: Champion = serde_json::from_str(&fs::read_to_string("testing/annie.json")?)?;
+        let cdrag = CDragon::default();
+        let base = &annie.skins[0];
+        assert_eq!(
+            cdrag.skin_path_of(base, &SkinAsset::UncenteredSplash)?,
+            PathBuf::from(
+                "assets/characters/annie/skins/base/images/annie_splash_uncentered_0.jpg"
+            )
+        );
+        assert!(cdrag
+            .skin_path_of(base, &SkinAsset::VintageLoadScreen)
+            .is_err());
+        let frankentibbers = annie
+            .skins
+            .iter()
+            .find(|skin| skin.name == "FrankenTibbers Annie")
+            .unwrap();
+        let vintage = cdrag.skin_path_of(frankentibbers, &SkinAsset::VintageLoadScreen)?;
+        assert!(vintage.starts_with("assets/characters/annie/skins/"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn champs_out_of_date() -> color_eyre::Result<()> {
         let plugins = CDragon::default().fetch_plugins().await?;
@@ -650,7 +922,7 @@ mod test {
         #[future] cdrag_instance: color_eyre::Result<CDragon>,
     ) -> color_eyre::Result<()> {
         let cdrag = cdrag_instance.await?;
-        assert!(cdrag.champions.len() > 0);
+        assert!(!cdrag.champions.is_empty());
         Ok(())
     }
 
@@ -664,6 +936,41 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn refreshes_only_cached_catalogs() -> color_eyre::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        cdrag.cache_versioned(&["old"], CacheFile::Items, "14.23")?;
+        let mut failed = vec![];
+        cdrag
+            .refresh(
+                CacheFile::Perks,
+                "14.24",
+                async { Ok(["new"]) },
+                &mut failed,
+            )
+            .await;
+        assert!(cdrag.load_obj::<Value>(CacheFile::Perks).is_err());
+
+        let offline = async { Err::<[&str; 1], _>(eyre!("offline")) };
+        cdrag
+            .refresh(CacheFile::Items, "14.24", offline, &mut failed)
+            .await;
+        assert_eq!(failed, [CacheFile::Items]);
+        assert_eq!(cdrag.load_obj::<Vec<String>>(CacheFile::Items)?, ["old"]);
+
+        cdrag
+            .refresh(
+                CacheFile::Items,
+                "14.24",
+                async { Ok(["new"]) },
+                &mut failed,
+            )
+            .await;
+        assert_eq!(cdrag.load_obj::<Vec<String>>(CacheFile::Items)?, ["new"]);
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn cleanup(
@@ -0,0 +1,246 @@
+//! Challenges, the account-wide achievements with a point threshold per tier.
+
+use std::collections::{BTreeMap, HashMap};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use strum::{Display, EnumIter};
+
+use crate::{
+    cdragon::{asset_url, normalize_asset_path, parse_json, CDragon, CacheFile, Champion},
+    http,
+};
+
+#[derive(
+    Debug,
+    Display,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Deserialize,
+    Serialize,
+    EnumIter,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChallengeTier {
+    Iron,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Diamond,
+    Master,
+    Grandmaster,
+    Challenger,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct Threshold {
+    /// The points needed to reach the tier
+    pub value: f64,
+}
+
+/// What [`Challenge::available_ids`] refers to.
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IdListType {
+    #[default]
+    None,
+    Champion,
+    Skin,
+    /// Items, summoner icons and the like, which blitzadex doesn't know about
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Challenge {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub description_short: String,
+    /// e.g. "IMAGINATION" or "COLLECTION"
+    #[serde(default)]
+    pub category: String,
+    /// Ordered from Iron up. Few challenges have every tier
+    pub thresholds: BTreeMap<ChallengeTier, Threshold>,
+    #[serde(default)]
+    pub id_list_type: IdListType,
+    /// The champions or skins that count toward the challenge
+    #[serde(default)]
+    pub available_ids: Vec<u64>,
+    /// The token shown for each tier, [Normalized Path](crate::cdragon::Skin)s
+    #[serde(default, deserialize_with = "deserialize_tier_icons")]
+    pub level_to_icon_path: BTreeMap<ChallengeTier, String>,
+}
+
+fn deserialize_tier_icons<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<ChallengeTier, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let icons = BTreeMap::<ChallengeTier, String>::deserialize(deserializer)?;
+    Ok(icons
+        .into_iter()
+        .map(|(tier, path)| (tier, normalize_asset_path(&path)))
+        .collect())
+}
+
+/// Challenges keyed by id.
+pub type Challenges = BTreeMap<u64, Challenge>;
+
+/// The champions and skins a challenge is about.
+#[derive(Debug, PartialEq)]
+pub enum Related<'a> {
+    Champions(Vec<&'a Champion>),
+    /// The skin names
+    Skins(Vec<&'a str>),
+}
+
+impl Challenge {
+    /// The token of `tier`, or of the highest tier below it that has one.
+    pub fn icon(&self, tier: ChallengeTier) -> Option<&str> {
+        self.level_to_icon_path
+            .range(..=tier)
+            .next_back()
+            .map(|(_, path)| path.as_str())
+    }
+
+    /// Resolves [`Challenge::available_ids`] against `champions`, skipping unknown ids.
+    pub fn related<'a>(&self, champions: &'a HashMap<u64, Champion>) -> Option<Related<'a>> {
+        match self.id_list_type {
+            IdListType::Champion => {
+                let mut related: Vec<&Champion> = self
+                    .available_ids
+                    .iter()
+                    .filter_map(|id| champions.get(id))
+                    .collect();
+                related.sort_by(|a, b| a.name.cmp(&b.name));
+                Some(Related::Champions(related))
+            }
+            IdListType::Skin => {
+                let mut related: Vec<&str> = champions
+                    .values()
+                    .flat_map(|champ| &champ.skins)
+                    .filter(|skin| self.available_ids.contains(&skin.id))
+                    .map(|skin| skin.name.as_str())
+                    .collect();
+                related.sort();
+                Some(Related::Skins(related))
+            }
+            IdListType::None | IdListType::Other => None,
+        }
+    }
+}
+
+/// Finds a challenge by name, ignoring case, falling back to the first whose name contains
+/// `name`.
+pub fn find<'a>(challenges: &'a Challenges, name: &str) -> Option<&'a Challenge> {
+    let lowered = name.to_lowercase();
+    challenges
+        .values()
+        .find(|challenge| challenge.name.eq_ignore_ascii_case(name))
+        .or_else(|| {
+            challenges
+                .values()
+                .find(|challenge| challenge.name.to_lowercase().contains(&lowered))
+        })
+}
+
+/// Like [`find`], but an error when there's no such challenge.
+pub fn named<'a>(challenges: &'a Challenges, name: &str) -> Result<&'a Challenge> {
+    find(challenges, name).ok_or_else(|| eyre!("no challenge named {name}"))
+}
+
+/// Parses the challenges file as published, which wraps the challenges with the titles they
+/// award, or as cached, which holds only the challenges.
+fn parse(bytes: &[u8]) -> Result<Challenges> {
+    let mut file: Value = parse_json(bytes)?;
+    let challenges = match file.get_mut("challenges") {
+        Some(challenges) => challenges.take(),
+        None => file,
+    };
+    serde_json::from_value(challenges).with_context(|| "unexpected challenges file")
+}
+
+impl CDragon {
+    /// Loads the cached challenges, fetching them when they aren't cached yet.
+    pub async fn challenges(&self) -> Result<Challenges> {
+        if let Ok(challenges) = self.load_obj(CacheFile::Challenges) {
+            return Ok(challenges);
+        }
+        let challenges = self.fetch_challenges().await?;
+        self.cache_obj(&challenges, CacheFile::Challenges)?;
+        Ok(challenges)
+    }
+
+    pub async fn fetch_challenges(&self) -> Result<Challenges> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/challenges.json"))
+            .await
+            .with_context(|| "couldn't fetch the challenges")?;
+        parse(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn thresholds_and_related() -> Result<()> {
+        let challenges = parse(&std::fs::read("testing/challenges.json")?)?;
+        assert_eq!(challenges.len(), 3);
+
+        let aram = named(&challenges, "aram authority")?;
+        let tiers: Vec<ChallengeTier> = aram.thresholds.keys().copied().collect();
+        assert_eq!(
+            tiers,
+            [
+                ChallengeTier::Iron,
+                ChallengeTier::Bronze,
+                ChallengeTier::Silver,
+                ChallengeTier::Gold,
+                ChallengeTier::Challenger
+            ]
+        );
+        assert_eq!(aram.thresholds[&ChallengeTier::Silver].value, 325.);
+        assert_eq!(
+            aram.icon(ChallengeTier::Gold),
+            Some("assets/challenges/config/101000/tokens/gold.png")
+        );
+        assert_eq!(
+            aram.icon(ChallengeTier::Diamond),
+            aram.icon(ChallengeTier::Gold)
+        );
+        assert_eq!(aram.icon(ChallengeTier::Iron), None);
+
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let champions = HashMap::from([(annie.id, annie)]);
+        assert_eq!(aram.related(&champions), None);
+        let Some(Related::Champions(related)) = named(&challenges, "jack of")?.related(&champions)
+        else {
+            panic!("expected related champions");
+        };
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].name, "Annie");
+        let skins = named(&challenges, "Spirit Blossom Collector")?.related(&champions);
+        assert_eq!(skins, Some(Related::Skins(vec!["Goth Annie"])));
+
+        // The cached form parses too
+        let cached = serde_json::to_vec(&challenges)?;
+        assert_eq!(parse(&cached)?, challenges);
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+//! Terminal swatches and palette exports for skin chromas.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::cdragon::{Champion, Chroma};
+
+/// Parses a "#RRGGBB" hex code.
+pub fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// A block of each of the chroma's colors, drawn with 24-bit ANSI background colors.
+pub fn swatch(chroma: &Chroma) -> String {
+    chroma
+        .colors
+        .iter()
+        .filter_map(|hex| parse_hex(hex))
+        .map(|(r, g, b)| format!("\x1b[48;2;{r};{g};{b}m  \x1b[0m"))
+        .collect()
+}
+
+/// Lists every chroma of a champion's skins with a swatch next to its name.
+pub fn render(champion: &Champion) -> String {
+    let mut out = String::new();
+    for skin in champion
+        .skins
+        .iter()
+        .filter(|skin| !skin.chromas.is_empty())
+    {
+        let _ = writeln!(out, "{}", skin.name);
+        for chroma in &skin.chromas {
+            let _ = writeln!(
+                out,
+                "  {} {} {}",
+                swatch(chroma),
+                chroma.name,
+                chroma.colors.join(" ")
+            );
+        }
+    }
+    out
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaletteEntry<'a> {
+    pub skin: &'a str,
+    pub chroma: &'a str,
+    pub colors: &'a [String],
+}
+
+/// Every chroma color of a champion, flattened for artists to import.
+pub fn palette(champion: &Champion) -> Vec<PaletteEntry<'_>> {
+    champion
+        .skins
+        .iter()
+        .flat_map(|skin| {
+            skin.chromas.iter().map(|chroma| PaletteEntry {
+                skin: &skin.name,
+                chroma: &chroma.name,
+                colors: &chroma.colors,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn hex_codes() {
+        assert_eq!(parse_hex("#D33528"), Some((0xd3, 0x35, 0x28)));
+        assert_eq!(parse_hex("d33528"), Some((0xd3, 0x35, 0x28)));
+        assert_eq!(parse_hex("#D335"), None);
+        assert_eq!(parse_hex("#GG3528"), None);
+    }
+
+    #[test]
+    fn annie_palette() -> color_eyre::Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let palette = palette(&annie);
+        let lunar = palette
+            .iter()
+            .find(|entry| entry.chroma == "Lunar Beast Annie")
+            .unwrap();
+        assert_eq!(lunar.colors, ["#D33528", "#D33528"]);
+        Ok(())
+    }
+}
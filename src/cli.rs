@@ -0,0 +1,1542 @@
+use std::{
+    fmt::Write,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use clap::{Parser, Subcommand};
+use color_eyre::eyre::{eyre, Result};
+use tracing_subscriber::{prelude::*, Registry};
+
+#[cfg(unix)]
+use crate::daemon;
+use crate::{
+    announce, archetype,
+    audio::AudioFormat,
+    backfill::{self, RateLimit},
+    card,
+    cdragon::{CDragon, CacheFile, Champion, ChampionSummary, Skin, SkinAsset},
+    challenges::{self, Related},
+    chroma, codegen,
+    community::Position,
+    compare,
+    dex::{self, Order},
+    game_data::{self, SpellRef},
+    list, manifest, names, pack, patches,
+    profile::Completion,
+    progress::ProgressBar,
+    query::Query,
+    random::{self, Damage},
+    range, recommend, selftest,
+    similarity::{self, Metric},
+    status,
+    tft::{self, TeamPlan},
+    timing::Timings,
+    trend, update, whatis,
+};
+#[cfg(feature = "image")]
+use crate::{
+    collage::Layout,
+    mastery, skins,
+    thumbnail::{Derivative, ImageFormat},
+    tierlist,
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "A League of Legends champion dex built on CommunityDragon"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print how long the command spent on the network, parsing, cache I/O and rendering
+    #[arg(long, global = true)]
+    timing: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Cluster the roster into archetypes by playstyle
+    Archetypes {
+        /// How many archetypes to split the roster into
+        #[arg(short, default_value_t = 6)]
+        k: usize,
+        /// Print the clusters as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Inspect the downloaded asset store
+    Assets {
+        #[command(subcommand)]
+        action: AssetsAction,
+    },
+    /// Cache catalogs of older patches, picking up where an interrupted backfill stopped
+    Backfill {
+        /// The first patch, e.g. 13.1
+        #[arg(long)]
+        from: String,
+        /// The last patch, the latest by default
+        #[arg(long)]
+        to: Option<String>,
+        /// Which catalogs to cache for each patch
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "champions")]
+        catalog: Vec<CacheFile>,
+        /// How long to wait between requests, in milliseconds
+        #[arg(long, default_value_t = 250)]
+        delay: u64,
+    },
+    /// Show a challenge's tiers and the champions or skins it counts
+    Challenge { name: String },
+    /// Composite the splash art of a skin line into a banner image
+    #[cfg(feature = "image")]
+    Collage {
+        /// The skin line, or part of its name, e.g. "project"
+        #[arg(long)]
+        skinline: String,
+        #[arg(long)]
+        out: PathBuf,
+        /// How many splashes to put side by side
+        #[arg(long, default_value_t = 4)]
+        columns: u32,
+        /// The width of each splash in pixels
+        #[arg(long, default_value_t = 480)]
+        tile_width: u32,
+    },
+    /// Save a mastery crest, or a champion's portrait with the crest over it
+    #[cfg(feature = "image")]
+    Mastery {
+        /// Leave out to save only the crest
+        champion: Option<String>,
+        #[arg(long, default_value_t = mastery::MAX_LEVEL)]
+        level: u32,
+        #[arg(long)]
+        out: PathBuf,
+        /// The width of the portrait in pixels
+        #[arg(long, default_value_t = 256)]
+        size: u32,
+    },
+    /// Generate a Rust source file with a static array of champion summaries
+    Codegen {
+        /// Write the source here instead of printing it
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Keep the champions loaded and answer other blitzadex commands' queries
+    #[cfg(unix)]
+    Daemon,
+    /// Show champions side by side: roles, tactical info and playstyle ratings
+    Compare {
+        #[arg(required = true, num_args = 2..)]
+        champions: Vec<String>,
+    },
+    /// Compare two abilities rank by rank as a Markdown table, e.g. `compare-spells annie:q ahri:w`
+    CompareSpells {
+        #[arg(value_name = "CHAMPION:KEY")]
+        a: SpellRef,
+        #[arg(value_name = "CHAMPION:KEY")]
+        b: SpellRef,
+    },
+    /// Show a champion's chromas with color swatches
+    Chromas {
+        champion: String,
+        /// Write the chroma colors to a palette JSON file instead
+        #[arg(long)]
+        palette: Option<PathBuf>,
+    },
+    /// Show the dex completion grid, or track progress on a champion
+    Dex {
+        #[command(subcommand)]
+        action: Option<DexAction>,
+        /// Only show champions with this tag
+        #[arg(long, global = true)]
+        tag: Option<String>,
+        /// Only show champions played in this lane
+        #[arg(long, value_enum, global = true)]
+        position: Option<Position>,
+        /// The order to show the dex in, defaults to `order` under `[dex]` in blitzadex.toml
+        #[arg(long, value_enum, global = true)]
+        order: Option<Order>,
+    },
+    /// Download a champion's skin art into the asset store
+    Download {
+        champion: String,
+        /// Only download this skin, by name
+        #[arg(long)]
+        skin: Option<String>,
+        /// Which crop of the art to download
+        #[arg(long, value_enum, default_value_t)]
+        variant: SkinAsset,
+        /// Also make a copy at most this many pixels wide
+        #[cfg(feature = "image")]
+        #[arg(long, value_name = "WIDTH")]
+        resize: Option<u32>,
+        /// Also make a copy in this format, PNG unless given
+        #[cfg(feature = "image")]
+        #[arg(long, value_enum)]
+        format: Option<ImageFormat>,
+    },
+    /// Save a champion's pick and ban voice lines and stinger sound, e.g. as alias-choose.ogg
+    Audio {
+        champion: String,
+        /// MP3 and WAV are transcoded with ffmpeg
+        #[arg(long, value_enum, default_value_t)]
+        format: AudioFormat,
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// Print a table of a cached catalog, without going online
+    List {
+        #[command(subcommand)]
+        catalog: ListCatalog,
+    },
+    /// List or download the esports spectate art, e.g. team logos
+    Esports {
+        #[command(subcommand)]
+        action: EsportsAction,
+    },
+    /// List the recorded versions of a catalog, or show a champion as it was at one of them
+    History {
+        #[arg(value_enum, default_value_t = CacheFile::Champions)]
+        catalog: CacheFile,
+        #[arg(long)]
+        champion: Option<String>,
+        #[arg(long, requires = "champion")]
+        as_of: Option<String>,
+    },
+    /// Print a JSON manifest of every known asset of a champion, with URLs and cache status
+    Manifest { champion: String },
+    /// Show a champion's card: title, bio, playstyle, difficulty and asset URLs. Takes a name,
+    /// alias or id
+    Show { champion: String },
+    /// Find champions with a query like `role:support dmg:magic diff:<2 skinline:"star guardian"`,
+    /// best matches first. Names are matched fuzzily, so `jinks` finds Jinx
+    Search {
+        #[arg(required = true)]
+        query: Vec<String>,
+        /// Only find champions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Look up a champion by name using only the summary index
+    Quick {
+        name: String,
+        /// Also load the champion's details
+        #[arg(long)]
+        detail: bool,
+    },
+    /// Build or fetch shareable data packs
+    Pack {
+        #[command(subcommand)]
+        action: PackAction,
+    },
+    /// List the patches CommunityDragon has versioned data for, marking the cached ones
+    Patches {
+        /// Print the patches as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Pick random champions for a custom game
+    Random {
+        /// Only pick champions with this role, a class like mage or a lane like mid
+        #[arg(long)]
+        role: Option<String>,
+        /// Only pick champions that mostly deal this kind of damage
+        #[arg(long, value_enum)]
+        damage: Option<Damage>,
+        /// Only pick champions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Never pick this champion, can be repeated
+        #[arg(long, value_name = "CHAMPION")]
+        exclude: Vec<String>,
+        /// How many champions to pick
+        #[arg(long, short = 'n', default_value_t = 1, conflicts_with_all = ["team", "aram"])]
+        count: usize,
+        /// Pick a champion for each lane of a team of five
+        #[arg(long)]
+        team: bool,
+        /// Pick two teams of five for an ARAM, nobody on both
+        #[arg(long)]
+        aram: bool,
+        /// Seed the picks, to draw the same ones again
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+    /// Chart a champion's spell ranges against familiar distances
+    Range { champion: String },
+    /// Suggest champions to pick up
+    Recommend {
+        /// Suggest easy picks for someone new to the game
+        #[arg(
+            long,
+            required_unless_present = "arena_duo",
+            conflicts_with = "arena_duo"
+        )]
+        new_player: bool,
+        /// Suggest Arena duo partners that complement this champion
+        #[arg(long, value_name = "CHAMPION")]
+        arena_duo: Option<String>,
+        /// Only suggest champions with this class role or played in this lane, e.g. tank or top
+        #[arg(long)]
+        role: Option<String>,
+        /// Only suggest champions played in this lane
+        #[arg(long, value_enum)]
+        position: Option<Position>,
+        /// Only suggest champions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// How many champions to suggest
+        #[arg(long, short = 'n', default_value_t = 5)]
+        count: usize,
+    },
+    /// Check that CommunityDragon is reachable and the cache works, without touching your cache
+    Selftest,
+    /// Show how fresh each cached catalog is compared to CommunityDragon
+    Status {
+        /// Don't check CommunityDragon, only show what's cached: entities and size per catalog,
+        /// and the game data's version
+        #[arg(long)]
+        offline: bool,
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Find the champions that play most like another one
+    Similar {
+        champion: String,
+        #[arg(long, value_enum, default_value_t)]
+        metric: Metric,
+        /// How many champions to show
+        #[arg(long, short = 'n', default_value_t = 5)]
+        count: usize,
+    },
+    /// Manage your own tags on champions
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Share a tier list of champions, by default one of your dex progress
+    #[cfg(feature = "image")]
+    Tierlist {
+        #[arg(long, value_enum, default_value_t)]
+        render: tierlist::Format,
+        /// Where to write the image
+        #[arg(required_if_eq("render", "png"))]
+        out: Option<PathBuf>,
+        /// A JSON file mapping the tiers S, A, B and C to champion names
+        #[arg(long)]
+        tiers: Option<PathBuf>,
+    },
+    /// Chart how a field of a champion changed across the backfilled patches
+    Trend {
+        champion: String,
+        /// The field's path in the champion data, e.g. playstyleInfo.damage
+        #[arg(long)]
+        field: String,
+        /// Print the values as CSV instead
+        #[arg(long)]
+        csv: bool,
+    },
+    /// Teamfight Tactics tools
+    Tft {
+        #[command(subcommand)]
+        action: TftAction,
+    },
+    /// Fetch the latest data from CommunityDragon into the cache
+    Update {
+        /// Only report what would be fetched, without downloading anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Wait for an update already in progress to finish, instead of failing
+        #[arg(long)]
+        wait: bool,
+        /// Write an announcement for each champion new to the roster into this directory
+        #[arg(long, value_name = "DIR")]
+        announce: Option<PathBuf>,
+        /// Also fetch the TFT champions, traits, items, augments, companions and arena skins
+        #[arg(long)]
+        tft: bool,
+        /// Also fetch the Arena augments
+        #[arg(long)]
+        arena: bool,
+        /// Also fetch the champion names of this locale, e.g. ko_kr, so searches match them.
+        /// Locales fetched before are kept up to date
+        #[arg(long)]
+        locale: Vec<String>,
+    },
+    /// Fill the cache ahead of time, by a profile from blitzadex.toml or a builtin one: minimal,
+    /// full or assets
+    Warm {
+        #[arg(long, default_value = "minimal")]
+        profile: String,
+    },
+    /// Find the champion, skin or chroma an asset path or CDN URL belongs to
+    Whatis { path: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum AssetsAction {
+    /// Rehash every downloaded asset and report any that are corrupt
+    Verify,
+    /// Record the current set of downloaded assets under a name, e.g. a patch
+    Snapshot { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum DexAction {
+    /// Mark a champion as played or mastered
+    Mark {
+        champion: String,
+        #[arg(value_enum, default_value_t = Completion::Played)]
+        completion: Completion,
+    },
+    /// Clear any progress on a champion
+    Unmark { champion: String },
+}
+
+#[derive(Debug, Subcommand)]
+enum ListCatalog {
+    /// The champions with their id, title, roles and damage type
+    Champions {
+        #[arg(long, value_enum, default_value_t)]
+        sort: list::Sort,
+        /// Only list champions with this role, e.g. mage
+        #[arg(long)]
+        role: Option<String>,
+        /// Only list champions with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// List the champions in a dex order instead, e.g. by release
+        #[arg(long, value_enum, conflicts_with = "sort")]
+        order: Option<Order>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum EsportsAction {
+    /// List the images, with their sizes
+    List {
+        /// Only list images whose path contains this, e.g. teams
+        #[arg(long)]
+        filter: Option<String>,
+    },
+    /// Download the images into the asset store
+    Download {
+        /// Only download images whose path contains this, e.g. teams
+        #[arg(long)]
+        filter: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PackAction {
+    /// Bundle cached catalogs into a compressed, versioned pack
+    Build {
+        #[arg(default_value = "blitzadex-pack.tar.gz")]
+        out: PathBuf,
+        /// Catalogs to include, defaults to every cached one
+        #[arg(long, value_enum, value_delimiter = ',')]
+        catalog: Vec<CacheFile>,
+    },
+    /// Download a pack published by someone else into the cache
+    Fetch {
+        url: String,
+        /// Wait for an update already in progress to finish, instead of failing
+        #[arg(long)]
+        wait: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TftAction {
+    /// Read or write the team planner's share codes
+    Plan {
+        #[command(subcommand)]
+        action: PlanAction,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum PlanAction {
+    /// List the units in a team planner code
+    Import { code: String },
+    /// Build a team planner code from unit names, e.g. `export jinx vi`
+    Export {
+        #[arg(required = true)]
+        units: Vec<String>,
+        /// The set the units are from, defaults to the latest one
+        #[arg(long)]
+        set: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum TagAction {
+    /// Tag a champion, e.g. `tag add akali pocket-pick`
+    Add { champion: String, tag: String },
+    /// Remove a tag from a champion
+    Remove { champion: String, tag: String },
+    /// List the tags on a champion, or every tag when no champion is given
+    List { champion: Option<String> },
+}
+
+impl Cli {
+    pub async fn run(self) -> Result<()> {
+        let timings = self.timing.then(Timings::default);
+        let progress = ProgressBar::new(self.command.shows_progress());
+        let subscriber = Registry::default()
+            .with(timings.clone())
+            .with(progress.clone());
+        tracing::subscriber::set_global_default(subscriber)?;
+        let start = Instant::now();
+        let result = match self.command.is_query() {
+            true => query(self.command).await,
+            false => self.command.run().await,
+        };
+        progress.clear();
+        if let Some(timings) = timings {
+            eprint!("{}", timings.report(start.elapsed()));
+        }
+        result
+    }
+}
+
+/// Runs a query through the daemon when one is running, or loads the catalogs and answers it
+/// here otherwise.
+async fn query(command: Command) -> Result<()> {
+    #[cfg(unix)]
+    if let Some(output) = daemon::ask(&std::env::args().skip(1).collect::<Vec<_>>())? {
+        print!("{output}");
+        return Ok(());
+    }
+    print!("{}", command.answer(&CDragon::new().await?)?);
+    Ok(())
+}
+
+impl Command {
+    /// Whether the command draws its progress, which only an update does and only when stderr
+    /// is a terminal.
+    fn shows_progress(&self) -> bool {
+        matches!(self, Command::Update { dry_run: false, .. }) && std::io::stderr().is_terminal()
+    }
+
+    /// Whether the command only reads the loaded catalogs, so a daemon can answer it.
+    fn is_query(&self) -> bool {
+        matches!(
+            self,
+            Command::Archetypes { .. }
+                | Command::Chromas { palette: None, .. }
+                | Command::Compare { .. }
+                | Command::Manifest { .. }
+                | Command::Random { .. }
+                | Command::Range { .. }
+                | Command::Recommend { .. }
+                | Command::Show { .. }
+                | Command::Similar { .. }
+                | Command::Whatis { .. }
+        )
+    }
+
+    /// Renders the output of a query, see [`Command::is_query`].
+    fn answer(&self, cdrag: &CDragon) -> Result<String> {
+        let mut out = String::new();
+        match self {
+            Command::Archetypes { k, json } => {
+                let clusters = archetype::cluster(&cdrag.champions, *k);
+                if *json {
+                    writeln!(out, "{}", serde_json::to_string_pretty(&clusters)?)?;
+                } else {
+                    out.push_str(&archetype::render(&clusters));
+                }
+            }
+            Command::Chromas {
+                champion,
+                palette: None,
+            } => out.push_str(&chroma::render(find_champion(cdrag, champion)?)),
+            Command::Compare { champions } => {
+                let champions = champions
+                    .iter()
+                    .map(|name| find_champion(cdrag, name))
+                    .collect::<Result<Vec<_>>>()?;
+                out.push_str(&compare::render(&champions));
+            }
+            Command::Manifest { champion } => {
+                let champ = find_champion(cdrag, champion)?;
+                let manifest = manifest::build(champ, &cdrag.asset_store()?, cdrag.provenance());
+                writeln!(out, "{}", serde_json::to_string_pretty(&manifest)?)?;
+            }
+            Command::Range { champion } => {
+                out.push_str(&range::render_chart(find_champion(cdrag, champion)?));
+            }
+            Command::Random {
+                role,
+                damage,
+                tag,
+                exclude,
+                count,
+                team,
+                aram,
+                seed,
+            } => {
+                let filters = random::Filters {
+                    role: role
+                        .as_deref()
+                        .map(|role| check_role(cdrag, role))
+                        .transpose()?,
+                    damage: *damage,
+                    exclude: exclude
+                        .iter()
+                        .map(|name| find_champion(cdrag, name).map(|champ| champ.id))
+                        .collect::<Result<_>>()?,
+                };
+                let tagged = tag_filter(cdrag, tag.as_deref())?;
+                out = pick_random(cdrag, &filters, tagged, *count, *team, *aram, *seed)?;
+            }
+            Command::Recommend {
+                arena_duo,
+                role,
+                position,
+                tag,
+                count,
+                ..
+            } => {
+                out = recommend(
+                    cdrag,
+                    arena_duo.as_deref(),
+                    role.as_deref(),
+                    *position,
+                    tag.as_deref(),
+                    *count,
+                )?
+            }
+            Command::Show { champion } => {
+                let champ = find_champion(cdrag, champion)?;
+                let number =
+                    dex::number_of(cdrag.champions.keys().copied(), champ.id).unwrap_or_default();
+                out.push_str(&card::render(champ, number));
+            }
+            Command::Similar {
+                champion,
+                metric,
+                count,
+            } => {
+                let target = find_champion(cdrag, champion)?;
+                for (champ, score) in
+                    similarity::most_similar(target, &cdrag.champions, *metric, *count)
+                {
+                    writeln!(out, "{score:.3} {}", champ.name)?;
+                }
+            }
+            Command::Whatis { path } => {
+                let owner = whatis::what_is(&cdrag.champions, path)
+                    .ok_or_else(|| eyre!("no champion, skin or chroma owns {path}"))?;
+                writeln!(out, "{owner}")?;
+                if let Some(hash) = cdrag.asset_store()?.hash_of(&whatis::normalize(path)) {
+                    writeln!(out, "downloaded, sha256 {hash}")?;
+                }
+            }
+            _ => return Err(eyre!("only queries can be answered from loaded catalogs")),
+        }
+        Ok(out)
+    }
+
+    async fn run(self) -> Result<()> {
+        match self {
+            Command::Assets { action } => assets(&CDragon::lazy(), action),
+            Command::Challenge { name } => challenge(&CDragon::new().await?, &name).await,
+            #[cfg(feature = "image")]
+            Command::Collage {
+                skinline,
+                out,
+                columns,
+                tile_width,
+            } => {
+                let cdrag = CDragon::lazy();
+                let skin_line =
+                    skins::skin_line_named(&cdrag.skin_lines().await?, &skinline)?.clone();
+                let catalog = cdrag.skins().await?;
+                let members = skins::in_skin_line(&catalog, skin_line.id);
+                if members.is_empty() {
+                    return Err(eyre!("{} has no skins", skin_line.name));
+                }
+                let layout = Layout::with_width(columns, tile_width);
+                cdrag.collage(&members, &layout).await?.save(&out)?;
+                println!(
+                    "{} {} skins written to {}",
+                    members.len(),
+                    skin_line.name,
+                    out.display()
+                );
+                Ok(())
+            }
+            #[cfg(feature = "image")]
+            Command::Mastery {
+                champion,
+                level,
+                out,
+                size,
+            } => {
+                let widget = match champion {
+                    Some(champion) => {
+                        let cdrag = CDragon::new().await?;
+                        let champ = find_champion(&cdrag, &champion)?;
+                        cdrag.mastery_overlay(champ, level, size).await?
+                    }
+                    None => CDragon::lazy().mastery_crest(level).await?,
+                };
+                widget.save(&out)?;
+                println!("written to {}", out.display());
+                Ok(())
+            }
+            Command::Codegen { out } => {
+                let cdrag = CDragon::lazy();
+                let summaries = cdrag.champion_summaries().await?;
+                let source = codegen::champion_summaries(&summaries, &cdrag.provenance());
+                match out {
+                    Some(path) => std::fs::write(path, source)?,
+                    None => print!("{source}"),
+                }
+                Ok(())
+            }
+            Command::Chromas {
+                champion,
+                palette: Some(path),
+            } => {
+                let cdrag = CDragon::new().await?;
+                let champ = find_champion(&cdrag, &champion)?;
+                let ser = serde_json::to_string_pretty(&chroma::palette(champ))?;
+                std::fs::write(&path, ser)?;
+                println!("palette written to {}", path.display());
+                Ok(())
+            }
+            #[cfg(unix)]
+            Command::Daemon => {
+                let daemon = daemon::Daemon::bind().await?;
+                println!("listening on {}", daemon.path().display());
+                daemon
+                    .serve(|cdrag, args| {
+                        let cli = Cli::try_parse_from(
+                            std::iter::once("blitzadex".to_string()).chain(args),
+                        )?;
+                        cli.command.answer(cdrag)
+                    })
+                    .await
+            }
+            Command::CompareSpells { a, b } => compare_spells(&CDragon::lazy(), [a, b]).await,
+            Command::Dex {
+                action,
+                tag,
+                position,
+                order,
+            } => dex(&CDragon::new().await?, action, tag, position, order),
+            Command::Download {
+                champion,
+                skin,
+                variant,
+                #[cfg(feature = "image")]
+                resize,
+                #[cfg(feature = "image")]
+                format,
+            } => {
+                let cdrag = CDragon::new().await?;
+                #[cfg_attr(not(feature = "image"), allow(unused_variables))]
+                let downloaded = download(&cdrag, &champion, skin, variant).await?;
+                // Only make copies when asked for one
+                #[cfg(feature = "image")]
+                if resize.is_some() || format.is_some() {
+                    let derivative = Derivative {
+                        width: resize,
+                        format: format.unwrap_or_default(),
+                    };
+                    derive_copies(&cdrag, &downloaded, variant, &derivative).await?;
+                }
+                Ok(())
+            }
+            Command::Audio {
+                champion,
+                format,
+                out,
+            } => audio(&CDragon::new().await?, &champion, format, &out).await,
+            Command::Esports { action } => esports(&CDragon::lazy(), action).await,
+            Command::List {
+                catalog:
+                    ListCatalog::Champions {
+                        sort,
+                        role,
+                        tag,
+                        order,
+                    },
+            } => {
+                let cdrag = CDragon::lazy();
+                let mut champions = cdrag.cached_champions()?;
+                let tagged = tag_filter(&cdrag, tag.as_deref())?;
+                champions.retain(|_, champion| tagged(champion));
+                let mut listed = list::champions(&champions, sort, role.as_deref());
+                if let Some(order) = order {
+                    listed.sort_by(|a, b| order.compare(a, b));
+                }
+                print!("{}", list::render_champions(&listed));
+                Ok(())
+            }
+            Command::Search { query, tag } => {
+                search(&CDragon::new().await?, &query.join(" "), tag.as_deref()).await
+            }
+            Command::Quick { name, detail } => quick(&CDragon::lazy(), &name, detail).await,
+            Command::History {
+                catalog,
+                champion,
+                as_of,
+            } => history(&CDragon::new().await?, catalog, champion, as_of),
+            Command::Backfill {
+                from,
+                to,
+                catalog,
+                delay,
+            } => backfill(&from, to.as_deref(), &catalog, delay).await,
+            Command::Pack { action } => pack(action).await,
+            Command::Patches { json } => {
+                let branches = CDragon::lazy().patches().await?;
+                match json {
+                    true => println!("{}", serde_json::to_string_pretty(&branches)?),
+                    false => print!("{}", patches::render(&branches)),
+                }
+                Ok(())
+            }
+            Command::Selftest => {
+                let steps = selftest::run().await;
+                for step in &steps {
+                    println!("{:<18}{}", step.name, step.outcome);
+                }
+                match steps.iter().all(selftest::Step::passed) {
+                    true => Ok(()),
+                    false => Err(eyre!("selftest failed")),
+                }
+            }
+            Command::Status { offline, json } => {
+                let cdrag = CDragon::lazy();
+                if offline {
+                    let status = cdrag.cache_status().await?;
+                    match json {
+                        true => println!("{}", serde_json::to_string_pretty(&status)?),
+                        false => print!("{}", status::render(&status)),
+                    }
+                } else {
+                    let freshness = cdrag.freshness().await?;
+                    match json {
+                        true => println!("{}", serde_json::to_string_pretty(&freshness)?),
+                        false => print!("{}", status::render_freshness(&freshness)),
+                    }
+                }
+                Ok(())
+            }
+            Command::Tag { action } => tag(&CDragon::new().await?, action),
+            #[cfg(feature = "image")]
+            Command::Tierlist { render, out, tiers } => {
+                tier_list(&CDragon::new().await?, render, out, tiers).await
+            }
+            Command::Trend {
+                champion,
+                field,
+                csv,
+            } => trend(&CDragon::new().await?, &champion, &field, csv),
+            Command::Tft {
+                action: TftAction::Plan { action },
+            } => team_plan(&CDragon::lazy(), action).await,
+            Command::Update { dry_run: true, .. } => {
+                let plan = CDragon::lazy().plan_update().await?;
+                print!("{}", update::render(&plan));
+                Ok(())
+            }
+            Command::Update {
+                dry_run: false,
+                wait,
+                announce,
+                tft,
+                arena,
+                locale,
+            } => update(wait, announce, tft, arena, &locale).await,
+            Command::Warm { profile } => {
+                let cdrag = CDragon::lazy();
+                for target in cdrag.warm_profile(&profile)? {
+                    cdrag.warm(target).await?;
+                    println!("warmed {target}");
+                }
+                Ok(())
+            }
+            Command::Archetypes { .. }
+            | Command::Chromas { palette: None, .. }
+            | Command::Compare { .. }
+            | Command::Manifest { .. }
+            | Command::Random { .. }
+            | Command::Range { .. }
+            | Command::Recommend { .. }
+            | Command::Show { .. }
+            | Command::Similar { .. }
+            | Command::Whatis { .. } => query(self).await,
+        }
+    }
+}
+
+/// Finds a champion by its name, alias or nickname, or by its id.
+fn find_champion<'a>(cdrag: &'a CDragon, name: &str) -> Result<&'a Champion> {
+    cdrag
+        .champion_by_name(name)
+        .or_else(|| cdrag.champions.get(&name.parse().ok()?))
+        .ok_or_else(|| eyre!("no champion named {name}"))
+}
+
+fn assets(cdrag: &CDragon, action: AssetsAction) -> Result<()> {
+    let store = cdrag.asset_store()?;
+    match action {
+        AssetsAction::Verify => {
+            let corrupt = store.verify()?;
+            for path in &corrupt {
+                println!("corrupt: {path}");
+            }
+            println!(
+                "{} of {} assets intact",
+                store.entries().count() - corrupt.len(),
+                store.entries().count()
+            );
+        }
+        AssetsAction::Snapshot { name } => {
+            let path = store.snapshot(&name)?;
+            println!("snapshot written to {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "image")]
+async fn tier_list(
+    cdrag: &CDragon,
+    render: tierlist::Format,
+    out: Option<PathBuf>,
+    tiers: Option<PathBuf>,
+) -> Result<()> {
+    let tiers = match tiers {
+        Some(path) => tierlist::read(&path)?,
+        None => tierlist::from_profile(&cdrag.champions, &cdrag.profile()?),
+    };
+    match (render, out) {
+        (tierlist::Format::Png, Some(out)) => {
+            cdrag.render_tierlist(&tiers).await?.save(&out)?;
+            println!("tier list written to {}", out.display());
+        }
+        _ => print!("{}", tierlist::markdown(&tiers)),
+    }
+    Ok(())
+}
+
+fn dex(
+    cdrag: &CDragon,
+    action: Option<DexAction>,
+    tag: Option<String>,
+    position: Option<Position>,
+    order: Option<Order>,
+) -> Result<()> {
+    let mut profile = cdrag.profile()?;
+    match action {
+        None => {
+            let mut entries = dex::entries(&cdrag.champions, &profile);
+            if let Some(tag) = tag {
+                entries.retain(|entry| profile.has_tag(entry.champion.id, &tag));
+            }
+            if let Some(position) = position {
+                entries.retain(|entry| entry.champion.plays(position));
+            }
+            let order = match order {
+                Some(order) => order,
+                None => cdrag.dex_order()?,
+            };
+            dex::sort(&mut entries, order);
+            print!("{}", dex::render_grid(&entries));
+        }
+        Some(DexAction::Mark {
+            champion,
+            completion,
+        }) => {
+            let champ = find_champion(cdrag, &champion)?;
+            profile.mark(champ.id, completion);
+            cdrag.save_profile(&profile)?;
+            let number =
+                dex::number_of(cdrag.champions.keys().copied(), champ.id).unwrap_or_default();
+            println!("#{number:03} {} marked {completion}", champ.name);
+        }
+        Some(DexAction::Unmark { champion }) => {
+            let champ = find_champion(cdrag, &champion)?;
+            profile.unmark(champ.id);
+            cdrag.save_profile(&profile)?;
+            println!("{} unmarked", champ.name);
+        }
+    }
+    Ok(())
+}
+
+/// Downloads a champion's skin art and returns the skins that had it.
+async fn download<'a>(
+    cdrag: &'a CDragon,
+    champion: &str,
+    skin: Option<String>,
+    variant: SkinAsset,
+) -> Result<Vec<&'a Skin>> {
+    let champ = find_champion(cdrag, champion)?;
+    let skins: Vec<_> = champ
+        .skins
+        .iter()
+        .filter(|s| {
+            skin.as_ref()
+                .is_none_or(|name| s.name.eq_ignore_ascii_case(name))
+        })
+        .collect();
+    if skins.is_empty() {
+        return Err(eyre!(
+            "{} has no skin named {}",
+            champ.name,
+            skin.unwrap_or_default()
+        ));
+    }
+    let mut downloaded = vec![];
+    for skin in skins {
+        // Only some skins have every variant, e.g. the vintage loading screen
+        if skin.load_screen_vintage_path.is_none() && variant == SkinAsset::VintageLoadScreen {
+            println!("{}: no {variant}, skipped", skin.name);
+            continue;
+        }
+        cdrag.download_skin_asset(skin, &variant).await?;
+        println!("{}: {variant}", skin.name);
+        downloaded.push(skin);
+    }
+    Ok(downloaded)
+}
+
+/// Makes a resized or re-encoded copy of the downloaded art of each of `skins`.
+#[cfg(feature = "image")]
+async fn derive_copies(
+    cdrag: &CDragon,
+    skins: &[&Skin],
+    variant: SkinAsset,
+    derivative: &Derivative,
+) -> Result<()> {
+    for skin in skins {
+        let asset_path = cdrag.skin_path_of(skin, &variant)?;
+        let asset_path = asset_path.to_string_lossy();
+        cdrag.derived_asset(&asset_path, derivative).await?;
+        println!(
+            "{}: copy at {}",
+            skin.name,
+            derivative.store_path(&asset_path)
+        );
+    }
+    Ok(())
+}
+
+async fn audio(cdrag: &CDragon, champion: &str, format: AudioFormat, out: &Path) -> Result<()> {
+    let champ = find_champion(cdrag, champion)?;
+    let sounds = [
+        ("choose", &champ.choose_vo_path),
+        ("ban", &champ.ban_vo_path),
+        ("stinger", &champ.stinger_sfx_path),
+    ];
+    std::fs::create_dir_all(out)?;
+    for (kind, asset) in sounds {
+        if asset.is_empty() {
+            continue;
+        }
+        let bytes = cdrag.audio(asset, format).await?;
+        let file = out.join(format!(
+            "{}-{kind}.{}",
+            champ.alias.to_lowercase(),
+            format.extension()
+        ));
+        std::fs::write(&file, bytes)?;
+        println!("{}", file.display());
+    }
+    Ok(())
+}
+
+async fn esports(cdrag: &CDragon, action: EsportsAction) -> Result<()> {
+    let (EsportsAction::List { filter } | EsportsAction::Download { filter }) = &action;
+    let filter = filter.as_deref().unwrap_or_default().to_lowercase();
+    let assets: Vec<_> = cdrag
+        .esports_assets()
+        .await?
+        .into_iter()
+        .filter(|asset| asset.path.to_lowercase().contains(&filter))
+        .collect();
+    match action {
+        EsportsAction::List { .. } => {
+            for asset in &assets {
+                let size = asset.size.map(|size| size.to_string()).unwrap_or_default();
+                println!("{size:>10}  {}", asset.path);
+            }
+        }
+        EsportsAction::Download { .. } => {
+            let mut store = cdrag.asset_store()?;
+            for asset in &assets {
+                cdrag.download_esports_asset(&mut store, asset).await?;
+                println!("{}", asset.store_path());
+            }
+            store.flush()?;
+            println!("downloaded {} images", assets.len());
+        }
+    }
+    Ok(())
+}
+
+async fn update(
+    wait: bool,
+    announce: Option<PathBuf>,
+    tft: bool,
+    arena: bool,
+    locales: &[String],
+) -> Result<()> {
+    let mut cdrag = CDragon::lazy();
+    cdrag.wait_for_lock = wait;
+    // Without a previous roster every champion would look new
+    let before: Option<Vec<ChampionSummary>> = cdrag.load_obj(CacheFile::ChampionSummaries).ok();
+    cdrag.update().await?;
+    println!(
+        "updated to patch {}, {} champions cached",
+        cdrag.patch_version().await?.patch(),
+        cdrag.champions.len()
+    );
+    if tft {
+        cdrag.update_tft().await?;
+        println!("TFT data cached");
+    }
+    if arena {
+        cdrag.update_arena().await?;
+        println!("Arena augments cached");
+    }
+    let locales = cdrag.update_names(locales).await?;
+    if !locales.is_empty() {
+        println!("champion names cached for {}", locales.join(", "));
+    }
+    let Some(before) = before else {
+        return Ok(());
+    };
+    let after = cdrag.champion_summaries().await?;
+    for summary in announce::new_champions(&before, &after) {
+        println!("new champion: {}", summary.name);
+        let (Some(dir), Some(champ)) = (&announce, cdrag.champions.get(&summary.id)) else {
+            continue;
+        };
+        let post = cdrag
+            .write_announcement(champ, &dir.join(&summary.alias))
+            .await?;
+        println!("  announcement written to {}", post.display());
+    }
+    Ok(())
+}
+
+fn history(
+    cdrag: &CDragon,
+    catalog: CacheFile,
+    champion: Option<String>,
+    as_of: Option<String>,
+) -> Result<()> {
+    if let Some(champion) = champion {
+        let champ = find_champion(cdrag, &champion)?;
+        let champ = match as_of {
+            Some(version) => &cdrag.champion_as_of(champ.id, &version)?,
+            None => champ,
+        };
+        println!("{}", serde_json::to_string_pretty(champ)?);
+        return Ok(());
+    }
+    let history = cdrag.history(catalog)?;
+    for rev in &history.revisions {
+        println!(
+            "{} -> {}: {} changes",
+            rev.from,
+            rev.to,
+            rev.forward.0.len()
+        );
+    }
+    match history.current {
+        Some(current) => println!("current: {current}"),
+        None => println!("no history recorded for {catalog} yet"),
+    }
+    Ok(())
+}
+
+async fn pack(action: PackAction) -> Result<()> {
+    let manifest = match action {
+        PackAction::Build { out, catalog } => {
+            let manifest = pack::build(&CDragon::new().await?, &catalog, &out)?;
+            println!("packed into {}", out.display());
+            manifest
+        }
+        PackAction::Fetch { url, wait } => {
+            let mut cdrag = CDragon::lazy();
+            cdrag.wait_for_lock = wait;
+            let manifest = pack::fetch(&cdrag, &url).await?;
+            println!("unpacked {url}");
+            manifest
+        }
+    };
+    if let Some(updated) = manifest.game_data_updated {
+        println!("game data from {updated}");
+    }
+    println!("catalogs: {}", manifest.catalogs.join(", "));
+    Ok(())
+}
+
+/// Errors when no champion has the class `role`, listing the roles there are.
+fn check_class_role(cdrag: &CDragon, role: &str) -> Result<()> {
+    let mut roles: Vec<&str> = cdrag
+        .champions
+        .values()
+        .flat_map(|champ| champ.roles.iter().map(String::as_str))
+        .collect();
+    roles.sort();
+    roles.dedup();
+    match roles.iter().any(|r| r.eq_ignore_ascii_case(role)) {
+        true => Ok(()),
+        false => Err(eyre!(
+            "no champions with the role {role}, the roles are {}",
+            roles.join(", ")
+        )),
+    }
+}
+
+/// Whether a champion has `tag` in the user's profile, always true when there's no tag.
+fn tag_filter(cdrag: &CDragon, tag: Option<&str>) -> Result<impl Fn(&Champion) -> bool> {
+    let tagged = match tag {
+        Some(tag) => Some((tag.to_string(), cdrag.profile()?)),
+        None => None,
+    };
+    Ok(move |champion: &Champion| {
+        tagged
+            .as_ref()
+            .is_none_or(|(tag, profile)| profile.has_tag(champion.id, tag))
+    })
+}
+
+/// Reads a class role or a lane, see [`random::Role`].
+fn check_role(cdrag: &CDragon, role: &str) -> Result<random::Role> {
+    let role = random::Role::parse(role);
+    if let random::Role::Class(class) = &role {
+        check_class_role(cdrag, class)?;
+    }
+    Ok(role)
+}
+
+fn pick_random(
+    cdrag: &CDragon,
+    filters: &random::Filters,
+    tagged: impl Fn(&Champion) -> bool,
+    count: usize,
+    team: bool,
+    aram: bool,
+    seed: Option<u64>,
+) -> Result<String> {
+    let mut rng = seed.map_or_else(fastrand::Rng::new, fastrand::Rng::with_seed);
+    let mut pool = filters.pool(&cdrag.champions);
+    pool.retain(|champion| tagged(champion));
+    let mut out = String::new();
+    for number in 1..=if aram { 2 } else { 1 } {
+        let picks = match (team, aram) {
+            (true, _) => random::draw_team(&mut pool, &mut rng)?,
+            (false, true) => random::draw(&mut pool, random::TEAM_SIZE, &mut rng)?,
+            (false, false) => random::draw(&mut pool, count, &mut rng)?,
+        };
+        let indent = match aram {
+            true => {
+                writeln!(out, "team {number}")?;
+                "  "
+            }
+            false => "",
+        };
+        for pick in picks {
+            let lane = pick
+                .lane
+                .map(|lane| format!("{:<9}", lane.to_string()))
+                .unwrap_or_default();
+            writeln!(out, "{indent}{lane}{}", pick.champion.name)?;
+        }
+    }
+    Ok(out)
+}
+
+fn recommend(
+    cdrag: &CDragon,
+    arena_duo: Option<&str>,
+    role: Option<&str>,
+    position: Option<Position>,
+    tag: Option<&str>,
+    count: usize,
+) -> Result<String> {
+    let role = role.map(|role| check_role(cdrag, role)).transpose()?;
+    let tagged = tag_filter(cdrag, tag)?;
+    let champions = cdrag.champions.values().filter(|champion| tagged(champion));
+    let recs = match arena_duo {
+        Some(name) => {
+            let champion = find_champion(cdrag, name)?;
+            recommend::arena_duo(champions, champion, role.as_ref(), position, count)
+        }
+        None => recommend::for_new_player(champions, role.as_ref(), position, count),
+    };
+    if recs.is_empty() {
+        return Err(eyre!("no champions match"));
+    }
+    let mut out = String::new();
+    for rec in recs {
+        writeln!(out, "{:.2} {}", rec.score, rec.champion.name)?;
+        if !rec.reasons.is_empty() {
+            writeln!(out, "     {}", rec.reasons.join(", "))?;
+        }
+    }
+    Ok(out)
+}
+
+async fn backfill(from: &str, to: Option<&str>, catalogs: &[CacheFile], delay: u64) -> Result<()> {
+    if let Some(catalog) = catalogs
+        .iter()
+        .find(|catalog| !backfill::can_backfill(**catalog))
+    {
+        return Err(eyre!(
+            "{catalog} can't be backfilled, blitzadex puts it together itself"
+        ));
+    }
+    let cdrag = CDragon::lazy();
+    let patches = backfill::between(&cdrag.patches().await?, from, to)?;
+    let mut limit = RateLimit::new(std::time::Duration::from_millis(delay));
+    for patch in &patches {
+        for catalog in catalogs {
+            let outcome = cdrag.backfill(patch, *catalog, &mut limit).await?;
+            println!("{patch:<8}{:<32}{outcome}", catalog.to_string());
+        }
+    }
+    Ok(())
+}
+
+fn trend(cdrag: &CDragon, champion: &str, field: &str, csv: bool) -> Result<()> {
+    let champ = find_champion(cdrag, champion)?;
+    let points = cdrag.trend(champ.id, field)?;
+    match csv {
+        true => print!("{}", trend::csv(&points)),
+        false => print!("{}", trend::render(&champ.name, field, &points)),
+    }
+    Ok(())
+}
+
+fn tag(cdrag: &CDragon, action: TagAction) -> Result<()> {
+    let mut profile = cdrag.profile()?;
+    match action {
+        TagAction::Add { champion, tag } => {
+            let champ = find_champion(cdrag, &champion)?;
+            if profile.tag(champ.id, &tag) {
+                cdrag.save_profile(&profile)?;
+            }
+            println!(
+                "{}: {}",
+                champ.name,
+                profile.tags(champ.id).collect::<Vec<_>>().join(", ")
+            );
+        }
+        TagAction::Remove { champion, tag } => {
+            let champ = find_champion(cdrag, &champion)?;
+            if !profile.untag(champ.id, &tag) {
+                return Err(eyre!("{} isn't tagged {tag}", champ.name));
+            }
+            cdrag.save_profile(&profile)?;
+            println!(
+                "{}: {}",
+                champ.name,
+                profile.tags(champ.id).collect::<Vec<_>>().join(", ")
+            );
+        }
+        TagAction::List {
+            champion: Some(champion),
+        } => {
+            let champ = find_champion(cdrag, &champion)?;
+            for tag in profile.tags(champ.id) {
+                println!("{tag}");
+            }
+        }
+        TagAction::List { champion: None } => {
+            let mut all: Vec<_> = profile.all_tags().into_iter().collect();
+            all.sort();
+            for (tag, ids) in all {
+                let mut names: Vec<&str> = ids
+                    .iter()
+                    .filter_map(|id| cdrag.champions.get(id))
+                    .map(|champ| champ.name.as_str())
+                    .collect();
+                names.sort();
+                println!("{tag}: {}", names.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn challenge(cdrag: &CDragon, name: &str) -> Result<()> {
+    let challenges = cdrag.challenges().await?;
+    let challenge = challenges::named(&challenges, name)?;
+    println!("{} ({})", challenge.name, challenge.category.to_lowercase());
+    println!("{}", challenge.description);
+    for (tier, threshold) in &challenge.thresholds {
+        println!("  {:<12}{:>8}", tier.to_string(), threshold.value);
+    }
+    let list = |names: Vec<&str>| match names.is_empty() {
+        true => "none known".to_string(),
+        false => names.join(", "),
+    };
+    match challenge.related(&cdrag.champions) {
+        Some(Related::Champions(champions)) => println!(
+            "champions: {}",
+            list(champions.iter().map(|champ| champ.name.as_str()).collect())
+        ),
+        Some(Related::Skins(skins)) => println!("skins: {}", list(skins)),
+        None => {}
+    }
+    Ok(())
+}
+
+async fn compare_spells(cdrag: &CDragon, spells: [SpellRef; 2]) -> Result<()> {
+    let summaries = cdrag.champion_summaries().await?;
+    let mut fetched = vec![];
+    for spell in spells {
+        let summary = summaries
+            .iter()
+            .find(|summary| summary.is_named(&spell.champion))
+            .ok_or_else(|| eyre!("no champion named {}", spell.champion))?;
+        let game_data = cdrag.fetch_champion_game_data(&summary.alias).await?;
+        fetched.push((summary, spell, game_data));
+    }
+    let mut columns = vec![];
+    for (summary, spell, game_data) in &fetched {
+        let script_name = format!("{}{}", summary.alias, spell.key);
+        let data = game_data.spell(&script_name).ok_or_else(|| {
+            let known: Vec<&str> = game_data
+                .spells
+                .iter()
+                .map(|spell| spell.script_name.as_str())
+                .collect();
+            eyre!(
+                "no spell {script_name} in the game data, it has {}",
+                known.join(", ")
+            )
+        })?;
+        columns.push((
+            format!("{} {}", summary.name, spell.key),
+            data,
+            spell.ranks(),
+        ));
+    }
+    let column = |i: usize| (columns[i].0.as_str(), columns[i].1, columns[i].2);
+    print!("{}", game_data::compare_spells(column(0), column(1)));
+    Ok(())
+}
+
+async fn team_plan(cdrag: &CDragon, action: PlanAction) -> Result<()> {
+    let sets = cdrag.team_planner_sets().await?;
+    match action {
+        PlanAction::Import { code } => {
+            let plan = TeamPlan::decode(&code)?;
+            println!("{}", plan.set);
+            for unit in plan.units(&sets)? {
+                println!("  {}", unit.display_name);
+            }
+        }
+        PlanAction::Export { units, set } => {
+            let set = match set {
+                Some(set) => set,
+                None => tft::latest_set(&sets)
+                    .ok_or_else(|| eyre!("the team planner data has no sets"))?
+                    .to_string(),
+            };
+            println!("{}", TeamPlan::from_units(&sets, &set, &units)?.encode()?);
+        }
+    }
+    Ok(())
+}
+
+async fn search(cdrag: &CDragon, query: &str, tag: Option<&str>) -> Result<()> {
+    let query: Query = query.parse()?;
+    let skin_lines = if query.uses_skin_lines() {
+        cdrag.skin_lines().await?
+    } else {
+        vec![]
+    };
+    let tagged = tag_filter(cdrag, tag)?;
+    let found = query.search(
+        cdrag.champions.values().filter(|champion| tagged(champion)),
+        &skin_lines,
+        &cdrag.localized_names()?,
+    );
+    if found.is_empty() {
+        return Err(eyre!("no champion matches"));
+    }
+    for champ in found {
+        println!(
+            "{} {} ({})",
+            champ.name,
+            champ.title,
+            champ.roles.join(", ")
+        );
+    }
+    Ok(())
+}
+
+async fn quick(cdrag: &CDragon, name: &str, detail: bool) -> Result<()> {
+    let (summaries, refresh) = cdrag.champion_summaries_now().await?;
+    let overlay = cdrag.community()?;
+    let folded = names::fold(name);
+    let nicknamed = overlay.alias_for_nickname(name);
+    let summary = summaries
+        .iter()
+        .find(|summary| summary.is_named(name) || nicknamed.is_some_and(|a| summary.is_named(a)))
+        .or_else(|| {
+            summaries
+                .iter()
+                .find(|summary| names::fold(&summary.name).starts_with(&folded))
+        })
+        .ok_or_else(|| eyre!("no champion named {name}"))?;
+    let number = dex::number_of(summaries.iter().map(|s| s.id), summary.id).unwrap_or_default();
+    let profile = cdrag.profile()?;
+    let badges: String = profile
+        .tags(summary.id)
+        .map(|t| format!(" [{t}]"))
+        .collect();
+    println!(
+        "#{number:03} {} ({}) - {}{badges}",
+        summary.name,
+        summary.id,
+        summary.roles.join(", ")
+    );
+    if detail {
+        let champ = cdrag.champion(summary.id).await?;
+        println!("{} {}", champ.name, champ.title);
+        println!(
+            "difficulty {}, {} damage",
+            champ.tactical_info.difficulty, champ.tactical_info.damage_type
+        );
+        if let Some(meta) = overlay.get(&champ.alias) {
+            if !meta.nicknames.is_empty() {
+                println!("aka {}", meta.nicknames.join(", "));
+            }
+            if !meta.positions.is_empty() {
+                let positions: Vec<String> = meta.positions.iter().map(|p| p.to_string()).collect();
+                println!("plays {}", positions.join(", "));
+            }
+            if let Some(voice_actor) = &meta.voice_actor {
+                println!("voiced by {voice_actor}");
+            }
+            if let Some(patch) = &meta.release_patch {
+                println!("released in {patch}");
+            }
+        }
+    }
+    if let Some(refresh) = refresh {
+        if let Err(err) = refresh.await? {
+            eprintln!("couldn't refresh the champion summaries: {err}");
+        }
+    }
+    Ok(())
+}
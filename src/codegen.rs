@@ -0,0 +1,85 @@
+//! Rust source generated from the champion data, for binaries that can't do any I/O.
+//!
+//! The generated file stands alone: it declares its own `ChampionSummary` with `&'static str`
+//! fields, so it doesn't need blitzadex, serde or an allocator.
+
+use std::fmt::Write;
+
+use crate::{cdragon::ChampionSummary, provenance::Provenance};
+
+const HEADER: &str = "\
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChampionSummary {
+    pub id: u64,
+    pub name: &'static str,
+    pub alias: &'static str,
+    pub square_portrait_path: &'static str,
+    pub roles: &'static [&'static str],
+}
+";
+
+/// A Rust source file with a `CHAMPIONS` array of `summaries`, sorted by id, headed by where the
+/// data came from.
+pub fn champion_summaries(summaries: &[ChampionSummary], provenance: &Provenance) -> String {
+    let mut sorted: Vec<&ChampionSummary> = summaries.iter().collect();
+    sorted.sort_by_key(|summary| summary.id);
+    let mut out = "// Generated by `blitzadex codegen`, do not edit.\n".to_string();
+    out.push_str(&provenance.comment("//"));
+    out.push('\n');
+    out.push_str(HEADER);
+    let _ = writeln!(
+        out,
+        "\npub static CHAMPIONS: [ChampionSummary; {}] = [",
+        sorted.len()
+    );
+    for summary in sorted {
+        // Debug formatting of a str is a valid Rust string literal, escapes included
+        let _ = writeln!(out, "    ChampionSummary {{");
+        let _ = writeln!(out, "        id: {},", summary.id);
+        let _ = writeln!(out, "        name: {:?},", summary.name);
+        let _ = writeln!(out, "        alias: {:?},", summary.alias);
+        let _ = writeln!(
+            out,
+            "        square_portrait_path: {:?},",
+            summary.square_portrait_path
+        );
+        let _ = writeln!(out, "        roles: &{:?},", summary.roles);
+        let _ = writeln!(out, "    }},");
+    }
+    out.push_str("];\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color_eyre::Result;
+    use serde_json::Value;
+
+    #[test]
+    fn static_array() -> Result<()> {
+        let summaries: Vec<Value> =
+            serde_json::from_str(&std::fs::read_to_string("testing/champion-summary.json")?)?;
+        let mut summaries = summaries
+            .into_iter()
+            .skip(1)
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<ChampionSummary>, _>>()?;
+        summaries.reverse();
+        summaries[0].name = "Kai\"Sa".to_string();
+        let source = champion_summaries(&summaries, &Provenance::new(Some("15.1".to_string())));
+        assert!(source.starts_with("// Generated by `blitzadex codegen`, do not edit.\n"));
+        assert!(source.contains("// patch: 15.1\n"));
+        assert!(source.contains(&format!(
+            "pub static CHAMPIONS: [ChampionSummary; {}] = [",
+            summaries.len()
+        )));
+        let annie = source.find("name: \"Annie\"").unwrap();
+        let olaf = source.find("name: \"Olaf\"").unwrap();
+        assert!(annie < olaf);
+        assert!(source.contains("roles: &[\"mage\", \"support\"],"));
+        assert!(source.contains("name: \"Kai\\\"Sa\","));
+        assert!(source.ends_with("];\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+//! Banners composited from skin splash art, e.g. every skin in a skin line side by side.
+
+use color_eyre::{eyre::Context, Result};
+use image::{imageops, DynamicImage, Rgba, RgbaImage};
+
+use crate::cdragon::{CDragon, Skin};
+
+const BACKGROUND: Rgba<u8> = Rgba([0, 0, 0, 255]);
+/// The aspect ratio of CDragon's centered splashes, 1215x717
+const SPLASH_ASPECT: f64 = 1215. / 717.;
+
+/// How the splashes are laid out in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub columns: u32,
+    pub tile_width: u32,
+    pub tile_height: u32,
+    /// Between the tiles and around the edge
+    pub gap: u32,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::with_width(4, 480)
+    }
+}
+
+impl Layout {
+    /// A layout whose tiles keep the splashes' aspect ratio.
+    pub fn with_width(columns: u32, tile_width: u32) -> Self {
+        Self {
+            columns: columns.max(1),
+            tile_width,
+            tile_height: (tile_width as f64 / SPLASH_ASPECT).round() as u32,
+            gap: 4,
+        }
+    }
+}
+
+/// Fills a grid with `splashes` in order, cropping each to cover its tile.
+pub fn compose(splashes: &[RgbaImage], layout: &Layout) -> RgbaImage {
+    let Layout {
+        columns,
+        tile_width,
+        tile_height,
+        gap,
+    } = *layout;
+    let rows = (splashes.len() as u32).div_ceil(columns).max(1);
+    let columns = columns.min(splashes.len() as u32).max(1);
+    let mut banner = RgbaImage::from_pixel(
+        gap + columns * (tile_width + gap),
+        gap + rows * (tile_height + gap),
+        BACKGROUND,
+    );
+    for (i, splash) in splashes.iter().enumerate() {
+        let i = i as u32;
+        let tile = DynamicImage::from(splash.clone())
+            .resize_to_fill(tile_width, tile_height, imageops::FilterType::Triangle)
+            .to_rgba8();
+        let x = gap + (i % columns) * (tile_width + gap);
+        let y = gap + (i / columns) * (tile_height + gap);
+        imageops::overlay(&mut banner, &tile, x.into(), y.into());
+    }
+    banner
+}
+
+impl CDragon {
+    /// Composites the centered splashes of `skins` into a banner, see
+    /// [`CDragon::stored_asset`].
+    pub async fn collage(&self, skins: &[&Skin], layout: &Layout) -> Result<RgbaImage> {
+        let mut splashes = vec![];
+        for skin in skins {
+            let bytes = self.stored_asset(&skin.splash_path).await?;
+            let splash = image::load_from_memory(&bytes)
+                .with_context(|| format!("the splash of {} isn't an image", skin.name))?;
+            splashes.push(splash.to_rgba8());
+        }
+        Ok(compose(&splashes, layout))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let layout = Layout::with_width(2, 120);
+        assert_eq!(layout.tile_height, 71);
+        let red = RgbaImage::from_pixel(1215, 717, Rgba([255, 0, 0, 255]));
+        let blue = RgbaImage::from_pixel(1215, 717, Rgba([0, 0, 255, 255]));
+        let banner = compose(&[red.clone(), blue.clone(), red], &layout);
+        assert_eq!(banner.dimensions(), (4 + 2 * 124, 4 + 2 * 75));
+        assert_eq!(banner.get_pixel(0, 0), &BACKGROUND);
+        assert_eq!(banner.get_pixel(10, 10), &Rgba([255, 0, 0, 255]));
+        assert_eq!(banner.get_pixel(124 + 10, 10), &Rgba([0, 0, 255, 255]));
+        assert_eq!(banner.get_pixel(10, 75 + 10), &Rgba([255, 0, 0, 255]));
+        assert_eq!(banner.get_pixel(124 + 10, 75 + 10), &BACKGROUND);
+
+        // A single splash doesn't leave empty columns
+        assert_eq!(compose(&[blue], &layout).width(), 4 + 124);
+    }
+}
@@ -0,0 +1,104 @@
+//! Community maintained metadata that CDragon doesn't provide, overlaid onto the champions when
+//! they are loaded.
+//!
+//! The overlay is a JSON object keyed by champion alias. A copy ships with blitzadex
+//! (`data/community.json`), and users can add to or override it with a `community.json` of the
+//! same shape in the config directory. The bundled copy has the positions of every champion, but
+//! voice actors and release patches of only a few, so features that use those only see the
+//! champions that have them until they're filled in:
+//!
+//! ```json
+//! {
+//!   "MissFortune": {
+//!     "nicknames": ["mf"],
+//!     "voiceActor": "...",
+//!     "releasePatch": "...",
+//!     "positions": ["bottom"],
+//!     "anythingElse": "..."
+//!   }
+//! }
+//! ```
+
+use std::{collections::HashMap, fs, path::Path};
+
+use color_eyre::{eyre::Context, Result};
+
+pub use blitzadex_types::{CommunityMetadata, Position};
+
+const BUILTIN: &str = include_str!("../data/community.json");
+pub const OVERLAY_FILE: &str = "community.json";
+
+/// Community metadata for every champion, keyed by lowercase alias.
+#[derive(Debug, Default)]
+pub struct Overlay(HashMap<String, CommunityMetadata>);
+
+impl Overlay {
+    fn parse(ser: &str) -> Result<Self> {
+        let entries: HashMap<String, CommunityMetadata> = serde_json::from_str(ser)?;
+        Ok(Self(
+            entries
+                .into_iter()
+                .map(|(alias, meta)| (alias.to_lowercase(), meta))
+                .collect(),
+        ))
+    }
+
+    /// The overlay that ships with blitzadex.
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN).expect("data/community.json is valid")
+    }
+
+    /// The builtin overlay with the user's `community.json` from `config_dir` layered on top.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let mut overlay = Self::builtin();
+        let path = config_dir.join(OVERLAY_FILE);
+        if path.try_exists().unwrap_or(false) {
+            let user = Self::parse(&fs::read_to_string(&path)?)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            for (alias, meta) in user.0 {
+                overlay.0.entry(alias).or_default().merge(meta);
+            }
+        }
+        Ok(overlay)
+    }
+
+    pub fn get(&self, alias: &str) -> Option<&CommunityMetadata> {
+        self.0.get(&alias.to_lowercase())
+    }
+
+    /// The alias of the champion with `nickname`, if any.
+    pub fn alias_for_nickname(&self, nickname: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(_, meta)| meta.has_nickname(nickname))
+            .map(|(alias, _)| alias.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn user_overlay_wins() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join(OVERLAY_FILE),
+            r#"{"missfortune": {"nicknames": ["sarah"], "releasePatch": "V1.0.0.100", "positions": ["adc", "support"], "main": "yes", "rating": 5}}"#,
+        )?;
+        let overlay = Overlay::load(dir.path())?;
+        let mf = overlay.get("MissFortune").unwrap();
+        assert_eq!(mf.nicknames, ["mf", "sarah"]);
+        assert_eq!(mf.release_patch.as_deref(), Some("V1.0.0.100"));
+        assert_eq!(mf.positions, [Position::Bottom, Position::Support]);
+        assert_eq!(mf.extra["main"], "yes");
+        assert_eq!(mf.extra["rating"], 5);
+        assert_eq!(
+            overlay.get("Blitzcrank").unwrap().positions,
+            [Position::Support]
+        );
+        assert_eq!(overlay.alias_for_nickname("SARAH"), Some("missfortune"));
+        assert_eq!(overlay.alias_for_nickname("tf"), Some("twistedfate"));
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Champions side by side, a column each, to contrast picks at a glance.
+//!
+//! ```text
+//!                Ahri   Syndra  LeBlanc
+//! roles          mage   mage    assassin, mage
+//! damage type    Magic  Magic   Magic
+//! ...
+//! ```
+
+use std::fmt::Write;
+
+use crate::{card::rating, cdragon::Champion};
+
+/// How wide the column of labels is
+const LABEL_WIDTH: usize = 15;
+
+/// Renders `champions` side by side: their roles and tactical info, then their playstyle
+/// ratings.
+pub fn render(champions: &[&Champion]) -> String {
+    let row = |label: &str, cell: &dyn Fn(&Champion) -> String| -> (String, Vec<String>) {
+        (
+            label.to_string(),
+            champions.iter().map(|champion| cell(champion)).collect(),
+        )
+    };
+    let rows = [
+        row("", &|champ| champ.name.clone()),
+        row("roles", &|champ| champ.roles.join(", ")),
+        row("damage type", &|champ| {
+            champ
+                .tactical_info
+                .damage_type
+                .trim_start_matches('k')
+                .to_string()
+        }),
+        row("style", &|champ| champ.tactical_info.style.to_string()),
+        row("difficulty", &|champ| {
+            rating(champ.tactical_info.difficulty)
+        }),
+        row("damage", &|champ| rating(champ.playstyle_info.damage)),
+        row("durability", &|champ| {
+            rating(champ.playstyle_info.durability)
+        }),
+        row("crowd control", &|champ| {
+            rating(champ.playstyle_info.crowd_control)
+        }),
+        row("mobility", &|champ| rating(champ.playstyle_info.mobility)),
+        row("utility", &|champ| rating(champ.playstyle_info.utility)),
+    ];
+    let mut widths = vec![0; champions.len()];
+    for (_, cells) in &rows {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    for (label, cells) in &rows {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        let line = format!("{label:<LABEL_WIDTH$}{}", cells.join("  "));
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color_eyre::Result;
+
+    #[test]
+    fn side_by_side() -> Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let mut tibbers: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        tibbers.name = "Tibbers the Bear".to_string();
+        tibbers.roles = vec!["tank".to_string()];
+        tibbers.playstyle_info.durability = 3;
+
+        let table = render(&[&annie, &tibbers]);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!("{}Annie          Tibbers the Bear", " ".repeat(15))
+        );
+        assert_eq!(lines[1], "roles          mage, support  tank");
+        assert_eq!(lines[2], "damage type    Magic          Magic");
+        assert_eq!(lines[4], "difficulty     ■□□            ■□□");
+        assert!(lines[6].starts_with("durability     "));
+        assert!(lines[6].ends_with("■■■"));
+        assert_eq!(lines.len(), 10);
+        Ok(())
+    }
+}
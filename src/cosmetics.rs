@@ -0,0 +1,8 @@
+//! The cosmetics a player collects for their profile and in game, from the game data plugin.
+
+pub mod banners;
+pub mod emotes;
+pub mod regalia;
+pub mod summoner_icons;
+pub mod trophies;
+pub mod ward_skins;
@@ -0,0 +1,90 @@
+//! Summoner banners, the flag and frame shown behind a player's profile as part of their
+//! regalia.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BannerFlag {
+    pub level: u64,
+    /// e.g. "worlds" for the flags of a Worlds event
+    pub theme: String,
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin) of the icon in the customization screen
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub inventory_icon: String,
+    /// [Normalized Path](crate::cdragon::Skin) of the flag as shown on the profile
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub profile_icon: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BannerFrame {
+    pub level: u64,
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub inventory_icon: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct Banners {
+    #[serde(rename = "BannerFlags")]
+    pub flags: Vec<BannerFlag>,
+    #[serde(rename = "BannerFrames")]
+    pub frames: Vec<BannerFrame>,
+}
+
+impl Banners {
+    /// Every flag with every frame, flags first, which is every banner a profile can show.
+    pub fn combinations(&self) -> impl Iterator<Item = (&BannerFlag, &BannerFrame)> {
+        self.flags
+            .iter()
+            .flat_map(|flag| self.frames.iter().map(move |frame| (flag, frame)))
+    }
+}
+
+impl CDragon {
+    /// Loads the cached summoner banners, fetching them when they aren't cached yet.
+    pub async fn banners(&self) -> Result<Banners> {
+        if let Ok(banners) = self.load_obj(CacheFile::SummonerBanners) {
+            return Ok(banners);
+        }
+        let bytes = http::get(&self.http_client, &asset_url("v1/summoner-banners.json"))
+            .await
+            .with_context(|| "couldn't fetch the summoner banners")?;
+        let banners: Banners = parse_json(&bytes)?;
+        self.cache_obj(&banners, CacheFile::SummonerBanners)?;
+        Ok(banners)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn every_combination() -> Result<()> {
+        let banners: Banners =
+            serde_json::from_str(&std::fs::read_to_string("testing/summoner-banners.json")?)?;
+        assert_eq!(
+            banners.flags[1].profile_icon,
+            "assets/loadouts/summonerbanners/flags/worlds2024.png"
+        );
+        let combinations: Vec<(&str, &str)> = banners
+            .combinations()
+            .map(|(flag, frame)| (flag.name.as_str(), frame.name.as_str()))
+            .collect();
+        assert_eq!(combinations.len(), 6);
+        assert_eq!(combinations[0], ("Default", "Unranked"));
+        assert_eq!(combinations[5], ("Worlds 2024", "Bronze"));
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+//! Summoner emotes, shown over a player's champion in game.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Emote {
+    pub id: u64,
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub inventory_icon: String,
+    pub description: String,
+}
+
+/// The emotes, without the unnamed placeholder the game data lists as id 0.
+fn named(emotes: Vec<Emote>) -> Vec<Emote> {
+    emotes
+        .into_iter()
+        .filter(|emote| !emote.name.is_empty())
+        .collect()
+}
+
+impl CDragon {
+    /// Loads the cached emotes, fetching them when they aren't cached yet.
+    pub async fn emotes(&self) -> Result<Vec<Emote>> {
+        if let Ok(emotes) = self.load_obj(CacheFile::SummonerEmotes) {
+            return Ok(emotes);
+        }
+        let emotes = self.fetch_emotes().await?;
+        self.cache_obj(&emotes, CacheFile::SummonerEmotes)?;
+        Ok(emotes)
+    }
+
+    pub async fn fetch_emotes(&self) -> Result<Vec<Emote>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/summoner-emotes.json"))
+            .await
+            .with_context(|| "couldn't fetch the emotes")?;
+        Ok(named(parse_json(&bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn named_emotes() -> Result<()> {
+        let emotes: Vec<Emote> =
+            serde_json::from_str(&std::fs::read_to_string("testing/summoner-emotes.json")?)?;
+        let emotes = named(emotes);
+        assert_eq!(emotes.len(), 1);
+        assert_eq!(
+            emotes[0].inventory_icon,
+            "assets/loadouts/summoneremotes/rewards/thumbs_up_inventory.png"
+        );
+        Ok(())
+    }
+}
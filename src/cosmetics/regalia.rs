@@ -0,0 +1,167 @@
+//! Regalia, the crests framing a player's icon: one per ranked tier and division, and prestige
+//! crests for summoner levels.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    cdragon::{
+        asset_url, deserialize_asset_path, deserialize_optional_asset_path, parse_json, CDragon,
+        CacheFile,
+    },
+    http,
+};
+
+/// A ranked tier as the Riot API spells it, e.g. "GOLD".
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum RankedTier {
+    Unranked,
+    Iron,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Emerald,
+    Diamond,
+    Master,
+    Grandmaster,
+    Challenger,
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Division {
+    I,
+    II,
+    III,
+    IV,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedCrest {
+    pub tier: RankedTier,
+    /// None for the tiers without divisions, from Master up
+    pub division: Option<Division>,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub crest_path: String,
+    /// [Normalized Path](crate::cdragon::Skin) of the banner trim that goes with the crest
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub banner_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestigeCrest {
+    /// The summoner level it's shown from
+    pub level: u64,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub crest_path: String,
+}
+
+/// `regalia.json`
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Regalia {
+    #[serde(default)]
+    pub ranked_crests: Vec<RankedCrest>,
+    #[serde(default)]
+    pub prestige_crests: Vec<PrestigeCrest>,
+}
+
+impl Regalia {
+    /// The crest of a tier and division. Tiers without divisions ignore `division`, and tiers
+    /// with a single crest for every division fall back to it.
+    pub fn ranked_crest(
+        &self,
+        tier: RankedTier,
+        division: Option<Division>,
+    ) -> Option<&RankedCrest> {
+        let of_tier = || {
+            self.ranked_crests
+                .iter()
+                .filter(move |crest| crest.tier == tier)
+        };
+        of_tier()
+            .find(|crest| crest.division == division)
+            .or_else(|| of_tier().find(|crest| crest.division.is_none()))
+    }
+
+    /// The prestige crest shown at a summoner level, the one of the highest level reached.
+    pub fn prestige_crest(&self, level: u64) -> Option<&PrestigeCrest> {
+        self.prestige_crests
+            .iter()
+            .filter(|crest| crest.level <= level)
+            .max_by_key(|crest| crest.level)
+    }
+}
+
+impl CDragon {
+    /// Loads the cached regalia, fetching them when they aren't cached yet.
+    pub async fn regalia(&self) -> Result<Regalia> {
+        if let Ok(regalia) = self.load_obj(CacheFile::Regalia) {
+            return Ok(regalia);
+        }
+        let regalia = self.fetch_regalia().await?;
+        self.cache_obj(&regalia, CacheFile::Regalia)?;
+        Ok(regalia)
+    }
+
+    pub async fn fetch_regalia(&self) -> Result<Regalia> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/regalia.json"))
+            .await
+            .with_context(|| "couldn't fetch the regalia")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crests() -> Result<()> {
+        let regalia: Regalia =
+            serde_json::from_str(&std::fs::read_to_string("testing/regalia.json")?)?;
+        let gold = regalia
+            .ranked_crest(RankedTier::Gold, Some(Division::IV))
+            .unwrap();
+        assert_eq!(
+            gold.crest_path,
+            "assets/regalia/crests/ranked/gold/gold_4.png"
+        );
+        assert_eq!(
+            gold.banner_path.as_deref(),
+            Some("assets/regalia/bannerskins/ranked/gold.png")
+        );
+        assert!(regalia
+            .ranked_crest(RankedTier::Gold, Some(Division::II))
+            .is_none());
+        let challenger = regalia
+            .ranked_crest(RankedTier::Challenger, Some(Division::I))
+            .unwrap();
+        assert_eq!(challenger.division, None);
+        assert_eq!(
+            regalia
+                .ranked_crest(RankedTier::Emerald, Some(Division::II))
+                .unwrap()
+                .banner_path,
+            None
+        );
+
+        assert_eq!(regalia.prestige_crest(0), None);
+        assert_eq!(
+            regalia.prestige_crest(49).map(|crest| crest.level),
+            Some(30)
+        );
+        assert_eq!(
+            regalia.prestige_crest(500).map(|crest| crest.level),
+            Some(50)
+        );
+        Ok(())
+    }
+}
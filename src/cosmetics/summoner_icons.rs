@@ -0,0 +1,86 @@
+//! Summoner icons, the profile pictures players pick.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{
+        asset_url, deserialize_optional_asset_path, parse_json, CDragon, CacheFile,
+        RegionalDescription, RegionalRarity,
+    },
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerIcon {
+    pub id: u64,
+    pub title: String,
+    #[serde(default)]
+    pub year_released: Option<u32>,
+    pub is_legacy: bool,
+    /// [Normalized Path](crate::cdragon::Skin), a few old icons have no image
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub image_path: Option<String>,
+    #[serde(default)]
+    pub descriptions: Vec<RegionalDescription>,
+    #[serde(default)]
+    pub rarities: Vec<RegionalRarity>,
+    /// Regions the icon isn't available in, e.g. "CN"
+    #[serde(default)]
+    pub disabled_regions: Vec<String>,
+    /// e.g. "T1", for the icons of esports teams
+    #[serde(default)]
+    pub esports_team: Option<String>,
+    #[serde(default)]
+    pub esports_region: Option<String>,
+    #[serde(default)]
+    pub esports_event: Option<String>,
+}
+
+/// The icons released in `year`.
+pub fn released_in(icons: &[SummonerIcon], year: u32) -> Vec<&SummonerIcon> {
+    icons
+        .iter()
+        .filter(|icon| icon.year_released == Some(year))
+        .collect()
+}
+
+impl CDragon {
+    /// Loads the cached summoner icons, fetching them when they aren't cached yet.
+    pub async fn summoner_icons(&self) -> Result<Vec<SummonerIcon>> {
+        if let Ok(icons) = self.load_obj(CacheFile::SummonerIcons) {
+            return Ok(icons);
+        }
+        let icons = self.fetch_summoner_icons().await?;
+        self.cache_obj(&icons, CacheFile::SummonerIcons)?;
+        Ok(icons)
+    }
+
+    pub async fn fetch_summoner_icons(&self) -> Result<Vec<SummonerIcon>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/summoner-icons.json"))
+            .await
+            .with_context(|| "couldn't fetch the summoner icons")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summoner_icons() -> Result<()> {
+        let icons: Vec<SummonerIcon> =
+            serde_json::from_str(&std::fs::read_to_string("testing/summoner-icons.json")?)?;
+        let t1 = &icons[1];
+        assert_eq!(t1.image_path.as_deref(), Some("v1/profile-icons/4568.jpg"));
+        assert_eq!(t1.esports_team.as_deref(), Some("T1"));
+        assert_eq!(t1.disabled_regions, ["CN"]);
+        assert_eq!(icons[2].image_path, None);
+        assert_eq!(released_in(&icons, 2022), [t1]);
+        let cached: Vec<SummonerIcon> = serde_json::from_str(&serde_json::to_string(&icons)?)?;
+        assert_eq!(cached, icons);
+        Ok(())
+    }
+}
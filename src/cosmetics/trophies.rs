@@ -0,0 +1,74 @@
+//! Summoner trophies, won in Clash and shown on a player's profile.
+
+use std::collections::BTreeMap;
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_optional_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Trophy {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The trophy's look for each Clash tier it can be won in, tier I being the highest
+    #[serde(default)]
+    pub tiers: BTreeMap<u32, TrophyTier>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TrophyTier {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub image_path: Option<String>,
+}
+
+impl CDragon {
+    /// Loads the cached trophies, fetching them when they aren't cached yet.
+    pub async fn trophies(&self) -> Result<Vec<Trophy>> {
+        if let Ok(trophies) = self.load_obj(CacheFile::SummonerTrophies) {
+            return Ok(trophies);
+        }
+        let trophies = self.fetch_trophies().await?;
+        self.cache_obj(&trophies, CacheFile::SummonerTrophies)?;
+        Ok(trophies)
+    }
+
+    pub async fn fetch_trophies(&self) -> Result<Vec<Trophy>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/summoner-trophies.json"))
+            .await
+            .with_context(|| "couldn't fetch the trophies")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trophy_tiers() -> Result<()> {
+        let trophies: Vec<Trophy> =
+            serde_json::from_str(&std::fs::read_to_string("testing/summoner-trophies.json")?)?;
+        let tiers: Vec<u32> = trophies[0].tiers.keys().copied().collect();
+        assert_eq!(tiers, [1, 4]);
+        assert_eq!(
+            trophies[0].tiers[&4].image_path.as_deref(),
+            Some("assets/loadouts/summonertrophies/clash/trophy_tier4.png")
+        );
+        let cached: Vec<Trophy> = serde_json::from_str(&serde_json::to_string(&trophies)?)?;
+        assert_eq!(cached, trophies);
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+//! Ward skins, the looks players pick for their wards.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{
+        asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile, RegionalDescription,
+        RegionalRarity,
+    },
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WardSkin {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub ward_image_path: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub ward_shadow_image_path: String,
+    pub is_legacy: bool,
+    #[serde(default)]
+    pub regional_descriptions: Vec<RegionalDescription>,
+    #[serde(default)]
+    pub rarities: Vec<RegionalRarity>,
+}
+
+impl CDragon {
+    /// Loads the cached ward skins, fetching them when they aren't cached yet.
+    pub async fn ward_skins(&self) -> Result<Vec<WardSkin>> {
+        if let Ok(ward_skins) = self.load_obj(CacheFile::WardSkins) {
+            return Ok(ward_skins);
+        }
+        let ward_skins = self.fetch_ward_skins().await?;
+        self.cache_obj(&ward_skins, CacheFile::WardSkins)?;
+        Ok(ward_skins)
+    }
+
+    pub async fn fetch_ward_skins(&self) -> Result<Vec<WardSkin>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/ward-skins.json"))
+            .await
+            .with_context(|| "couldn't fetch the ward skins")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ward_skins() -> Result<()> {
+        let ward_skins: Vec<WardSkin> =
+            serde_json::from_str(&std::fs::read_to_string("testing/ward-skins.json")?)?;
+        let lantern = &ward_skins[1];
+        assert!(lantern.is_legacy);
+        assert_eq!(
+            lantern.ward_image_path,
+            "assets/loadouts/wardskins/ward_batolantern.png"
+        );
+        assert_eq!(lantern.regional_descriptions[0].region, "riot");
+        let cached: Vec<WardSkin> = serde_json::from_str(&serde_json::to_string(&ward_skins)?)?;
+        assert_eq!(cached, ward_skins);
+        Ok(())
+    }
+}
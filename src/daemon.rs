@@ -0,0 +1,230 @@
+//! `blitzadex daemon`: keeps the champions loaded and answers queries from other blitzadex
+//! processes over a unix socket, so they skip loading the catalogs themselves.
+//!
+//! A client writes its command line arguments as a JSON array on one line, and the daemon
+//! replies with a JSON [`Reply`] and closes the connection. Both ends give up on a connection that
+//! stalls, see the timeouts below, and a client that can't get a reply answers the query itself.
+//! The daemon reloads the catalogs when the cached champions change, e.g. after an update.
+
+use std::{
+    fs,
+    io::Write,
+    os::unix::{
+        fs::{DirBuilderExt, PermissionsExt},
+        net::UnixStream,
+    },
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, SystemTime},
+};
+
+use color_eyre::{
+    eyre::{eyre, Context, ContextCompat},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader as AsyncBufReader},
+    net::UnixListener,
+    task::LocalSet,
+    time::timeout,
+};
+
+use crate::{
+    cdragon::{CDragon, CacheFile},
+    progress,
+};
+
+/// The directory holding the socket, which only the user can enter
+const SOCKET_DIR: &str = "daemon";
+const SOCKET: &str = "daemon.sock";
+/// How long the daemon waits for a client to send its query, or to take the reply
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a client waits for the daemon to answer before answering the query itself
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum Reply {
+    /// What the command would have printed
+    Output(String),
+    Error(String),
+}
+
+/// Where the daemon listens, under the user's runtime directory, or the cache directory when
+/// there's no runtime directory.
+pub fn socket_path() -> Result<PathBuf> {
+    let proj_dirs = directories::ProjectDirs::from("", "", "blitzadex")
+        .with_context(|| "failed to find the project directory")?;
+    let dir = proj_dirs.runtime_dir().unwrap_or(proj_dirs.cache_dir());
+    Ok(dir.join(SOCKET_DIR).join(SOCKET))
+}
+
+/// Asks a running daemon to answer `args`. `None` when no daemon is running or it couldn't be
+/// reached, in which case the caller answers the query itself.
+pub fn ask(args: &[String]) -> Result<Option<String>> {
+    let Ok(stream) = UnixStream::connect(socket_path()?) else {
+        return Ok(None);
+    };
+    let Ok(reply) = request(stream, args) else {
+        return Ok(None);
+    };
+    match reply {
+        Reply::Output(output) => Ok(Some(output)),
+        Reply::Error(err) => Err(eyre!(err)),
+    }
+}
+
+fn request(mut stream: UnixStream, args: &[String]) -> Result<Reply> {
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_read_timeout(Some(ANSWER_TIMEOUT))?;
+    writeln!(stream, "{}", serde_json::to_string(args)?)?;
+    Ok(serde_json::from_reader(stream)?)
+}
+
+fn champions_modified(cdrag: &CDragon) -> Option<SystemTime> {
+    fs::metadata(cdrag.cache_dir.join(CacheFile::Champions.to_string()))
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// A daemon listening on [`socket_path`] with the catalogs loaded, see [`Daemon::serve`].
+pub struct Daemon {
+    listener: UnixListener,
+    path: PathBuf,
+    cdrag: Rc<CDragon>,
+    /// When the loaded champions were cached
+    loaded: Option<SystemTime>,
+}
+
+impl Daemon {
+    /// Loads the catalogs and starts listening. Only the user can connect to the socket: it's
+    /// bound inside a directory only the user can enter, so it's never reachable with looser
+    /// permissions, even before they're set on the socket itself.
+    pub async fn bind() -> Result<Self> {
+        let path = socket_path()?;
+        if UnixStream::connect(&path).is_ok() {
+            return Err(eyre!("a daemon is already running"));
+        }
+        if let Some(dir) = path.parent() {
+            fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(dir)?;
+            // The mode only applies to a directory that didn't exist yet
+            fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+        }
+        // Left behind by a daemon that didn't shut down cleanly
+        let _ = fs::remove_file(&path);
+        let listener =
+            UnixListener::bind(&path).with_context(|| format!("couldn't listen on {path:?}"))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        let cdrag = CDragon::new().await?;
+        Ok(Daemon {
+            listener,
+            path,
+            loaded: champions_modified(&cdrag),
+            cdrag: Rc::new(cdrag),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Answers queries until the process is stopped, passing each query's arguments and the
+    /// loaded catalogs to `answer`.
+    ///
+    /// Connections are handled side by side, so a client that's slow to send its query doesn't
+    /// hold up the others.
+    pub async fn serve(
+        mut self,
+        answer: impl Fn(&CDragon, Vec<String>) -> Result<String> + 'static,
+    ) -> Result<()> {
+        let answer = Rc::new(answer);
+        LocalSet::new()
+            .run_until(async move {
+                loop {
+                    let Ok((stream, _)) = self.listener.accept().await else {
+                        continue;
+                    };
+                    self.reload().await;
+                    let cdrag = Rc::clone(&self.cdrag);
+                    let answer = Rc::clone(&answer);
+                    tokio::task::spawn_local(async move {
+                        // The client may have given up, which is no reason to stop serving the
+                        // others
+                        let _ = respond(stream, &cdrag, &*answer).await;
+                    });
+                }
+            })
+            .await
+    }
+
+    /// Reloads the catalogs if the cached champions changed. Queries keep being answered from
+    /// the loaded ones when reloading fails.
+    async fn reload(&mut self) {
+        let modified = champions_modified(&self.cdrag);
+        if modified == self.loaded {
+            return;
+        }
+        self.loaded = modified;
+        match CDragon::new().await {
+            Ok(cdrag) => self.cdrag = Rc::new(cdrag),
+            Err(err) => progress::notice(format_args!(
+                "couldn't reload the catalogs, still answering from the loaded ones: {err}"
+            )),
+        }
+    }
+}
+
+async fn respond(
+    stream: tokio::net::UnixStream,
+    cdrag: &CDragon,
+    answer: &impl Fn(&CDragon, Vec<String>) -> Result<String>,
+) -> Result<()> {
+    let (read, mut write) = stream.into_split();
+    let mut line = String::new();
+    timeout(
+        CLIENT_TIMEOUT,
+        AsyncBufReader::new(read).read_line(&mut line),
+    )
+    .await??;
+    let reply = match serde_json::from_str(&line)
+        .map_err(Into::into)
+        .and_then(|args| answer(cdrag, args))
+    {
+        Ok(output) => Reply::Output(output),
+        Err(err) => Reply::Error(err.to_string()),
+    };
+    timeout(
+        CLIENT_TIMEOUT,
+        write.write_all(&serde_json::to_vec(&reply)?),
+    )
+    .await??;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn answers_a_query() -> Result<()> {
+        let (mut client, server) = tokio::net::UnixStream::pair()?;
+        client.write_all(b"[\"show\",\"annie\"]\n").await?;
+        respond(
+            server,
+            &CDragon::default(),
+            &|_: &CDragon, args: Vec<String>| Ok(args.join(" ")),
+        )
+        .await?;
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).await?;
+        assert!(matches!(
+            serde_json::from_str(&reply)?,
+            Reply::Output(output) if output == "show annie"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use std::{cmp::Ordering, collections::HashMap, fmt::Write};
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    cdragon::{CDragon, Champion},
+    names::fold,
+    patch::patch_numbers,
+    profile::{Completion, Profile},
+    warm::Config,
+};
+
+const COLUMNS: usize = 10;
+
+/// A single slot in the dex.
+///
+/// Riot hands out champion ids in release order, so sorting by id gives the dex number.
+#[derive(Debug)]
+pub struct DexEntry<'a> {
+    pub number: usize,
+    pub champion: &'a Champion,
+    pub completion: Option<Completion>,
+}
+
+/// Numbers every champion and attaches the user's progress from the [`Profile`].
+pub fn entries<'a>(champions: &'a HashMap<u64, Champion>, profile: &Profile) -> Vec<DexEntry<'a>> {
+    let mut champions: Vec<&Champion> = champions.values().collect();
+    champions.sort_by_key(|champ| champ.id);
+    champions
+        .into_iter()
+        .enumerate()
+        .map(|(i, champion)| DexEntry {
+            number: i + 1,
+            champion,
+            completion: profile.completion(champion.id),
+        })
+        .collect()
+}
+
+/// The order the dex is shown in. Entries keep their dex number whatever the order.
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Order {
+    /// By dex number, which is also by id
+    #[default]
+    #[serde(alias = "id")]
+    #[cfg_attr(feature = "cli", value(alias = "id"))]
+    Dex,
+    /// By name
+    Alpha,
+    /// By the release patch from the community metadata. Champions without one are older than
+    /// the metadata, so they go first.
+    Release,
+}
+
+impl Order {
+    /// Compares two champions in this order, ties broken by id.
+    pub fn compare(self, a: &Champion, b: &Champion) -> Ordering {
+        let release = |champion: &Champion| {
+            champion
+                .community
+                .release_patch
+                .as_deref()
+                .map(patch_numbers)
+                .unwrap_or_default()
+        };
+        match self {
+            Order::Dex => Ordering::Equal,
+            Order::Alpha => fold(&a.name).cmp(&fold(&b.name)),
+            Order::Release => release(a).cmp(&release(b)),
+        }
+        .then(a.id.cmp(&b.id))
+    }
+}
+
+/// Puts `entries` in `order`, ties broken by dex number.
+pub fn sort(entries: &mut [DexEntry], order: Order) {
+    entries.sort_by(|a, b| order.compare(a.champion, b.champion));
+}
+
+impl CDragon {
+    /// The order to show the dex in when none is asked for, `order` under `[dex]` in
+    /// `blitzadex.toml`.
+    pub fn dex_order(&self) -> Result<Order> {
+        Ok(Config::load(&self.config_dir)?.dex.order)
+    }
+}
+
+/// The dex number of a single champion given the ids of the whole roster.
+pub fn number_of(ids: impl IntoIterator<Item = u64>, id: u64) -> Option<usize> {
+    let mut found = false;
+    let mut earlier = 0;
+    for other in ids {
+        found |= other == id;
+        if other < id {
+            earlier += 1;
+        }
+    }
+    found.then_some(earlier + 1)
+}
+
+/// Renders the dex as a grid of numbered slots, filled in by how far the user has got with each
+/// champion, followed by a completion summary.
+///
+/// ```text
+/// 001 ● 002 ◐ 003 ○ ...
+/// ```
+pub fn render_grid(entries: &[DexEntry]) -> String {
+    let mut out = String::new();
+    for row in entries.chunks(COLUMNS) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|entry| format!("{:03} {}", entry.number, symbol(entry.completion)))
+            .collect();
+        let _ = writeln!(out, "{}", cells.join("  "));
+    }
+    let played = entries
+        .iter()
+        .filter(|entry| entry.completion.is_some())
+        .count();
+    let mastered = entries
+        .iter()
+        .filter(|entry| entry.completion == Some(Completion::Mastered))
+        .count();
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "{} played {played}/{total}   {} mastered {mastered}/{total}",
+        symbol(Some(Completion::Played)),
+        symbol(Some(Completion::Mastered)),
+        total = entries.len()
+    );
+    out
+}
+
+fn symbol(completion: Option<Completion>) -> char {
+    match completion {
+        None => '○',
+        Some(Completion::Played) => '◐',
+        Some(Completion::Mastered) => '●',
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn roster() -> HashMap<u64, Champion> {
+        [(1, "Annie"), (2, "Olaf"), (3, "Galio")]
+            .into_iter()
+            .map(|(id, name)| {
+                (
+                    id,
+                    Champion {
+                        id,
+                        name: name.to_string(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn numbered_by_id() {
+        let champions = roster();
+        let entries = entries(&champions, &Profile::default());
+        let names: Vec<&str> = entries.iter().map(|e| e.champion.name.as_str()).collect();
+        assert_eq!(names, ["Annie", "Olaf", "Galio"]);
+        assert_eq!(number_of(champions.keys().copied(), 3), Some(3));
+        assert_eq!(number_of(champions.keys().copied(), 4), None);
+    }
+
+    #[test]
+    fn orders() {
+        let mut champions = roster();
+        champions.get_mut(&3).unwrap().community.release_patch = Some("V1.0.0.9".to_string());
+        champions.get_mut(&2).unwrap().community.release_patch = Some("V1.0.0.10".to_string());
+        let mut entries = entries(&champions, &Profile::default());
+        let names = |entries: &[DexEntry]| -> Vec<String> {
+            entries.iter().map(|e| e.champion.name.clone()).collect()
+        };
+        sort(&mut entries, Order::Alpha);
+        assert_eq!(names(&entries), ["Annie", "Galio", "Olaf"]);
+        assert_eq!(entries[1].number, 3);
+        sort(&mut entries, Order::Release);
+        assert_eq!(names(&entries), ["Annie", "Galio", "Olaf"]);
+        sort(&mut entries, Order::Dex);
+        assert_eq!(names(&entries), ["Annie", "Olaf", "Galio"]);
+        assert_eq!(
+            serde_json::from_str::<Order>("\"id\"").ok(),
+            Some(Order::Dex)
+        );
+    }
+
+    #[test]
+    fn mastered_counts_as_played() {
+        let champions = roster();
+        let mut profile = Profile::default();
+        profile.mark(1, Completion::Played);
+        profile.mark(2, Completion::Mastered);
+        let grid = render_grid(&entries(&champions, &profile));
+        assert!(grid.starts_with("001 ◐  002 ●  003 ○"));
+        assert!(grid.contains("played 2/3"));
+        assert!(grid.contains("mastered 1/3"));
+    }
+}
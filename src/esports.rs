@@ -0,0 +1,57 @@
+//! Art from the esports spectate plugin, like team logos and league branding, for broadcast
+//! overlays.
+//!
+//! The plugin has no index of its art, so the assets are found by walking its directory listing,
+//! see [`PluginAsset`].
+
+use color_eyre::Result;
+
+use crate::{assets::AssetStore, cdragon::CDragon, listing::PluginAsset};
+
+const PLUGIN_DIR: &str = "plugins/rcp-fe-lol-esports-spectate/global/default";
+/// How deep the plugin's directories are walked, leagues keep their team logos a few levels down
+const MAX_DEPTH: usize = 4;
+
+pub type EsportsAsset = PluginAsset;
+
+impl CDragon {
+    /// Every image in the esports spectate plugin.
+    pub async fn esports_assets(&self) -> Result<Vec<EsportsAsset>> {
+        self.plugin_images(PLUGIN_DIR, MAX_DEPTH).await
+    }
+
+    /// Downloads `asset` into `store` and returns its hash, see
+    /// [`CDragon::download_plugin_asset`].
+    pub async fn download_esports_asset(
+        &self,
+        store: &mut AssetStore,
+        asset: &EsportsAsset,
+    ) -> Result<String> {
+        self.download_plugin_asset(store, asset).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::listing::is_image;
+
+    #[test]
+    fn asset_paths() {
+        let logo = EsportsAsset {
+            plugin_dir: PLUGIN_DIR,
+            path: "images/Teams/T1.png".to_string(),
+            size: Some(2048),
+        };
+        assert_eq!(
+            logo.store_path(),
+            "plugins/rcp-fe-lol-esports-spectate/global/default/images/teams/t1.png"
+        );
+        assert_eq!(
+            logo.url(),
+            "https://raw.communitydragon.org/latest/plugins/rcp-fe-lol-esports-spectate/global/default/images/Teams/T1.png"
+        );
+        assert!(is_image("leagues/LCK.SVG"));
+        assert!(!is_image("trans.json") && !is_image("readme"));
+    }
+}
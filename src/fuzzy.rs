@@ -0,0 +1,131 @@
+//! Typo tolerant matching of search terms against names, for when a user doesn't quite remember
+//! how a champion is spelled.
+//!
+//! Both sides are [folded](fold) first, so "kai sa" and "ksante" already find Kai'Sa and K'Sante.
+//! Beyond that a term can match as a prefix, anywhere inside, as a subsequence starting at the
+//! same letter (`msfrtn` for Miss Fortune), or within a few edits of a word (`jinks` for Jinx).
+//! Better matches score higher so results can be ranked.
+
+use crate::names::fold;
+
+pub const EXACT: u32 = 1000;
+pub const PREFIX: u32 = 800;
+pub const SUBSTRING: u32 = 600;
+const SUBSEQUENCE: u32 = 400;
+const TYPO: u32 = 250;
+pub const WORD: u32 = 100;
+
+/// How many edits a term of `len` characters can be off by.
+fn max_edits(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        3..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    }
+}
+
+/// The optimal string alignment distance, edits counting insertions, deletions, substitutions and
+/// swapping two neighbours.
+fn distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// How many characters of `haystack` are skipped to find `needle` in it in order, when it starts
+/// at the same character.
+fn subsequence_gaps(needle: &str, haystack: &str) -> Option<usize> {
+    if needle.chars().next()? != haystack.chars().next()? {
+        return None;
+    }
+    let mut haystack = haystack.chars();
+    let mut gaps = 0;
+    for c in needle.chars() {
+        loop {
+            if haystack.next()? == c {
+                break;
+            }
+            gaps += 1;
+        }
+    }
+    Some(gaps)
+}
+
+/// How well a folded `term` matches `field`, or `None` when it doesn't.
+pub fn score(term: &str, field: &str) -> Option<u32> {
+    let folded = fold(field);
+    if term.is_empty() || folded.is_empty() {
+        return None;
+    }
+    if folded == term {
+        return Some(EXACT);
+    }
+    if folded.starts_with(term) {
+        return Some(PREFIX);
+    }
+    if folded.contains(term) {
+        return Some(SUBSTRING);
+    }
+    if let Some(gaps) = subsequence_gaps(term, &folded) {
+        return Some(SUBSEQUENCE.saturating_sub(10 * gaps as u32));
+    }
+    let term: Vec<char> = term.chars().collect();
+    let allowed = max_edits(term.len());
+    std::iter::once(folded)
+        .chain(field.split_whitespace().map(fold))
+        .map(|word| distance(&term, &word.chars().collect::<Vec<_>>()))
+        .min()
+        .filter(|edits| *edits <= allowed)
+        .map(|edits| TYPO - 50 * edits as u32)
+}
+
+/// Whether a folded `term` is one of the words of `text`, or starts one when it's long enough
+/// not to match everything. For prose like bios, where fuzzier matches are mostly noise.
+pub fn word_score(term: &str, text: &str) -> Option<u32> {
+    text.split_whitespace()
+        .map(fold)
+        .any(|word| word == term || (term.len() >= 4 && word.starts_with(term)))
+        .then_some(WORD)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ranks_and_tolerates_typos() {
+        assert_eq!(score("kaisa", "Kai'Sa"), Some(EXACT));
+        assert_eq!(score("ksante", "K'Sante"), Some(EXACT));
+        assert_eq!(score("miss", "Miss Fortune"), Some(PREFIX));
+        assert_eq!(score("fortune", "Miss Fortune"), Some(SUBSTRING));
+        assert!(score("msfrtn", "Miss Fortune").is_some_and(|s| s < SUBSTRING && s > TYPO));
+        assert_eq!(score("jinks", "Jinx"), Some(TYPO - 100));
+        assert_eq!(score("yasou", "Yasuo"), Some(TYPO - 50));
+        assert_eq!(score("fortnue", "Miss Fortune"), Some(TYPO - 50));
+        assert_eq!(score("jinx", "Annie"), None);
+        assert_eq!(score("ax", "Ahri"), None);
+
+        let bio = "Dangerous, yet disarmingly precocious, Annie is a child mage";
+        assert_eq!(word_score("annie", bio), Some(WORD));
+        assert_eq!(word_score("preco", bio), Some(WORD));
+        assert_eq!(word_score("ch", bio), None);
+    }
+}
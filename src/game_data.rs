@@ -0,0 +1,296 @@
+//! Champion data from the game files, as converted to JSON by CommunityDragon.
+//!
+//! The champion JSON in the game data plugin is what the client shows, which leaves out the real
+//! numbers. Those live in `game/data/characters/<alias>/<alias>.bin.json`, a flat map of every
+//! object in the champion's bin file keyed by its path (or by a hash when the path is unknown).
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    str::FromStr,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cdragon::{parse_json, CDragon},
+    http,
+};
+
+const GAME_URL: &str = "https://raw.communitydragon.org/latest/game";
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ResourceStats {
+    #[serde(rename = "arBase", default)]
+    pub base: f64,
+    #[serde(rename = "arPerLevel", default)]
+    pub per_level: f64,
+    #[serde(rename = "arBaseStaticRegen", default)]
+    pub regen: f64,
+    #[serde(rename = "arRegenPerLevel", default)]
+    pub regen_per_level: f64,
+}
+
+/// The champion's stats at level 1 and how much they grow per level, read from its
+/// CharacterRecord.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct BaseStats {
+    #[serde(rename = "baseHP", default)]
+    pub hp: f64,
+    #[serde(rename = "hpPerLevel", default)]
+    pub hp_per_level: f64,
+    #[serde(rename = "baseStaticHPRegen", default)]
+    pub hp_regen: f64,
+    #[serde(rename = "hpRegenPerLevel", default)]
+    pub hp_regen_per_level: f64,
+    #[serde(rename = "primaryAbilityResource", default)]
+    pub resource: ResourceStats,
+    #[serde(rename = "baseDamage", default)]
+    pub attack_damage: f64,
+    #[serde(rename = "damagePerLevel", default)]
+    pub attack_damage_per_level: f64,
+    #[serde(rename = "baseArmor", default)]
+    pub armor: f64,
+    #[serde(rename = "armorPerLevel", default)]
+    pub armor_per_level: f64,
+    #[serde(rename = "baseSpellBlock", default)]
+    pub magic_resist: f64,
+    #[serde(rename = "spellBlockPerLevel", default)]
+    pub magic_resist_per_level: f64,
+    #[serde(rename = "baseMoveSpeed", default)]
+    pub move_speed: f64,
+    #[serde(rename = "attackRange", default)]
+    pub attack_range: f64,
+    #[serde(rename = "attackSpeed", default)]
+    pub attack_speed: f64,
+    #[serde(rename = "attackSpeedRatio", default)]
+    pub attack_speed_ratio: f64,
+    /// In percent
+    #[serde(rename = "attackSpeedPerLevel", default)]
+    pub attack_speed_per_level: f64,
+}
+
+/// The per-rank numbers of one of a champion's spells.
+///
+/// The game files pad every per-rank list with a rank 0; it is dropped here, so index 0 is
+/// rank 1.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct SpellData {
+    pub script_name: String,
+    pub cooldown: Vec<f64>,
+    pub cost: Vec<f64>,
+    pub cast_range: Vec<f64>,
+    /// Named values used by the tooltip, e.g. "Damage" or "APRatio"
+    pub values: BTreeMap<String, Vec<f64>>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct ChampionGameData {
+    pub stats: BaseStats,
+    pub spells: Vec<SpellData>,
+}
+
+fn per_rank(value: Option<&Value>) -> Vec<f64> {
+    value
+        .and_then(Value::as_array)
+        .map(|ranks| ranks.iter().skip(1).filter_map(Value::as_f64).collect())
+        .unwrap_or_default()
+}
+
+impl ChampionGameData {
+    /// Picks the CharacterRecord and SpellObjects out of a converted bin file.
+    pub fn from_bin(bin: &Value, alias: &str) -> Result<Self> {
+        let objects = bin
+            .as_object()
+            .ok_or_else(|| eyre!("the bin file isn't an object"))?;
+        let root = objects
+            .iter()
+            .find(|(key, _)| {
+                key.eq_ignore_ascii_case(&format!("Characters/{alias}/CharacterRecords/Root"))
+            })
+            .map(|(_, record)| record)
+            .ok_or_else(|| eyre!("{alias} has no character record"))?;
+        let stats = BaseStats::deserialize(root).with_context(|| "unexpected character record")?;
+
+        let mut spells: Vec<SpellData> = objects
+            .values()
+            .filter(|obj| obj.get("__type").and_then(Value::as_str) == Some("SpellObject"))
+            .filter_map(|obj| {
+                let spell = obj.get("mSpell")?;
+                let values = spell
+                    .get("mDataValues")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|value| {
+                        let name = value.get("mName")?.as_str()?;
+                        Some((name.to_string(), per_rank(value.get("mValues"))))
+                    })
+                    .collect();
+                Some(SpellData {
+                    script_name: obj.get("mScriptName")?.as_str()?.to_string(),
+                    cooldown: per_rank(spell.get("cooldownTime")),
+                    cost: per_rank(spell.get("mana")),
+                    cast_range: per_rank(spell.get("castRange")),
+                    values,
+                })
+            })
+            .collect();
+        spells.sort_by(|a, b| a.script_name.cmp(&b.script_name));
+        Ok(Self { stats, spells })
+    }
+
+    pub fn spell(&self, script_name: &str) -> Option<&SpellData> {
+        self.spells
+            .iter()
+            .find(|spell| spell.script_name.eq_ignore_ascii_case(script_name))
+    }
+}
+
+/// One of a champion's abilities, written `<champion>:<key>`, e.g. "annie:q".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellRef {
+    pub champion: String,
+    /// Q, W, E or R
+    pub key: char,
+}
+
+impl FromStr for SpellRef {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (champion, key) = s
+            .rsplit_once(':')
+            .ok_or_else(|| eyre!("expected <champion>:<key>, e.g. annie:q"))?;
+        let key = match key.to_ascii_uppercase().as_str() {
+            key @ ("Q" | "W" | "E" | "R") => key.chars().next().unwrap_or_default(),
+            _ => return Err(eyre!("{key} isn't an ability key, expected Q, W, E or R")),
+        };
+        Ok(Self {
+            champion: champion.to_string(),
+            key,
+        })
+    }
+}
+
+impl SpellRef {
+    /// How many ranks the ability has, 3 for ultimates and 5 for the rest.
+    pub fn ranks(&self) -> usize {
+        match self.key {
+            'R' => 3,
+            _ => 5,
+        }
+    }
+}
+
+/// A Markdown table comparing two spells rank by rank: cooldown, cost, and the damage values and
+/// ratios the tooltips use. Each spell comes with the label for its column, e.g. "Annie Q".
+pub fn compare_spells<'a>(
+    a: (&str, &'a SpellData, usize),
+    b: (&str, &'a SpellData, usize),
+) -> String {
+    let mut metrics: Vec<(&str, &[f64], &[f64])> = vec![
+        ("cooldown", &a.1.cooldown, &b.1.cooldown),
+        ("cost", &a.1.cost, &b.1.cost),
+    ];
+    let compared = |name: &String| {
+        let lower = name.to_lowercase();
+        lower.contains("damage") || lower.contains("ratio")
+    };
+    let names: BTreeSet<&String> =
+        a.1.values
+            .keys()
+            .chain(b.1.values.keys())
+            .filter(|name| compared(name))
+            .collect();
+    let values = |spell: &'a SpellData, name: &str| -> &'a [f64] {
+        spell.values.get(name).map_or(&[], Vec::as_slice)
+    };
+    for name in names {
+        metrics.push((name, values(a.1, name), values(b.1, name)));
+    }
+
+    let cell = |values: &[f64], rank: usize, ranks: usize| match values.get(rank) {
+        Some(value) if rank < ranks => value.to_string(),
+        _ => "-".to_string(),
+    };
+    let mut out = String::new();
+    let _ = writeln!(out, "| | rank | {} | {} |", a.0, b.0);
+    let _ = writeln!(out, "|---|---|---|---|");
+    for (metric, in_a, in_b) in metrics {
+        for rank in 0..a.2.max(b.2) {
+            let _ = writeln!(
+                out,
+                "| {metric} | {} | {} | {} |",
+                rank + 1,
+                cell(in_a, rank, a.2),
+                cell(in_b, rank, b.2)
+            );
+        }
+    }
+    out
+}
+
+impl CDragon {
+    /// Fetches and parses a champion's converted bin file, `alias` being e.g. "MonkeyKing".
+    pub async fn fetch_champion_game_data(&self, alias: &str) -> Result<ChampionGameData> {
+        let lower = alias.to_lowercase();
+        let url = format!("{GAME_URL}/data/characters/{lower}/{lower}.bin.json");
+        let bytes = http::get(&self.http_client, &url)
+            .await
+            .with_context(|| format!("couldn't fetch the game data for {alias}"))?;
+        let bin: Value = parse_json(&bytes)?;
+        ChampionGameData::from_bin(&bin, alias)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn annie_bin() -> Result<()> {
+        let bin: Value = serde_json::from_str(&std::fs::read_to_string("testing/annie.bin.json")?)?;
+        let annie = ChampionGameData::from_bin(&bin, "Annie")?;
+        assert_eq!(annie.stats.hp, 560.);
+        assert_eq!(annie.stats.resource.base, 418.);
+        assert_eq!(annie.stats.attack_range, 625.);
+        assert_eq!(annie.spells.len(), 2);
+        let q = annie.spell("annieq").unwrap();
+        assert_eq!(q.cooldown[0], 4.);
+        assert_eq!(q.cost[..3], [60., 65., 70.]);
+        assert_eq!(q.values["Damage"][0], 80.);
+        assert!(annie.spell("AnnieR").unwrap().values.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn spell_comparison() -> Result<()> {
+        let bin: Value = serde_json::from_str(&std::fs::read_to_string("testing/annie.bin.json")?)?;
+        let annie = ChampionGameData::from_bin(&bin, "Annie")?;
+        let q: SpellRef = "annie:q".parse()?;
+        let r: SpellRef = "Annie:R".parse()?;
+        assert_eq!((q.key, r.ranks()), ('Q', 3));
+        assert!("annie".parse::<SpellRef>().is_err());
+        assert!("annie:p".parse::<SpellRef>().is_err());
+
+        let table = compare_spells(
+            ("Annie Q", annie.spell("AnnieQ").unwrap(), q.ranks()),
+            ("Annie R", annie.spell("AnnieR").unwrap(), r.ranks()),
+        );
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "| | rank | Annie Q | Annie R |");
+        assert_eq!(lines[2], "| cooldown | 1 | 4 | 130 |");
+        assert!(lines.contains(&"| cooldown | 4 | 4 | - |"));
+        assert!(lines.contains(&"| Damage | 2 | 115 | - |"));
+        assert!(lines.contains(&"| APRatio | 1 | 0.75 | - |"));
+        // Cooldown, cost, APRatio and Damage, five ranks each, under the header
+        assert_eq!(lines.len(), 2 + 4 * 5);
+        Ok(())
+    }
+}
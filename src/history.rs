@@ -0,0 +1,160 @@
+//! Delta encoded history of the cached catalogs.
+//!
+//! Rather than overwriting a catalog on update, the difference between the old and new document
+//! is recorded as a pair of JSON patches (RFC 6902) in `history/<catalog>`. The cache keeps only
+//! the latest document in full; older versions are rebuilt by applying the reverse patches
+//! newest to oldest, and the forward patches double as a changelog. Versions are labelled with
+//! the game patch the data was fetched for, see [`CDragon::data_version`].
+
+use std::fs;
+
+use chrono::{DateTime, Utc};
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use json_patch::Patch;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cdragon::{write_atomically, CDragon, CacheFile, Champion};
+
+const HISTORY: &str = "history";
+
+/// A single change to a catalog.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Revision {
+    /// The version before this change
+    pub from: String,
+    /// The version after this change
+    pub to: String,
+    pub recorded: DateTime<Utc>,
+    /// Turns the `from` document into the `to` document
+    pub forward: Patch,
+    /// Turns the `to` document back into the `from` document
+    pub reverse: Patch,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct History {
+    /// The version of the document currently in the cache
+    pub current: Option<String>,
+    /// Oldest first
+    pub revisions: Vec<Revision>,
+}
+
+impl History {
+    /// Every version that can be rebuilt, oldest first.
+    pub fn versions(&self) -> Vec<&str> {
+        let mut versions: Vec<&str> = self.revisions.iter().map(|rev| rev.from.as_str()).collect();
+        versions.extend(self.current.as_deref());
+        versions
+    }
+}
+
+impl CDragon {
+    /// The version label for freshly fetched data, the patch currently live upstream, e.g.
+    /// "15.1".
+    pub async fn data_version(&self) -> Result<String> {
+        Ok(self.fetch_patch_version().await?.patch().to_string())
+    }
+
+    pub fn history(&self, catalog: CacheFile) -> Result<History> {
+        let path = self.cache_dir.join(HISTORY).join(catalog.to_string());
+        if !path.try_exists().unwrap_or(false) {
+            return Ok(History::default());
+        }
+        let ser = fs::read_to_string(path)?;
+        serde_json::from_str(&ser).with_context(|| format!("failed to read the {catalog} history"))
+    }
+
+    fn save_history(&self, catalog: CacheFile, history: &History) -> Result<()> {
+        let dir = self.cache_dir.join(HISTORY);
+        fs::create_dir_all(&dir)?;
+        write_atomically(
+            &dir.join(catalog.to_string()),
+            serde_json::to_string(history)?,
+        )
+    }
+
+    /// Caches `obj` as `version` of the catalog, recording how it differs from the previously
+    /// cached version.
+    pub(crate) fn cache_versioned(
+        &self,
+        obj: &impl Serialize,
+        catalog: CacheFile,
+        version: &str,
+    ) -> Result<()> {
+        let new = serde_json::to_value(obj)?;
+        let mut history = self.history(catalog)?;
+        if let (Ok(old), Some(current)) = (self.load_obj::<Value>(catalog), &history.current) {
+            let forward = json_patch::diff(&old, &new);
+            if !forward.0.is_empty() {
+                history.revisions.push(Revision {
+                    from: current.clone(),
+                    to: version.to_string(),
+                    recorded: Utc::now(),
+                    forward,
+                    reverse: json_patch::diff(&new, &old),
+                });
+            }
+        }
+        history.current = Some(version.to_string());
+        self.cache_obj(&new, catalog)?;
+        self.save_history(catalog, &history)
+    }
+
+    /// Rebuilds the catalog as it was at `version`.
+    pub fn catalog_as_of(&self, catalog: CacheFile, version: &str) -> Result<Value> {
+        let history = self.history(catalog)?;
+        let mut doc: Value = self.load_obj(catalog)?;
+        if history.current.as_deref() == Some(version) {
+            return Ok(doc);
+        }
+        for rev in history.revisions.iter().rev() {
+            json_patch::patch(&mut doc, &rev.reverse)
+                .with_context(|| format!("the {catalog} history is inconsistent"))?;
+            if rev.from == version {
+                return Ok(doc);
+            }
+        }
+        Err(eyre!("no version {version} of {catalog} in the history"))
+    }
+
+    /// A champion as it was at `version`.
+    pub fn champion_as_of(&self, id: u64, version: &str) -> Result<Champion> {
+        let champions = self.catalog_as_of(CacheFile::Champions, version)?;
+        let champion = champions
+            .get(id.to_string())
+            .ok_or_else(|| eyre!("champion {id} didn't exist at {version}"))?;
+        Ok(serde_json::from_value(champion.clone())?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rebuilds_old_versions() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        let v1 = json!({"1": {"name": "Annie", "title": "the Dark Child"}});
+        let v2 = json!({"1": {"name": "Annie", "title": "the Dark Child"}, "2": {"name": "Olaf"}});
+        let v3 = json!({"1": {"name": "Annie", "title": "the Fire Child"}, "2": {"name": "Olaf"}});
+        cdrag.cache_versioned(&v1, CacheFile::Champions, "v1")?;
+        cdrag.cache_versioned(&v2, CacheFile::Champions, "v2")?;
+        // Unchanged data doesn't add a revision
+        cdrag.cache_versioned(&v2, CacheFile::Champions, "v2b")?;
+        cdrag.cache_versioned(&v3, CacheFile::Champions, "v3")?;
+
+        let history = cdrag.history(CacheFile::Champions)?;
+        assert_eq!(history.versions(), ["v1", "v2b", "v3"]);
+        assert_eq!(cdrag.catalog_as_of(CacheFile::Champions, "v3")?, v3);
+        assert_eq!(cdrag.catalog_as_of(CacheFile::Champions, "v2b")?, v2);
+        assert_eq!(cdrag.catalog_as_of(CacheFile::Champions, "v1")?, v1);
+        assert!(cdrag.catalog_as_of(CacheFile::Champions, "v0").is_err());
+        Ok(())
+    }
+}
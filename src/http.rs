@@ -0,0 +1,112 @@
+//! The one place requests to CommunityDragon are sent from.
+//!
+//! CommunityDragon is served through a CDN that answers with 429 (or 503 while under load) when
+//! it's being hit too hard, as happens when fetching every champion at once. Those responses are
+//! retried after the delay the `Retry-After` header asks for rather than failing the whole fetch.
+//!
+//! Requests are timed by a "network" span, see `--timing`.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::Context, Result};
+use reqwest::{header::RETRY_AFTER, Client, Response, StatusCode};
+
+use crate::progress;
+
+const MAX_RETRIES: u32 = 5;
+/// Upper bound on a single wait, in case a server asks for something unreasonable
+const MAX_WAIT: Duration = Duration::from_secs(120);
+
+/// Parses a `Retry-After` value, either a number of seconds or an HTTP date.
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// How long to wait before retrying `res`, or `None` if it isn't a rate limiting response.
+///
+/// A 429 without `Retry-After` backs off exponentially from 1 second. A 503 without it is
+/// treated as a real failure.
+fn backoff(res: &Response, attempt: u32) -> Option<Duration> {
+    let status = res.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+    let retry_after = res
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, Utc::now()));
+    match retry_after {
+        Some(wait) => Some(wait.min(MAX_WAIT)),
+        None if status == StatusCode::TOO_MANY_REQUESTS => Some(Duration::from_secs(1 << attempt)),
+        None => None,
+    }
+}
+
+/// Fetches the body of `url`, waiting and retrying while the server is rate limiting.
+///
+/// Any other unsuccessful status is an error.
+pub(crate) async fn get(client: &Client, url: &str) -> Result<Vec<u8>> {
+    // Timed from creation to drop, so it isn't entered across the awaits
+    let _span = tracing::info_span!("network");
+    let res = send(client, url).await?.error_for_status()?;
+    let bytes = res.bytes().await?.to_vec();
+    progress::downloaded(bytes.len());
+    Ok(bytes)
+}
+
+async fn send(client: &Client, url: &str) -> Result<Response> {
+    let mut attempt = 0;
+    loop {
+        let res = client
+            .get(url)
+            .send()
+            .await
+            .with_context(|| format!("couldn't reach {url}"))?;
+        match backoff(&res, attempt) {
+            Some(wait) if attempt < MAX_RETRIES => {
+                progress::notice(format_args!(
+                    "backing off for {}s, {} from {url}",
+                    wait.as_secs_f64().ceil(),
+                    res.status()
+                ));
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            _ => return Ok(res),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_after_formats() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("30", now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:10 GMT", now),
+            Some(Duration::from_secs(10))
+        );
+        // A date in the past means retry right away
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
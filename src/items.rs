@@ -0,0 +1,29 @@
+//! Items from the game data plugin's items.json.
+
+use color_eyre::{eyre::Context, Result};
+
+pub use blitzadex_types::{Item, ItemStat};
+
+use crate::{
+    cdragon::{asset_url, parse_json, CDragon, CacheFile},
+    http,
+};
+
+impl CDragon {
+    /// Loads the cached items, fetching them when they aren't cached yet.
+    pub async fn items(&self) -> Result<Vec<Item>> {
+        if let Ok(items) = self.load_obj(CacheFile::Items) {
+            return Ok(items);
+        }
+        let items = self.fetch_items().await?;
+        self.cache_obj(&items, CacheFile::Items)?;
+        Ok(items)
+    }
+
+    pub async fn fetch_items(&self) -> Result<Vec<Item>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/items.json"))
+            .await
+            .with_context(|| "couldn't fetch the items")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
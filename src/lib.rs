@@ -0,0 +1,67 @@
+pub mod announce;
+pub mod archetype;
+pub mod arena;
+pub mod assets;
+pub mod audio;
+pub mod backfill;
+pub mod card;
+pub mod cdragon;
+pub mod challenges;
+pub mod chroma;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod codegen;
+#[cfg(feature = "image")]
+pub mod collage;
+pub mod community;
+pub mod compare;
+pub mod cosmetics;
+#[cfg(all(feature = "cli", unix))]
+pub mod daemon;
+pub mod dex;
+pub mod esports;
+pub mod fuzzy;
+pub mod game_data;
+pub mod history;
+mod http;
+pub mod items;
+pub mod list;
+pub mod listing;
+pub mod lock;
+pub mod loot;
+pub mod manifest;
+#[cfg(feature = "image")]
+pub mod mastery;
+pub mod missions;
+pub mod names;
+pub mod pack;
+pub mod patch;
+pub mod patches;
+pub mod perks;
+pub mod profile;
+pub mod progress;
+pub mod projection;
+pub mod provenance;
+pub mod query;
+pub mod queues;
+pub mod random;
+pub mod range;
+pub mod recommend;
+pub mod selftest;
+pub mod similarity;
+pub mod skins;
+pub mod statstones;
+pub mod status;
+pub mod strings;
+pub mod summoner_spells;
+pub mod tft;
+#[cfg(feature = "image")]
+pub mod thumbnail;
+#[cfg(feature = "image")]
+pub mod tierlist;
+#[cfg(feature = "cli")]
+pub mod timing;
+pub mod trend;
+pub mod update;
+pub mod warm;
+pub mod whatis;
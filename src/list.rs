@@ -0,0 +1,141 @@
+//! Tables of the cached catalogs, for reading at a glance in a terminal.
+
+use std::{collections::HashMap, fmt::Write};
+
+use color_eyre::{eyre::Context, Result};
+use strum::Display;
+
+use crate::{
+    cdragon::{CDragon, CacheFile, Champion},
+    names::fold,
+};
+
+const HEADINGS: [&str; 5] = ["id", "name", "title", "roles", "damage"];
+
+/// What to order the champion table by, ties are broken by name. Names and titles are compared
+/// [folded](fold).
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[strum(serialize_all = "lowercase")]
+pub enum Sort {
+    Id,
+    #[default]
+    Name,
+    Title,
+    Damage,
+}
+
+/// The damage type without the `k` prefix, e.g. "Magic".
+fn damage(champion: &Champion) -> &str {
+    champion.tactical_info.damage_type.trim_start_matches('k')
+}
+
+/// The champions with `role`, or every champion, in `sort` order.
+pub fn champions<'a>(
+    champions: &'a HashMap<u64, Champion>,
+    sort: Sort,
+    role: Option<&str>,
+) -> Vec<&'a Champion> {
+    let mut listed: Vec<&Champion> = champions
+        .values()
+        .filter(|champion| {
+            role.is_none_or(|role| champion.roles.iter().any(|r| r.eq_ignore_ascii_case(role)))
+        })
+        .collect();
+    listed.sort_by(|a, b| {
+        let key = match sort {
+            Sort::Id => a.id.cmp(&b.id),
+            Sort::Name => fold(&a.name).cmp(&fold(&b.name)),
+            Sort::Title => fold(&a.title).cmp(&fold(&b.title)),
+            Sort::Damage => damage(a).cmp(damage(b)),
+        };
+        key.then_with(|| a.name.cmp(&b.name))
+    });
+    listed
+}
+
+/// Renders `champions` as a table with a column each for the id, name, title, roles and damage
+/// type.
+pub fn render_champions(champions: &[&Champion]) -> String {
+    let rows: Vec<[String; 5]> = champions
+        .iter()
+        .map(|champion| {
+            [
+                champion.id.to_string(),
+                champion.name.clone(),
+                champion.title.clone(),
+                champion.roles.join(", "),
+                damage(champion).to_string(),
+            ]
+        })
+        .collect();
+    let mut widths = HEADINGS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    let headings = HEADINGS.map(str::to_string);
+    for row in std::iter::once(&headings).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| match column {
+                0 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect();
+        let _ = writeln!(out, "{}", cells.join("  ").trim_end());
+    }
+    out
+}
+
+impl CDragon {
+    /// The cached champions with their community metadata, without fetching them when they
+    /// aren't cached.
+    pub fn cached_champions(&self) -> Result<HashMap<u64, Champion>> {
+        let mut champions: HashMap<u64, Champion> = self
+            .load_obj(CacheFile::Champions)
+            .with_context(|| "no champions are cached, run `blitzadex update` first")?;
+        let overlay = self.community()?;
+        for champ in champions.values_mut() {
+            champ.community = overlay.get(&champ.alias).cloned().unwrap_or_default();
+        }
+        Ok(champions)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn champion_table() -> Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let garen = Champion {
+            id: 86,
+            name: "Garen".to_string(),
+            title: "The Might of Demacia".to_string(),
+            roles: vec!["fighter".to_string(), "tank".to_string()],
+            ..Default::default()
+        };
+        let roster = HashMap::from([(annie.id, annie), (garen.id, garen)]);
+
+        assert_eq!(champions(&roster, Sort::Title, None)[0].name, "Annie");
+        assert_eq!(champions(&roster, Sort::Damage, None)[0].name, "Garen");
+        assert_eq!(champions(&roster, Sort::Id, None)[0].name, "Annie");
+        let tanks = champions(&roster, Sort::Name, Some("Tank"));
+        assert_eq!(tanks.len(), 1);
+
+        let table = render_champions(&champions(&roster, Sort::Id, None));
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("id  name   title"));
+        assert!(lines[1].starts_with(" 1  Annie  the Dark Child"));
+        assert!(lines[1].ends_with("Magic"));
+        assert!(lines[2].contains("fighter, tank"));
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+//! A typed client for CommunityDragon's `/json` directory listing API.
+//!
+//! Any directory on raw.communitydragon.org can be listed by prefixing its path with `/json`,
+//! which returns an array of [`DirEntry`]s.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub use blitzadex_types::EntryType;
+
+use crate::{
+    assets::AssetStore,
+    cdragon::{mtime_format, parse_json, CDragon},
+    http,
+};
+
+const JSON_API_URL: &str = "https://raw.communitydragon.org/json";
+const RAW_URL: &str = "https://raw.communitydragon.org/latest";
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "svg", "webp"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DirEntry {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: EntryType,
+    #[serde(with = "mtime_format")]
+    pub mtime: DateTime<Utc>,
+    /// Only files have a size
+    pub size: Option<u64>,
+}
+
+impl DirEntry {
+    pub fn is_dir(&self) -> bool {
+        self.ty == EntryType::Directory
+    }
+}
+
+/// Joins listing path segments, ignoring stray slashes so callers can pass either
+/// "latest/plugins" or "/latest/plugins/".
+pub fn join(parent: &str, child: &str) -> String {
+    let parent = parent.trim_matches('/');
+    let child = child.trim_matches('/');
+    match (parent.is_empty(), child.is_empty()) {
+        (true, _) => child.to_string(),
+        (_, true) => parent.to_string(),
+        _ => format!("{parent}/{child}"),
+    }
+}
+
+/// A file of a plugin without an index of its art, found by walking the plugin's listing, see
+/// [`CDragon::plugin_images`].
+///
+/// Downloads go into the [`AssetStore`](crate::assets::AssetStore) keyed by their path from the
+/// root of the patch, e.g. "plugins/rcp-fe-lol-esports-spectate/global/default/...", which can't
+/// collide with the game data's Normalized Paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginAsset {
+    /// e.g. "plugins/rcp-fe-lol-esports-spectate/global/default"
+    pub plugin_dir: &'static str,
+    /// Relative to the plugin's directory, e.g. "images/teams/t1.png"
+    pub path: String,
+    pub size: Option<u64>,
+}
+
+impl PluginAsset {
+    /// The key of the asset in the asset store.
+    pub fn store_path(&self) -> String {
+        join(self.plugin_dir, &self.path).to_lowercase()
+    }
+
+    pub fn url(&self) -> String {
+        format!("{RAW_URL}/{}", join(self.plugin_dir, &self.path))
+    }
+}
+
+pub(crate) fn is_image(path: &str) -> bool {
+    path.rsplit_once('.').is_some_and(|(_, ext)| {
+        IMAGE_EXTENSIONS
+            .iter()
+            .any(|image| image.eq_ignore_ascii_case(ext))
+    })
+}
+
+impl CDragon {
+    /// Every image beneath `plugin_dir`, walking at most `max_depth` directories deep.
+    pub async fn plugin_images(
+        &self,
+        plugin_dir: &'static str,
+        max_depth: usize,
+    ) -> Result<Vec<PluginAsset>> {
+        let files = self
+            .list_dir_recursive(&join("latest", plugin_dir), max_depth)
+            .await
+            .with_context(|| format!("couldn't list {plugin_dir}"))?;
+        let mut assets: Vec<PluginAsset> = files
+            .into_iter()
+            .filter(|(path, _)| is_image(path))
+            .map(|(path, entry)| PluginAsset {
+                plugin_dir,
+                path,
+                size: entry.size,
+            })
+            .collect();
+        assets.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(assets)
+    }
+
+    /// Downloads `asset` into `store` and returns its hash. The store's index is left for the
+    /// caller to flush, so a batch of downloads writes it once.
+    pub async fn download_plugin_asset(
+        &self,
+        store: &mut AssetStore,
+        asset: &PluginAsset,
+    ) -> Result<String> {
+        let bytes = http::get(&self.http_client, &asset.url())
+            .await
+            .with_context(|| format!("couldn't download {}", asset.path))?;
+        store
+            .put(&asset.store_path(), &bytes)
+            .with_context(|| format!("couldn't store {}", asset.path))
+    }
+
+    /// Lists the directory at `path`, relative to the root of raw.communitydragon.org, e.g.
+    /// "latest/plugins".
+    ///
+    /// The entries are deserialized into `T`, which is usually [`DirEntry`] but can be any type
+    /// with the same shape, like [`crate::cdragon::Plugin`].
+    pub async fn list_dir<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        // Without the trailing slash CDragon answers with a redirect
+        let url = format!("{}/", join(JSON_API_URL, path));
+        let res = http::get(&self.http_client, &url)
+            .await
+            .with_context(|| format!("couldn't list {path}"))?;
+        let entries = parse_json(&res).with_context(|| format!("unexpected listing for {path}"))?;
+        Ok(entries)
+    }
+
+    /// Lists every file beneath `path`, walking at most `max_depth` directories deep. The
+    /// returned paths are relative to `path`.
+    ///
+    /// Directories are walked breadth first, one request per directory.
+    pub async fn list_dir_recursive(
+        &self,
+        path: &str,
+        max_depth: usize,
+    ) -> Result<Vec<(String, DirEntry)>> {
+        let mut files = vec![];
+        let mut queue = VecDeque::from([(String::new(), 0)]);
+        while let Some((dir, depth)) = queue.pop_front() {
+            let entries: Vec<DirEntry> = self.list_dir(&join(path, &dir)).await?;
+            for entry in entries {
+                let entry_path = join(&dir, &entry.name);
+                if !entry.is_dir() {
+                    files.push((entry_path, entry));
+                } else if depth < max_depth {
+                    queue.push_back((entry_path, depth + 1));
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plugin_listing() -> Result<()> {
+        let entries: Vec<DirEntry> =
+            serde_json::from_str(&std::fs::read_to_string("testing/plugins.json")?)?;
+        let game_data = entries
+            .iter()
+            .find(|entry| entry.name == "rcp-be-lol-game-data")
+            .unwrap();
+        assert!(game_data.is_dir());
+        assert_eq!(game_data.size, None);
+        Ok(())
+    }
+
+    #[test]
+    fn joins_without_stray_slashes() {
+        assert_eq!(
+            join("/latest/plugins/", "rcp-fe-audio/"),
+            "latest/plugins/rcp-fe-audio"
+        );
+        assert_eq!(join("", "latest"), "latest");
+        assert_eq!(join("latest", ""), "latest");
+    }
+}
@@ -0,0 +1,77 @@
+//! A lock on the cache directory, so processes rewriting several catalogs at once, like an update
+//! run by hand while another runs in the background, don't interleave their writes.
+//!
+//! It's an advisory lock on a `.lock` file in the cache directory, released when the
+//! [`CacheLock`] is dropped or the process exits, however it exits.
+
+use std::{
+    fs::{self, File, TryLockError},
+    path::Path,
+    time::Duration,
+};
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::progress;
+
+const LOCK_FILE: &str = ".lock";
+/// How often a waiting [`CacheLock::acquire`] tries the lock again
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug)]
+pub struct CacheLock {
+    _file: File,
+}
+
+impl CacheLock {
+    /// Locks `cache_dir`. When another process holds the lock, either waits for it to be released
+    /// or fails with a message saying so.
+    ///
+    /// Waiting polls the lock rather than blocking on it, which would stall the runtime's only
+    /// thread.
+    pub async fn acquire(cache_dir: &Path, wait: bool) -> Result<Self> {
+        fs::create_dir_all(cache_dir)?;
+        let file = File::create(cache_dir.join(LOCK_FILE))?;
+        let mut waiting = false;
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) if wait => {
+                    if !waiting {
+                        progress::notice("waiting for another update to finish…");
+                        waiting = true;
+                    }
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(eyre!(
+                        "another update is in progress, pass --wait to wait for it to finish"
+                    ))
+                }
+                Err(TryLockError::Error(err)) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn one_holder_at_a_time() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let held = CacheLock::acquire(dir.path(), false).await?;
+        assert!(CacheLock::acquire(dir.path(), false).await.is_err());
+        drop(held);
+        let held = CacheLock::acquire(dir.path(), false).await?;
+
+        // Waiting leaves the runtime free to run the task that releases the lock
+        tokio::spawn(async move {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            drop(held);
+        });
+        CacheLock::acquire(dir.path(), true).await?;
+        Ok(())
+    }
+}
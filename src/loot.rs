@@ -0,0 +1,177 @@
+//! Hextech crafting's reference data: the loot items, their rarities and the recipes that open,
+//! disenchant or upgrade them.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(
+    Debug, Display, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize,
+)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum LootRarity {
+    #[default]
+    Default,
+    Epic,
+    Legendary,
+    Mythic,
+    Ultimate,
+    /// A rarity blitzadex doesn't know yet
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LootItem {
+    /// e.g. "CHEST_128"
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// [Normalized Path](crate::cdragon::Skin), empty for items without an image
+    #[serde(default, deserialize_with = "deserialize_asset_path")]
+    pub image: String,
+    #[serde(default)]
+    pub rarity: LootRarity,
+    /// e.g. "CHEST", "MATERIAL"
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// Empty when the item isn't limited in time
+    #[serde(default)]
+    pub start_date: String,
+    #[serde(default)]
+    pub end_date: String,
+    #[serde(default)]
+    pub mapped_store_id: u64,
+    /// How many a player can ever get, -1 for no limit
+    #[serde(default)]
+    pub lifetime_max: i64,
+    #[serde(default)]
+    pub auto_redeem: bool,
+}
+
+/// An ingredient of a recipe.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeSlot {
+    pub slot_number: u64,
+    /// Which loot goes into the slot, e.g. "lootId == 'MATERIAL_key'"
+    pub query: String,
+    pub quantity_expression: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecipeOutput {
+    /// A loot item or a loot table the reward is drawn from
+    pub loot_name: String,
+    #[serde(default)]
+    pub quantity_expression: String,
+    #[serde(default)]
+    pub probability: f64,
+    #[serde(default)]
+    pub allow_duplicates: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LootRecipe {
+    /// e.g. "CHEST_128_OPEN"
+    pub id: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub context_menu_text: String,
+    /// e.g. "OPEN", "DISENCHANT", "UPGRADE"
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub slots: Vec<RecipeSlot>,
+    #[serde(default)]
+    pub outputs: Vec<RecipeOutput>,
+}
+
+/// `loot.json`
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Loot {
+    #[serde(rename = "LootItems", default)]
+    pub items: Vec<LootItem>,
+    #[serde(rename = "LootRecipes", default)]
+    pub recipes: Vec<LootRecipe>,
+}
+
+impl Loot {
+    pub fn item(&self, id: &str) -> Option<&LootItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn of_rarity(&self, rarity: LootRarity) -> impl Iterator<Item = &LootItem> {
+        self.items.iter().filter(move |item| item.rarity == rarity)
+    }
+
+    /// The recipes with a slot taking the loot item `id`.
+    pub fn recipes_using<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a LootRecipe> {
+        let quoted = format!("'{id}'");
+        self.recipes
+            .iter()
+            .filter(move |recipe| recipe.slots.iter().any(|slot| slot.query.contains(&quoted)))
+    }
+}
+
+impl CDragon {
+    /// Loads the cached loot, fetching it when it isn't cached yet.
+    pub async fn loot(&self) -> Result<Loot> {
+        if let Ok(loot) = self.load_obj(CacheFile::Loot) {
+            return Ok(loot);
+        }
+        let loot = self.fetch_loot().await?;
+        self.cache_obj(&loot, CacheFile::Loot)?;
+        Ok(loot)
+    }
+
+    pub async fn fetch_loot(&self) -> Result<Loot> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/loot.json"))
+            .await
+            .with_context(|| "couldn't fetch the loot")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn items_and_recipes() -> Result<()> {
+        let loot: Loot = serde_json::from_str(&std::fs::read_to_string("testing/loot.json")?)?;
+        let chest = loot.item("CHEST_128").unwrap();
+        assert_eq!(chest.image, "assets/loot/chest_generic.png");
+        assert_eq!(chest.kind, "CHEST");
+        let seasonal = loot.item("CHEST_seasonal").unwrap();
+        assert_eq!(seasonal.rarity, LootRarity::Other);
+        assert_eq!(seasonal.image, "");
+        let epic: Vec<&str> = loot
+            .of_rarity(LootRarity::Epic)
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(epic, ["Masterwork Chest"]);
+
+        let opening: Vec<&str> = loot
+            .recipes_using("MATERIAL_key")
+            .map(|recipe| recipe.id.as_str())
+            .collect();
+        assert_eq!(opening, ["CHEST_128_OPEN", "CHEST_187_OPEN"]);
+        assert_eq!(loot.recipes_using("CHEST_187").count(), 1);
+        assert_eq!(loot.recipes[1].outputs[0].probability, 0.5);
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
-mod cdragon;
+use blitzadex::cli::Cli;
+use clap::Parser;
 
-use cdragon::{CDragon, PluginName};
-use color_eyre::eyre::Result;
-
-#[tokio::main]
-async fn main() -> color_eyre::Result<()> {
+fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
-
-    Ok(())
+    let cli = Cli::parse();
+    // Every command is I/O bound, so a single threaded runtime is plenty. Building it after
+    // parsing keeps `--help` and argument errors from paying for it at all.
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(cli.run())
 }
@@ -0,0 +1,154 @@
+//! Every known asset of a champion in one document, for tools that mirror or audit them.
+
+use serde::Serialize;
+
+use crate::{
+    assets::AssetStore,
+    cdragon::{asset_url, Champion, ABILITY_VIDEO_URL},
+    provenance::Provenance,
+    whatis::normalize,
+};
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct ManifestEntry {
+    /// What the asset is, e.g. "splash" or "choose-vo"
+    pub kind: &'static str,
+    /// The champion, skin or spell the asset belongs to
+    pub owner: String,
+    /// The [Normalized Path](crate::cdragon::Skin), or the path on the video host for ability
+    /// videos
+    pub path: String,
+    pub url: String,
+    /// Whether the asset is in the local asset store
+    pub cached: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AssetManifest {
+    pub id: u64,
+    pub champion: String,
+    pub assets: Vec<ManifestEntry>,
+    pub provenance: Provenance,
+}
+
+/// Lists the assets of `champion` referenced by its data, skipping any that are empty.
+pub fn build(champion: &Champion, store: &AssetStore, provenance: Provenance) -> AssetManifest {
+    let mut assets = vec![];
+    let mut push = |kind, owner: &str, path: &str| {
+        let path = normalize(path);
+        if !path.is_empty() {
+            assets.push(ManifestEntry {
+                kind,
+                owner: owner.to_string(),
+                url: asset_url(&path),
+                cached: store.hash_of(&path).is_some(),
+                path,
+            });
+        }
+    };
+    let name = &champion.name;
+    push("portrait", name, &champion.square_portrait_path);
+    push("choose-vo", name, &champion.choose_vo_path.path);
+    push("ban-vo", name, &champion.ban_vo_path.path);
+    push("stinger-sfx", name, &champion.stinger_sfx_path.path);
+    for skin in &champion.skins {
+        push("splash", &skin.name, &skin.splash_path);
+        push(
+            "uncentered-splash",
+            &skin.name,
+            &skin.uncentered_splash_path,
+        );
+        push("tile", &skin.name, &skin.tile_path);
+        push("load-screen", &skin.name, &skin.load_screen_path);
+        let optional = [
+            ("vintage-load-screen", &skin.load_screen_vintage_path),
+            ("splash-video", &skin.splash_video_path),
+        ];
+        for (kind, path) in optional {
+            push(kind, &skin.name, path.as_deref().unwrap_or_default());
+        }
+        for chroma in &skin.chromas {
+            push(
+                "chroma",
+                &chroma.name,
+                chroma.chroma_path.as_deref().unwrap_or_default(),
+            );
+        }
+    }
+    let passive = &champion.passive;
+    let abilities = champion
+        .spells
+        .iter()
+        .map(|spell| {
+            (
+                &spell.name,
+                &spell.ability_icon_path,
+                &spell.ability_video_path,
+            )
+        })
+        .chain([(
+            &passive.name,
+            &passive.ability_icon_path,
+            &passive.ability_video_path,
+        )]);
+    for (name, icon_path, _) in abilities.clone() {
+        push("ability-icon", name, icon_path);
+    }
+    // Ability videos live on a different host, so they can't go through `push`
+    for (name, _, video_path) in abilities {
+        if !video_path.is_empty() {
+            assets.push(ManifestEntry {
+                kind: "ability-video",
+                owner: name.clone(),
+                path: video_path.clone(),
+                url: format!("{ABILITY_VIDEO_URL}/{video_path}"),
+                cached: store.hash_of(video_path).is_some(),
+            });
+        }
+    }
+    AssetManifest {
+        id: champion.id,
+        champion: champion.name.clone(),
+        assets,
+        provenance,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color_eyre::Result;
+
+    #[test]
+    fn annie_manifest() -> Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let dir = tempfile::tempdir()?;
+        let mut store = AssetStore::open(dir.path())?;
+        store.put("v1/champion-icons/1.png", b"portrait")?;
+
+        let manifest = build(&annie, &store, Provenance::new(None));
+        let portrait = &manifest.assets[0];
+        assert_eq!(portrait.kind, "portrait");
+        assert!(portrait.cached);
+        assert!(portrait
+            .url
+            .ends_with("/global/default/v1/champion-icons/1.png"));
+        assert!(manifest.assets.iter().any(|a| a.kind == "choose-vo"));
+        let q_video = manifest
+            .assets
+            .iter()
+            .find(|a| a.kind == "ability-video")
+            .unwrap();
+        assert_eq!(
+            q_video.url,
+            "https://d28xe8vt774jo5.cloudfront.net/champion-abilities/0001/ability_0001_Q1.webm"
+        );
+        assert!(!q_video.cached);
+        assert!(manifest
+            .assets
+            .iter()
+            .any(|a| a.kind == "ability-video" && a.owner == "Pyromania"));
+        Ok(())
+    }
+}
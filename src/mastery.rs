@@ -0,0 +1,117 @@
+//! Champion mastery crests, and overlays of a crest on a champion's portrait for stream widgets.
+//!
+//! The crests come from the shared components plugin, one per level from 0 to [`MAX_LEVEL`];
+//! levels past it keep the highest crest.
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use image::{imageops, DynamicImage, Rgba, RgbaImage};
+
+use crate::{
+    cdragon::{CDragon, Champion},
+    listing::PluginAsset,
+    whatis::normalize,
+};
+
+const PLUGIN_DIR: &str = "plugins/rcp-fe-lol-shared-components/global/default";
+pub const MAX_LEVEL: u32 = 10;
+/// How much of the portrait's width the crest spans
+const CREST_SCALE: f64 = 0.6;
+
+/// The crest of a mastery level.
+pub fn crest(level: u32) -> PluginAsset {
+    PluginAsset {
+        plugin_dir: PLUGIN_DIR,
+        path: format!("mastery-{}.png", level.min(MAX_LEVEL)),
+        size: None,
+    }
+}
+
+/// Scales `portrait` to `size` pixels square and lays `crest` over the middle of its bottom
+/// edge, hanging below it by a third of the crest's height.
+pub fn overlay(portrait: &RgbaImage, crest: &RgbaImage, size: u32) -> RgbaImage {
+    let portrait = DynamicImage::from(portrait.clone())
+        .resize_exact(size, size, imageops::FilterType::Triangle)
+        .to_rgba8();
+    let crest_width = ((size as f64 * CREST_SCALE).round() as u32).max(1);
+    let crest_height =
+        ((crest.height() as u64 * crest_width as u64 / crest.width().max(1) as u64) as u32).max(1);
+    let crest = imageops::resize(
+        crest,
+        crest_width,
+        crest_height,
+        imageops::FilterType::Triangle,
+    );
+    let overhang = crest_height / 3;
+    let mut canvas = RgbaImage::from_pixel(size, size + overhang, Rgba([0, 0, 0, 0]));
+    imageops::overlay(&mut canvas, &portrait, 0, 0);
+    imageops::overlay(
+        &mut canvas,
+        &crest,
+        ((size - crest_width) / 2).into(),
+        (size + overhang - crest_height).into(),
+    );
+    canvas
+}
+
+impl CDragon {
+    /// The crest of a mastery level, from the asset store or downloaded into it.
+    pub async fn mastery_crest(&self, level: u32) -> Result<RgbaImage> {
+        let asset = crest(level);
+        let mut store = self.asset_store()?;
+        if store.get(&asset.store_path()).is_none() {
+            self.download_plugin_asset(&mut store, &asset).await?;
+            store.flush()?;
+        }
+        let path = store.get(&asset.store_path());
+        let crest = path
+            .map(image::open)
+            .ok_or_else(|| eyre!("the level {level} crest isn't stored"))?
+            .with_context(|| format!("the level {level} crest isn't an image"))?;
+        Ok(crest.to_rgba8())
+    }
+
+    /// The champion's portrait with the crest of a mastery level over it, see [`overlay`].
+    pub async fn mastery_overlay(
+        &self,
+        champion: &Champion,
+        level: u32,
+        size: u32,
+    ) -> Result<RgbaImage> {
+        let bytes = self
+            .stored_asset(&normalize(&champion.square_portrait_path))
+            .await?;
+        let portrait = image::load_from_memory(&bytes)
+            .with_context(|| format!("the portrait of {} isn't an image", champion.name))?;
+        Ok(overlay(
+            &portrait.to_rgba8(),
+            &self.mastery_crest(level).await?,
+            size,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn crest_over_portrait() {
+        assert_eq!(crest(7).path, "mastery-7.png");
+        assert_eq!(
+            crest(42).store_path(),
+            "plugins/rcp-fe-lol-shared-components/global/default/mastery-10.png"
+        );
+
+        let portrait = RgbaImage::from_pixel(120, 120, Rgba([0, 0, 255, 255]));
+        let crest = RgbaImage::from_pixel(50, 30, Rgba([255, 215, 0, 255]));
+        let widget = overlay(&portrait, &crest, 100);
+        // The crest is 60 by 36 and hangs 12 pixels below the portrait
+        assert_eq!(widget.dimensions(), (100, 112));
+        assert_eq!(widget.get_pixel(50, 110), &Rgba([255, 215, 0, 255]));
+        assert_eq!(widget.get_pixel(50, 10), &Rgba([0, 0, 255, 255]));
+        assert_eq!(widget.get_pixel(5, 110), &Rgba([0, 0, 0, 0]));
+    }
+}
@@ -0,0 +1,91 @@
+//! Mission and event art: the icons missions refer to by internal name, from
+//! mission-assets.json, and the event hub plugin's art, like event banners and token icons.
+//!
+//! The event shop's offers and token balances aren't static data, the client gets them from
+//! Riot's live services, so only the art is here.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+    listing::PluginAsset,
+    names::fold,
+};
+
+const EVENT_HUB_DIR: &str = "plugins/rcp-fe-lol-event-hub/global/default";
+/// How deep the event hub's directories are walked, each event keeps its art in its own
+const EVENT_HUB_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionAsset {
+    pub id: u64,
+    /// What missions refer to the asset by, e.g. "Event_SpiritBlossom_Token"
+    pub internal_name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub path: String,
+}
+
+/// Finds a mission asset by internal name, ignoring case and underscores.
+pub fn named<'a>(assets: &'a [MissionAsset], internal_name: &str) -> Option<&'a MissionAsset> {
+    let internal_name = fold(internal_name);
+    assets
+        .iter()
+        .find(|asset| fold(&asset.internal_name) == internal_name)
+}
+
+/// The assets of an event, by the event's part of their internal names, e.g. "SpiritBlossom".
+pub fn of_event<'a>(assets: &'a [MissionAsset], event: &str) -> Vec<&'a MissionAsset> {
+    let prefix = fold(&format!("event_{event}"));
+    assets
+        .iter()
+        .filter(|asset| fold(&asset.internal_name).starts_with(&prefix))
+        .collect()
+}
+
+impl CDragon {
+    /// Loads the cached mission assets, fetching them when they aren't cached yet.
+    pub async fn mission_assets(&self) -> Result<Vec<MissionAsset>> {
+        if let Ok(assets) = self.load_obj(CacheFile::MissionAssets) {
+            return Ok(assets);
+        }
+        let assets = self.fetch_mission_assets().await?;
+        self.cache_obj(&assets, CacheFile::MissionAssets)?;
+        Ok(assets)
+    }
+
+    pub async fn fetch_mission_assets(&self) -> Result<Vec<MissionAsset>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/mission-assets.json"))
+            .await
+            .with_context(|| "couldn't fetch the mission assets")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    /// Every image in the event hub plugin, see [`PluginAsset`].
+    pub async fn event_hub_assets(&self) -> Result<Vec<PluginAsset>> {
+        self.plugin_images(EVENT_HUB_DIR, EVENT_HUB_DEPTH).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mission_icons() -> Result<()> {
+        let assets: Vec<MissionAsset> =
+            serde_json::from_str(&std::fs::read_to_string("testing/mission-assets.json")?)?;
+        let token = named(&assets, "event_spiritblossom_token").unwrap();
+        assert_eq!(token.path, "assets/missions/events/spiritblossom/token.png");
+        assert!(named(&assets, "Event_SpiritBlossom").is_none());
+        let event: Vec<u64> = of_event(&assets, "Spirit Blossom")
+            .iter()
+            .map(|asset| asset.id)
+            .collect();
+        assert_eq!(event, [2, 3]);
+        Ok(())
+    }
+}
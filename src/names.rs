@@ -0,0 +1,232 @@
+//! Champion names in the client's other locales, so a search for "아리" finds Ahri.
+//!
+//! A locale's names are fetched with `blitzadex update --locale ko_kr`, kept up to date by later
+//! updates, and every cached locale is searched after that.
+//!
+//! Names are compared [folded](fold), so "kaisa" finds Kai'Sa and "nunu and willump" finds
+//! Nunu & Willump.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+};
+
+use color_eyre::{eyre::Context, Result};
+use serde_json::Value;
+
+pub use blitzadex_types::fold;
+
+use crate::{
+    cdragon::{parse_json, CDragon},
+    http,
+    strings::locale_dir,
+};
+
+const GAME_DATA_URL: &str =
+    "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global";
+/// Under the cache directory, one file per locale
+const NAMES: &str = "names";
+
+/// Champion ids to their names in one locale.
+pub type Names = BTreeMap<u64, String>;
+
+/// The names of every cached locale, [folded](fold).
+#[derive(Debug, Default)]
+pub struct LocalizedNames(HashMap<u64, Vec<String>>);
+
+impl LocalizedNames {
+    pub fn from_tables(tables: impl IntoIterator<Item = Names>) -> Self {
+        let mut names: HashMap<u64, Vec<String>> = HashMap::new();
+        for (id, name) in tables.into_iter().flatten() {
+            let entry = names.entry(id).or_default();
+            let name = fold(&name);
+            if !entry.contains(&name) {
+                entry.push(name);
+            }
+        }
+        Self(names)
+    }
+
+    /// The champion called `name` in one of the locales.
+    pub fn id_of(&self, name: &str) -> Option<u64> {
+        let name = fold(name);
+        self.0
+            .iter()
+            .find(|(_, names)| names.contains(&name))
+            .map(|(id, _)| *id)
+    }
+
+    /// Whether one of the champion's names contains `text`, already folded.
+    pub fn contains(&self, id: u64, text: &str) -> bool {
+        self.0
+            .get(&id)
+            .is_some_and(|names| names.iter().any(|name| name.contains(text)))
+    }
+}
+
+/// Reads the names out of a champion-summary.json, without the "None" placeholder.
+fn names_of(summaries: Vec<Value>) -> Names {
+    summaries
+        .iter()
+        .filter_map(|summary| {
+            let id = summary.get("id")?.as_u64()?;
+            let name = summary.get("name")?.as_str()?;
+            Some((id, name.to_string()))
+        })
+        .collect()
+}
+
+impl CDragon {
+    /// The locales with cached champion names, e.g. "ko_kr".
+    pub fn cached_locales(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(self.cache_dir.join(NAMES)) else {
+            return vec![];
+        };
+        let mut locales: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        locales.sort();
+        locales
+    }
+
+    /// The champion names of every cached locale.
+    pub fn localized_names(&self) -> Result<LocalizedNames> {
+        let tables = self
+            .cached_locales()
+            .iter()
+            .map(|locale| {
+                let bytes = fs::read(self.cache_dir.join(NAMES).join(format!("{locale}.json")))?;
+                Ok(parse_json(&bytes)?)
+            })
+            .collect::<Result<Vec<Names>>>()?;
+        Ok(LocalizedNames::from_tables(tables))
+    }
+
+    /// Fetches and caches the champion names of `locales` and of the locales already cached,
+    /// returning the locales that were.
+    pub async fn update_names(&self, locales: &[String]) -> Result<Vec<String>> {
+        let mut dirs = self.cached_locales();
+        for locale in locales {
+            let dir = locale_dir(locale)?;
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        fs::create_dir_all(self.cache_dir.join(NAMES))?;
+        for dir in &dirs {
+            let names = self.fetch_names(dir).await?;
+            fs::write(
+                self.cache_dir.join(NAMES).join(format!("{dir}.json")),
+                serde_json::to_string(&names)?,
+            )?;
+        }
+        Ok(dirs)
+    }
+
+    /// Fetches the champion names in the locale directory `dir`, see [`locale_dir`].
+    async fn fetch_names(&self, dir: &str) -> Result<Names> {
+        let url = format!("{GAME_DATA_URL}/{dir}/v1/champion-summary.json");
+        let bytes = http::get(&self.http_client, &url)
+            .await
+            .with_context(|| format!("couldn't fetch the {dir} champion names"))?;
+        Ok(names_of(parse_json(&bytes)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn localized_lookup() -> Result<()> {
+        let names = names_of(serde_json::from_str(
+            r#"[{"id": -1, "name": "없음"}, {"id": 1, "name": "애니"}, {"id": 103, "name": "아리"}]"#,
+        )?);
+        assert_eq!(names.len(), 2);
+
+        let dir = tempfile::tempdir()?;
+        let mut cdrag = CDragon::in_dir(dir.path());
+        assert!(cdrag.cached_locales().is_empty());
+        let cached = dir.path().join("cache").join(NAMES);
+        fs::create_dir_all(&cached)?;
+        fs::write(cached.join("ko_kr.json"), serde_json::to_string(&names)?)?;
+        fs::write(
+            cached.join("fr_fr.json"),
+            serde_json::to_string(&Names::from([(1, "Annie".to_string())]))?,
+        )?;
+        assert_eq!(cdrag.cached_locales(), ["fr_fr", "ko_kr"]);
+
+        let localized = cdrag.localized_names()?;
+        assert_eq!(localized.id_of("아리"), Some(103));
+        assert_eq!(localized.id_of("ANNIE"), Some(1));
+        assert!(localized.contains(1, "애"));
+        assert!(!localized.contains(103, "애"));
+
+        let annie: crate::cdragon::Champion =
+            serde_json::from_str(&fs::read_to_string("testing/annie.json")?)?;
+        cdrag.champions.insert(annie.id, annie);
+        assert_eq!(cdrag.champion_by_name("애니").map(|c| c.id), Some(1));
+        assert!(cdrag.champion_by_name("아리").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn folded_names() -> Result<()> {
+        assert_eq!(fold("Kai'Sa"), "kaisa");
+        assert_eq!(fold("Kaï Sa"), "kaisa");
+        assert_eq!(fold("Nunu & Willump"), fold("nunu and willump"));
+        assert_eq!(fold("Dr. Mundo"), "drmundo");
+        assert_eq!(fold("Renée Çelik"), "reneecelik");
+        assert_eq!(fold("Łódź"), "lodz");
+        assert_eq!(fold("아리"), "아리");
+
+        // Every champion, typed the ways players do
+        let file: Vec<Value> =
+            serde_json::from_str(&fs::read_to_string("testing/champion-summary.json")?)?;
+        // Without the "None" placeholder
+        let summaries: Vec<crate::cdragon::ChampionSummary> = file
+            .into_iter()
+            .skip(1)
+            .map(serde_json::from_value)
+            .collect::<Result<_, _>>()?;
+        for summary in &summaries {
+            let name = &summary.name;
+            for typed in [
+                name.to_lowercase(),
+                name.to_uppercase(),
+                name.replace('\'', ""),
+                name.replace(['\'', ' ', '.'], ""),
+                name.replace('&', "and"),
+                summary.alias.to_lowercase(),
+            ] {
+                assert!(summary.is_named(&typed), "{typed} should find {name}");
+            }
+        }
+        let named = |typed: &str| {
+            summaries
+                .iter()
+                .find(|s| s.is_named(typed))
+                .map(|s| &s.alias)
+        };
+        for (typed, alias) in [
+            ("kaisa", "Kaisa"),
+            ("belveth", "Belveth"),
+            ("Bel'Veth", "Belveth"),
+            ("nunu and willump", "Nunu"),
+            ("ksante", "KSante"),
+            ("drmundo", "DrMundo"),
+            ("jarvan iv", "JarvanIV"),
+            ("monkeyking", "MonkeyKing"),
+            ("leblanc", "Leblanc"),
+            ("renata", "Renata"),
+            ("kogmaw", "KogMaw"),
+        ] {
+            assert_eq!(named(typed).map(String::as_str), Some(alias), "{typed}");
+        }
+        assert_eq!(named("kai"), None);
+        Ok(())
+    }
+}
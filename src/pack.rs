@@ -0,0 +1,185 @@
+//! Data packs bundle cached catalogs into a single gzipped tarball so a dataset can be built once
+//! and shared, rather than everyone hitting the CDragon CDN.
+//!
+//! A pack is a `manifest.json` followed by the catalog files, named exactly like they are in the
+//! cache directory.
+
+use std::{
+    fs::{self, File},
+    io::{Read, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+use crate::{
+    cdragon::{write_atomically, CDragon, CacheFile, PluginName},
+    http,
+    lock::CacheLock,
+    provenance::Provenance,
+};
+
+/// Bumped whenever the layout of a pack changes in a way older versions can't read.
+pub const PACK_FORMAT: u32 = 1;
+const MANIFEST: &str = "manifest.json";
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct Manifest {
+    pub format: u32,
+    pub created: DateTime<Utc>,
+    /// When the game data plugin the catalogs came from was last updated upstream
+    pub game_data_updated: Option<DateTime<Utc>>,
+    /// The file names of the packed catalogs
+    pub catalogs: Vec<String>,
+    /// Missing from packs built before it was recorded
+    #[serde(default)]
+    pub provenance: Option<Provenance>,
+}
+
+/// Packs the given cached catalogs, or every cached catalog when `catalogs` is empty, into `out`.
+pub fn build(cdrag: &CDragon, catalogs: &[CacheFile], out: &Path) -> Result<Manifest> {
+    let catalogs: Vec<CacheFile> = if catalogs.is_empty() {
+        CacheFile::iter()
+            .filter(|catalog| {
+                cdrag
+                    .cache_dir
+                    .join(catalog.to_string())
+                    .try_exists()
+                    .unwrap_or(false)
+            })
+            .collect()
+    } else {
+        catalogs.to_vec()
+    };
+    let manifest = Manifest {
+        format: PACK_FORMAT,
+        created: Utc::now(),
+        game_data_updated: cdrag
+            .plugins
+            .iter()
+            .find(|plugin| plugin.name == PluginName::RcpBeLolGameData)
+            .map(|plugin| plugin.mtime),
+        catalogs: catalogs.iter().map(ToString::to_string).collect(),
+        provenance: Some(cdrag.provenance()),
+    };
+
+    let file = File::create(out).with_context(|| format!("couldn't create {}", out.display()))?;
+    let mut tar = tar::Builder::new(GzEncoder::new(file, Compression::best()));
+    let ser = serde_json::to_vec_pretty(&manifest)?;
+    append(&mut tar, MANIFEST, &ser)?;
+    for catalog in &manifest.catalogs {
+        let bytes = fs::read(cdrag.cache_dir.join(catalog))
+            .with_context(|| format!("{catalog} isn't cached, run an update first"))?;
+        append(&mut tar, catalog, &bytes)?;
+    }
+    tar.into_inner()?.finish()?.flush()?;
+    Ok(manifest)
+}
+
+fn append(tar: &mut tar::Builder<impl Write>, name: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp() as u64);
+    tar.append_data(&mut header, name, bytes)?;
+    Ok(())
+}
+
+/// Unpacks a pack into the cache directory, replacing the catalogs it contains.
+///
+/// Only files named after a known [`CacheFile`] are extracted, anything else in the archive is
+/// ignored. Nothing is replaced unless every catalog in the pack is valid JSON.
+pub async fn unpack(cdrag: &CDragon, pack: impl Read) -> Result<Manifest> {
+    let _lock = CacheLock::acquire(&cdrag.cache_dir, cdrag.wait_for_lock).await?;
+    let mut archive = tar::Archive::new(GzDecoder::new(pack));
+    let mut manifest: Option<Manifest> = None;
+    let mut catalogs = vec![];
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        if name == MANIFEST {
+            let read: Manifest = serde_json::from_reader(&mut entry)?;
+            if read.format > PACK_FORMAT {
+                return Err(eyre!(
+                    "this pack uses format {}, but only up to {PACK_FORMAT} is supported",
+                    read.format
+                ));
+            }
+            manifest = Some(read);
+            continue;
+        }
+        if manifest.is_none() {
+            return Err(eyre!("the pack doesn't start with a manifest"));
+        }
+        if CacheFile::iter().any(|catalog| catalog.to_string() == name) {
+            let mut contents = String::new();
+            entry.read_to_string(&mut contents)?;
+            serde_json::from_str::<Value>(&contents)
+                .with_context(|| format!("{name} in the pack isn't valid JSON"))?;
+            catalogs.push((name, contents));
+        }
+    }
+    for (name, contents) in catalogs {
+        write_atomically(&cdrag.cache_dir.join(name), &contents)?;
+    }
+    manifest.ok_or_else(|| eyre!("the pack doesn't contain a manifest"))
+}
+
+/// Downloads a pack published at `url` and unpacks it into the cache directory.
+pub async fn fetch(cdrag: &CDragon, url: &str) -> Result<Manifest> {
+    let bytes = http::get(&cdrag.http_client, url)
+        .await
+        .with_context(|| format!("couldn't download the pack at {url}"))?;
+    unpack(cdrag, bytes.as_slice()).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn round_trip() -> Result<()> {
+        let from = tempfile::tempdir()?;
+        let to = tempfile::tempdir()?;
+        let source = CDragon::in_dir(from.path());
+        fs::create_dir_all(&source.cache_dir)?;
+        fs::copy(
+            "testing/plugins.json",
+            source.cache_dir.join("plugins.json"),
+        )?;
+        let pack_path = from.path().join("pack.tar.gz");
+        let built = build(&source, &[CacheFile::Plugins], &pack_path)?;
+
+        let dest = CDragon::in_dir(to.path());
+        let unpacked = unpack(&dest, File::open(pack_path)?).await?;
+        assert_eq!(built, unpacked);
+        assert_eq!(
+            unpacked.provenance.map(|provenance| provenance.source),
+            Some("CommunityDragon".to_string())
+        );
+        assert_eq!(
+            fs::read(source.cache_dir.join("plugins.json"))?,
+            fs::read(dest.cache_dir.join("plugins.json"))?
+        );
+
+        let broken = from.path().join("broken.tar.gz");
+        let mut tar =
+            tar::Builder::new(GzEncoder::new(File::create(&broken)?, Compression::fast()));
+        append(&mut tar, MANIFEST, &serde_json::to_vec(&built)?)?;
+        append(&mut tar, "plugins.json", &fs::read("testing/plugins.json")?)?;
+        append(&mut tar, "items.json", b"[{\"id\": 1001,")?;
+        tar.into_inner()?.finish()?;
+        fs::remove_file(dest.cache_dir.join("plugins.json"))?;
+        assert!(unpack(&dest, File::open(broken)?).await.is_err());
+        assert!(!dest.cache_dir.join("plugins.json").exists());
+        Ok(())
+    }
+}
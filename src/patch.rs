@@ -0,0 +1,95 @@
+//! Which patch the game data is for, from CommunityDragon's content-metadata.json, e.g.
+//! "15.1.6463155+branch.releases-15-1.code.public.content.release" for patch 15.1.
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{parse_json, CDragon, CacheFile, PluginName},
+    http,
+};
+
+const CONTENT_METADATA_URL: &str = "https://raw.communitydragon.org/latest/content-metadata.json";
+
+/// content-metadata.json
+#[derive(Debug, Deserialize)]
+struct ContentMetadata {
+    version: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PatchVersion {
+    /// The full version, build and branch included
+    pub version: String,
+    /// When the game data of the patch was published
+    #[serde(default)]
+    pub released: Option<DateTime<Utc>>,
+}
+
+/// The numbers of a patch like "V1.0.0.100" or "13.1", so they compare numerically.
+pub(crate) fn patch_numbers(patch: &str) -> Vec<u64> {
+    patch
+        .trim_start_matches(['V', 'v'])
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+impl PatchVersion {
+    /// The patch as players know it, e.g. "15.1".
+    pub fn patch(&self) -> &str {
+        let numbers = self.version.split('+').next().unwrap_or_default();
+        let mut dots = numbers.match_indices('.').map(|(i, _)| i);
+        match (dots.next(), dots.next()) {
+            (Some(_), Some(second)) => &numbers[..second],
+            _ => numbers,
+        }
+    }
+}
+
+impl CDragon {
+    /// The patch the cached data is for, fetched when it isn't known yet. [`CDragon::update`]
+    /// records it along with the data.
+    pub async fn patch_version(&self) -> Result<PatchVersion> {
+        if let Ok(patch) = self.load_obj(CacheFile::ContentMetadata) {
+            return Ok(patch);
+        }
+        let patch = self.fetch_patch_version().await?;
+        self.cache_obj(&patch, CacheFile::ContentMetadata)?;
+        Ok(patch)
+    }
+
+    pub async fn fetch_patch_version(&self) -> Result<PatchVersion> {
+        let bytes = http::get(&self.http_client, CONTENT_METADATA_URL)
+            .await
+            .with_context(|| "couldn't fetch the content metadata")?;
+        let metadata: ContentMetadata = parse_json(&bytes)?;
+        Ok(PatchVersion {
+            version: metadata.version,
+            released: self
+                .cached_plugin_updated_date(&PluginName::RcpBeLolGameData)
+                .await,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn patch_numbers() {
+        let version = |version: &str| PatchVersion {
+            version: version.to_string(),
+            released: None,
+        };
+        assert_eq!(
+            version("15.1.6463155+branch.releases-15-1.code.public.content.release").patch(),
+            "15.1"
+        );
+        assert_eq!(version("14.23.636.3924").patch(), "14.23");
+        assert_eq!(version("14.23").patch(), "14.23");
+        assert_eq!(version("").patch(), "");
+    }
+}
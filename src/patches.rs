@@ -0,0 +1,197 @@
+//! The patches CommunityDragon keeps the game data of. Next to "latest", every patch has a
+//! versioned branch named after it, e.g. https://raw.communitydragon.org/14.10/, which is what
+//! older data is fetched from.
+//!
+//! Data of older patches is cached under `patches/<patch>` in the cache directory, apart from the
+//! latest data.
+
+use std::{fmt::Write, fs, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    cdragon::{parse_json, write_atomically, CDragon, CacheFile},
+    listing::DirEntry,
+    patch::{patch_numbers, PatchVersion},
+};
+
+const PATCHES: &str = "patches";
+
+/// A patch's branch of the game data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Branch {
+    /// e.g. "14.10"
+    pub patch: String,
+    /// When the branch was last updated
+    pub updated: DateTime<Utc>,
+    /// Whether data of the patch is cached under `patches/`
+    pub cached: bool,
+    /// Whether it's the patch of the latest data in the cache
+    pub current: bool,
+}
+
+/// Whether a directory at the root of CDragon is a patch's branch, like "14.10" rather than
+/// "latest" or "pbe".
+pub fn is_patch(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() >= 2
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The branches in a listing of CDragon's root, oldest patch first, marked with what's cached.
+pub fn branches(root: Vec<DirEntry>, cached: &[String], current: Option<&str>) -> Vec<Branch> {
+    let mut branches: Vec<Branch> = root
+        .into_iter()
+        .filter(|entry| entry.is_dir() && is_patch(&entry.name))
+        .map(|entry| Branch {
+            cached: cached.contains(&entry.name),
+            current: current == Some(entry.name.as_str()),
+            patch: entry.name,
+            updated: entry.mtime,
+        })
+        .collect();
+    branches.sort_by_cached_key(|branch| patch_numbers(&branch.patch));
+    branches
+}
+
+/// Renders the branches as a calendar, one line per patch.
+pub fn render(branches: &[Branch]) -> String {
+    let mut out = String::new();
+    for branch in branches {
+        let mut marks = vec![];
+        if branch.current {
+            marks.push("current");
+        }
+        if branch.cached {
+            marks.push("cached");
+        }
+        let line = format!(
+            "{:<8}{}  {}",
+            branch.patch,
+            branch.updated.format("%Y-%m-%d"),
+            marks.join(", ")
+        );
+        let _ = writeln!(out, "{}", line.trim_end());
+    }
+    let cached = branches
+        .iter()
+        .filter(|branch| branch.cached || branch.current)
+        .count();
+    let _ = writeln!(out, "{} patches, {cached} cached", branches.len());
+    out
+}
+
+impl CDragon {
+    /// Where the data of an older `patch` is cached.
+    pub fn patch_dir(&self, patch: &str) -> PathBuf {
+        self.cache_dir.join(PATCHES).join(patch)
+    }
+
+    /// Saves `catalog` of an older `patch`. Unlike the latest data it's written compact, as
+    /// there's a copy for every patch.
+    pub(crate) fn cache_patch_obj(
+        &self,
+        patch: &str,
+        obj: &impl Serialize,
+        catalog: CacheFile,
+    ) -> Result<()> {
+        let dir = self.patch_dir(patch);
+        fs::create_dir_all(&dir)?;
+        write_atomically(&dir.join(catalog.to_string()), &serde_json::to_string(obj)?)
+    }
+
+    /// Loads `catalog` of an older `patch`, see [`CDragon::cache_patch_obj`].
+    pub fn load_patch_obj<T: DeserializeOwned>(
+        &self,
+        patch: &str,
+        catalog: CacheFile,
+    ) -> Result<T> {
+        let bytes = fs::read(self.patch_dir(patch).join(catalog.to_string()))
+            .with_context(|| format!("{catalog} of {patch} isn't cached"))?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    /// The patches with catalogs cached under `patches/`, oldest first.
+    pub fn cached_patches(&self) -> Vec<String> {
+        let Ok(dirs) = fs::read_dir(self.cache_dir.join(PATCHES)) else {
+            return vec![];
+        };
+        let mut patches: Vec<String> = dirs
+            .filter_map(|entry| entry.ok())
+            // A backfill that stopped halfway through the champions leaves only some of them
+            .filter(|entry| {
+                fs::read_dir(entry.path()).is_ok_and(|mut files| {
+                    files.any(|file| {
+                        file.is_ok_and(|file| {
+                            file.path().extension().is_some_and(|ext| ext == "json")
+                        })
+                    })
+                })
+            })
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| is_patch(name))
+            .collect();
+        patches.sort_by_cached_key(|patch| patch_numbers(patch));
+        patches
+    }
+
+    /// Lists the patches CDragon has a branch for.
+    pub async fn patches(&self) -> Result<Vec<Branch>> {
+        let root: Vec<DirEntry> = self
+            .list_dir("")
+            .await
+            .with_context(|| "couldn't list the versioned branches")?;
+        let current = self
+            .load_obj::<PatchVersion>(CacheFile::ContentMetadata)
+            .ok();
+        Ok(branches(
+            root,
+            &self.cached_patches(),
+            current.as_ref().map(PatchVersion::patch),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lists_branches() -> Result<()> {
+        let root: Vec<DirEntry> =
+            serde_json::from_str(&fs::read_to_string("testing/branches.json")?)?;
+        assert!(is_patch("14.10"));
+        assert!(!is_patch("latest"));
+        assert!(!is_patch("14."));
+
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        assert!(cdrag.cached_patches().is_empty());
+        for patch in ["14.9", "13.10"] {
+            cdrag.cache_patch_obj(patch, &Vec::<u64>::new(), CacheFile::Items)?;
+        }
+        assert!(cdrag
+            .load_patch_obj::<Vec<u64>>("14.9", CacheFile::Items)?
+            .is_empty());
+        assert!(cdrag
+            .load_patch_obj::<Vec<u64>>("14.10", CacheFile::Items)
+            .is_err());
+        // Nothing but some of the champions was backfilled into it
+        fs::create_dir_all(cdrag.patch_dir("13.1").join("champions"))?;
+        assert_eq!(cdrag.cached_patches(), ["13.10", "14.9"]);
+
+        let branches = branches(root, &cdrag.cached_patches(), Some("15.1"));
+        let patches: Vec<&str> = branches.iter().map(|b| b.patch.as_str()).collect();
+        assert_eq!(patches, ["13.1", "13.2", "13.10", "14.9", "14.10", "15.1"]);
+        let report = render(&branches);
+        assert!(report.starts_with("13.1    2023-01-10\n"));
+        assert!(report.contains("14.9    2024-04-30  cached\n"));
+        assert!(report.contains("15.1    2025-01-08  current\n"));
+        assert!(report.ends_with("6 patches, 3 cached\n"));
+        Ok(())
+    }
+}
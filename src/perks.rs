@@ -0,0 +1,128 @@
+//! Runes, which the game data calls perks, and the paths (styles) they're picked from.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Perk {
+    pub id: u64,
+    pub name: String,
+    /// With placeholders like `@DamageForAttack@` for the numbers
+    pub tooltip: String,
+    #[serde(default)]
+    pub short_desc: String,
+    #[serde(default)]
+    pub long_desc: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub icon_path: String,
+}
+
+/// A row of a style, the player picks one of its perks.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkSlot {
+    /// e.g. "kKeyStone" or "kStatMod"
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// e.g. "Heroism", empty for the keystones
+    pub slot_label: String,
+    /// The ids of the slot's [`Perk`]s
+    pub perks: Vec<u64>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkStyle {
+    pub id: u64,
+    /// e.g. "Precision"
+    pub name: String,
+    pub tooltip: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub icon_path: String,
+    /// The styles this one can be paired with as the secondary path
+    pub allowed_sub_styles: Vec<u64>,
+    pub slots: Vec<PerkSlot>,
+}
+
+impl PerkStyle {
+    /// Whether `perk` can be picked in this style.
+    pub fn offers(&self, perk: u64) -> bool {
+        self.slots.iter().any(|slot| slot.perks.contains(&perk))
+    }
+}
+
+/// perkstyles.json wraps the styles with a schema version.
+#[derive(Deserialize)]
+struct PerkStylesFile {
+    styles: Vec<PerkStyle>,
+}
+
+impl CDragon {
+    /// Loads the cached perks, fetching them when they aren't cached yet.
+    pub async fn perks(&self) -> Result<Vec<Perk>> {
+        if let Ok(perks) = self.load_obj(CacheFile::Perks) {
+            return Ok(perks);
+        }
+        let perks = self.fetch_perks().await?;
+        self.cache_obj(&perks, CacheFile::Perks)?;
+        Ok(perks)
+    }
+
+    /// Loads the cached perk styles, fetching them when they aren't cached yet.
+    pub async fn perk_styles(&self) -> Result<Vec<PerkStyle>> {
+        if let Ok(styles) = self.load_obj(CacheFile::PerkStyles) {
+            return Ok(styles);
+        }
+        let styles = self.fetch_perk_styles().await?;
+        self.cache_obj(&styles, CacheFile::PerkStyles)?;
+        Ok(styles)
+    }
+
+    pub async fn fetch_perks(&self) -> Result<Vec<Perk>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/perks.json"))
+            .await
+            .with_context(|| "couldn't fetch the perks")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    pub async fn fetch_perk_styles(&self) -> Result<Vec<PerkStyle>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/perkstyles.json"))
+            .await
+            .with_context(|| "couldn't fetch the perk styles")?;
+        let file: PerkStylesFile = parse_json(&bytes)?;
+        Ok(file.styles)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn perks_and_styles() -> Result<()> {
+        let perks: Vec<Perk> =
+            serde_json::from_str(&std::fs::read_to_string("testing/perks.json")?)?;
+        assert_eq!(perks[0].name, "Press the Attack");
+        assert_eq!(
+            perks[0].icon_path,
+            "v1/perk-images/styles/precision/presstheattack/presstheattack.png"
+        );
+
+        let file: PerkStylesFile =
+            serde_json::from_str(&std::fs::read_to_string("testing/perkstyles.json")?)?;
+        let precision = &file.styles[0];
+        assert_eq!(precision.slots[0].kind, "kKeyStone");
+        assert_eq!(precision.slots[1].slot_label, "Heroism");
+        assert!(precision.offers(8005) && precision.offers(5008));
+        assert!(!precision.offers(8112));
+        Ok(())
+    }
+}
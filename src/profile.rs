@@ -0,0 +1,100 @@
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// How far the user has progressed with a champion.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Completion {
+    Played,
+    Mastered,
+}
+
+/// State owned by the user rather than by CDragon.
+///
+/// Unlike the cache it is never refetched, so it lives in the data directory. See
+/// [`crate::cdragon::CDragon::profile`].
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Profile {
+    #[serde(default)]
+    completion: HashMap<u64, Completion>,
+    #[serde(default)]
+    tags: HashMap<u64, BTreeSet<String>>,
+}
+
+impl Profile {
+    pub fn completion(&self, champion_id: u64) -> Option<Completion> {
+        self.completion.get(&champion_id).copied()
+    }
+
+    pub fn mark(&mut self, champion_id: u64, completion: Completion) {
+        self.completion.insert(champion_id, completion);
+    }
+
+    pub fn unmark(&mut self, champion_id: u64) -> Option<Completion> {
+        self.completion.remove(&champion_id)
+    }
+
+    /// The user's tags on a champion, in alphabetical order.
+    pub fn tags(&self, champion_id: u64) -> impl Iterator<Item = &str> {
+        self.tags
+            .get(&champion_id)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+    }
+
+    pub fn has_tag(&self, champion_id: u64, tag: &str) -> bool {
+        self.tags
+            .get(&champion_id)
+            .is_some_and(|tags| tags.contains(tag))
+    }
+
+    /// Every tag in use along with the champions carrying it.
+    pub fn all_tags(&self) -> HashMap<&str, Vec<u64>> {
+        let mut all: HashMap<&str, Vec<u64>> = HashMap::new();
+        for (id, tags) in &self.tags {
+            for tag in tags {
+                all.entry(tag.as_str()).or_default().push(*id);
+            }
+        }
+        all
+    }
+
+    /// Returns false when the champion already had the tag.
+    pub fn tag(&mut self, champion_id: u64, tag: impl Into<String>) -> bool {
+        self.tags.entry(champion_id).or_default().insert(tag.into())
+    }
+
+    /// Returns false when the champion didn't have the tag.
+    pub fn untag(&mut self, champion_id: u64, tag: &str) -> bool {
+        let Some(tags) = self.tags.get_mut(&champion_id) else {
+            return false;
+        };
+        let removed = tags.remove(tag);
+        if tags.is_empty() {
+            self.tags.remove(&champion_id);
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn untagging_the_last_tag_forgets_the_champion() {
+        let mut profile = Profile::default();
+        assert!(profile.tag(84, "pocket-pick"));
+        assert!(!profile.tag(84, "pocket-pick"));
+        assert!(profile.has_tag(84, "pocket-pick"));
+        assert!(profile.untag(84, "pocket-pick"));
+        assert!(!profile.untag(84, "pocket-pick"));
+        assert!(profile.all_tags().is_empty());
+        assert_eq!(profile.tags(84).count(), 0);
+    }
+}
@@ -0,0 +1,226 @@
+//! Progress of [`CDragon::update`](crate::cdragon::CDragon::update), reported as tracing events
+//! so the library doesn't draw anything itself. The command line draws them as a progress line
+//! with [`ProgressBar`].
+//!
+//! An update emits a "started" event, then an event for each catalog cached, each champion
+//! fetched and each response downloaded, and a "finished" event once it's done.
+//!
+//! Anything else worth telling the user about while they wait, like a request being retried, is a
+//! "notice" event with a message. Notices are printed on a line of their own, whether or not an
+//! update is drawing its progress.
+
+#[cfg(feature = "cli")]
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+#[cfg(feature = "cli")]
+use tracing::{Event, Subscriber};
+#[cfg(feature = "cli")]
+use tracing_subscriber::{layer::Context, Layer};
+
+use crate::{cdragon::CacheFile, update::human_bytes};
+
+const BAR_WIDTH: usize = 24;
+
+pub(crate) fn started() {
+    tracing::info!(target: "blitzadex::progress", started = true);
+}
+
+pub(crate) fn finished() {
+    tracing::info!(target: "blitzadex::progress", finished = true);
+}
+
+pub(crate) fn cached(catalog: CacheFile) {
+    tracing::info!(target: "blitzadex::progress", cached = %catalog);
+}
+
+/// `done` of `total` champions fetched
+pub(crate) fn champions(done: usize, total: usize) {
+    tracing::info!(target: "blitzadex::progress", done, total);
+}
+
+pub(crate) fn downloaded(bytes: usize) {
+    tracing::info!(target: "blitzadex::progress", bytes);
+}
+
+pub(crate) fn notice(message: impl std::fmt::Display) {
+    tracing::info!(target: "blitzadex::progress", notice = %message);
+}
+
+/// Where an update is at.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub bytes: u64,
+    /// Champions fetched and how many there are, once they're being fetched
+    pub champions: Option<(u64, u64)>,
+    /// The last catalog cached
+    pub cached: Option<String>,
+}
+
+impl Progress {
+    /// A single line, e.g. `[############------------] 85/170 champions, 12.3 MB downloaded`.
+    pub fn render(&self) -> String {
+        let downloaded = format!("{} downloaded", human_bytes(self.bytes));
+        match (self.champions, &self.cached) {
+            (Some((done, total)), _) if done < total => {
+                let filled = (done * BAR_WIDTH as u64 / total.max(1)) as usize;
+                format!(
+                    "[{}{}] {done}/{total} champions, {downloaded}",
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH - filled)
+                )
+            }
+            (_, Some(cached)) => format!("{cached} cached, {downloaded}"),
+            _ => format!("fetching plugins, {downloaded}"),
+        }
+    }
+}
+
+impl Visit for Progress {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "bytes" => self.bytes += value,
+            "done" => self.champions = Some((value, self.champions.unwrap_or_default().1)),
+            "total" => self.champions = Some((self.champions.unwrap_or_default().0, value)),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "cached" {
+            self.cached = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+#[cfg(feature = "cli")]
+struct BarState {
+    progress: Progress,
+    active: bool,
+    drawn: bool,
+}
+
+/// Draws an update's progress on stderr, overwriting a single line, and prints notices above it.
+#[derive(Debug, Clone, Default)]
+#[cfg(feature = "cli")]
+pub struct ProgressBar {
+    state: Arc<Mutex<BarState>>,
+    /// Whether to draw the progress line, or only print the notices
+    bar: bool,
+}
+
+#[cfg(feature = "cli")]
+impl ProgressBar {
+    pub fn new(bar: bool) -> Self {
+        ProgressBar {
+            bar,
+            ..Default::default()
+        }
+    }
+
+    /// Clears the line, in case the update stopped before finishing.
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if state.drawn {
+                eprint!("\r\x1b[2K");
+                state.drawn = false;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "cli")]
+impl<S: Subscriber> Layer<S> for ProgressBar {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "blitzadex::progress" {
+            return;
+        }
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let mut flags = Flags::default();
+        event.record(&mut flags);
+        if let Some(notice) = flags.notice {
+            match state.drawn {
+                true => eprint!("\r\x1b[2K{notice}\n{}", state.progress.render()),
+                false => eprintln!("{notice}"),
+            }
+            return;
+        }
+        if !self.bar {
+            return;
+        }
+        if flags.started {
+            *state = BarState {
+                active: true,
+                ..Default::default()
+            };
+        }
+        if !state.active {
+            return;
+        }
+        event.record(&mut state.progress);
+        if flags.finished {
+            state.active = false;
+            if state.drawn {
+                eprint!("\r\x1b[2K");
+                state.drawn = false;
+            }
+            return;
+        }
+        eprint!("\r\x1b[2K{}", state.progress.render());
+        state.drawn = true;
+    }
+}
+
+/// The "started", "finished" and "notice" fields of an event.
+#[derive(Default)]
+#[cfg(feature = "cli")]
+struct Flags {
+    started: bool,
+    finished: bool,
+    notice: Option<String>,
+}
+
+#[cfg(feature = "cli")]
+impl Visit for Flags {
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        match field.name() {
+            "started" => self.started = value,
+            "finished" => self.finished = value,
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "notice" {
+            self.notice = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn renders() {
+        let mut progress = Progress::default();
+        assert_eq!(progress.render(), "fetching plugins, 0 B downloaded");
+        progress.cached = Some("plugins.json".to_string());
+        progress.bytes = 12_300_000;
+        assert_eq!(progress.render(), "plugins.json cached, 12.3 MB downloaded");
+        progress.champions = Some((85, 170));
+        assert_eq!(
+            progress.render(),
+            format!(
+                "[{}{}] 85/170 champions, 12.3 MB downloaded",
+                "#".repeat(12),
+                "-".repeat(12)
+            )
+        );
+        progress.champions = Some((170, 170));
+        assert!(progress.render().starts_with("plugins.json cached"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Reading only some of every champion's fields, for tools that need a column or two across the
+//! whole roster.
+//!
+//! Deserializing [`Champion`](crate::cdragon::Champion)s allocates every skin, chroma and spell,
+//! which is most of the time and memory it takes to load the catalog. A projection walks the
+//! cached catalog with its own visitors instead and skips over anything that wasn't asked for
+//! without building it.
+
+use std::{collections::HashMap, fmt, fs};
+
+use color_eyre::{eyre::Context, Result};
+use serde::{
+    de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::cdragon::{CDragon, CacheFile, PlaystyleInfo, TactialInfo};
+
+/// A field of a champion that can be projected. The id is always read.
+#[derive(Debug, Display, EnumIter, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "camelCase")]
+pub enum Field {
+    Name,
+    Alias,
+    Title,
+    ShortBio,
+    TacticalInfo,
+    PlaystyleInfo,
+    SquarePortraitPath,
+    Roles,
+}
+
+/// The projected fields of a champion, `None` for the ones that weren't asked for.
+#[derive(Debug, Default, PartialEq)]
+pub struct ChampionFields {
+    pub id: u64,
+    pub name: Option<String>,
+    pub alias: Option<String>,
+    pub title: Option<String>,
+    pub short_bio: Option<String>,
+    pub tactical_info: Option<TactialInfo>,
+    pub playstyle_info: Option<PlaystyleInfo>,
+    pub square_portrait_path: Option<String>,
+    pub roles: Option<Vec<String>>,
+}
+
+/// A key of a champion object, matched without allocating it.
+enum Key {
+    Id,
+    Field(Field),
+    Other,
+}
+
+impl<'de> Deserialize<'de> for Key {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl Visitor<'_> for KeyVisitor {
+            type Value = Key;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a champion field")
+            }
+
+            fn visit_str<E>(self, key: &str) -> Result<Key, E> {
+                if key == "id" {
+                    return Ok(Key::Id);
+                }
+                Ok(Field::iter()
+                    .find(|field| field.to_string() == key)
+                    .map_or(Key::Other, Key::Field))
+            }
+        }
+
+        deserializer.deserialize_identifier(KeyVisitor)
+    }
+}
+
+/// Reads a single champion object, keeping only `fields`.
+struct ChampionSeed<'a>(&'a [Field]);
+
+impl<'de> DeserializeSeed<'de> for ChampionSeed<'_> {
+    type Value = ChampionFields;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ChampionSeed<'_> {
+    type Value = ChampionFields;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a champion")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut champion = ChampionFields::default();
+        while let Some(key) = map.next_key()? {
+            match key {
+                Key::Id => champion.id = map.next_value()?,
+                Key::Field(field) if self.0.contains(&field) => match field {
+                    Field::Name => champion.name = map.next_value()?,
+                    Field::Alias => champion.alias = map.next_value()?,
+                    Field::Title => champion.title = map.next_value()?,
+                    Field::ShortBio => champion.short_bio = map.next_value()?,
+                    Field::TacticalInfo => champion.tactical_info = map.next_value()?,
+                    Field::PlaystyleInfo => champion.playstyle_info = map.next_value()?,
+                    Field::SquarePortraitPath => {
+                        champion.square_portrait_path = map.next_value()?
+                    }
+                    Field::Roles => champion.roles = map.next_value()?,
+                },
+                Key::Field(_) | Key::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(champion)
+    }
+}
+
+/// Reads the cached champions, keyed by id, keeping only `fields`.
+struct RosterSeed<'a>(&'a [Field]);
+
+impl<'de> Visitor<'de> for RosterSeed<'_> {
+    type Value = HashMap<u64, ChampionFields>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("champions keyed by id")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut champions = HashMap::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(id) = map.next_key::<u64>()? {
+            champions.insert(id, map.next_value_seed(ChampionSeed(self.0))?);
+        }
+        Ok(champions)
+    }
+}
+
+/// Projects `fields` out of a champions catalog as it's cached.
+pub fn project(bytes: &[u8], fields: &[Field]) -> Result<HashMap<u64, ChampionFields>> {
+    let _span = tracing::info_span!("parse").entered();
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let champions = deserializer.deserialize_map(RosterSeed(fields))?;
+    deserializer.end()?;
+    Ok(champions)
+}
+
+impl CDragon {
+    /// Reads only `fields` of every cached champion, fetching the champions when they aren't
+    /// cached yet. See the [module docs](self).
+    pub async fn champions_with(&self, fields: &[Field]) -> Result<HashMap<u64, ChampionFields>> {
+        let path = self.cache_dir.join(CacheFile::Champions.to_string());
+        if !path.try_exists().unwrap_or(false) {
+            let champions = self.fetch_all_champions().await?;
+            self.cache_versioned(
+                &champions,
+                CacheFile::Champions,
+                &self.data_version().await?,
+            )?;
+        }
+        let bytes = {
+            let _span = tracing::info_span!("cache").entered();
+            fs::read(&path).with_context(|| format!("couldn't read {}", path.display()))?
+        };
+        project(&bytes, fields)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn only_requested_fields() -> Result<()> {
+        let annie: Value = serde_json::from_str(&fs::read_to_string("testing/annie.json")?)?;
+        let catalog = serde_json::to_vec(&serde_json::json!({ "1": annie }))?;
+
+        let champions = project(&catalog, &[Field::Name, Field::Roles])?;
+        let annie = &champions[&1];
+        assert_eq!(annie.id, 1);
+        assert_eq!(annie.name.as_deref(), Some("Annie"));
+        assert_eq!(
+            annie.roles.as_deref(),
+            Some(&["mage".to_string(), "support".to_string()][..])
+        );
+        assert_eq!(annie.title, None);
+        assert_eq!(annie.tactical_info, None);
+
+        let tactical = project(&catalog, &[Field::TacticalInfo])?;
+        assert_eq!(tactical[&1].tactical_info.as_ref().unwrap().difficulty, 1);
+        assert_eq!(tactical[&1].name, None);
+        assert!(project(b"{\"1\": []}", &[Field::Name]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! Where exported data came from. Packs, asset manifests and generated source all carry a
+//! provenance block, so a dataset that gets passed around can still be traced back to the patch
+//! it was taken from, along with the notice Riot asks fan projects to show.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{CDragon, CacheFile},
+    patch::PatchVersion,
+};
+
+pub const SOURCE: &str = "CommunityDragon";
+pub const SOURCE_URL: &str = "https://communitydragon.org";
+/// Riot's "Legal Jibber Jabber" for projects using their data
+pub const NOTICE: &str = "blitzadex isn't endorsed by Riot Games and doesn't reflect the views \
+    or opinions of Riot Games or anyone officially involved in producing or managing Riot Games \
+    properties. Riot Games, and all associated properties are trademarks or registered \
+    trademarks of Riot Games, Inc.";
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct Provenance {
+    pub source: String,
+    pub source_url: String,
+    /// The patch the data is for, e.g. "15.1", when it's known
+    pub patch: Option<String>,
+    pub generated: DateTime<Utc>,
+    pub notice: String,
+}
+
+impl Provenance {
+    pub fn new(patch: Option<String>) -> Self {
+        Self {
+            source: SOURCE.to_string(),
+            source_url: SOURCE_URL.to_string(),
+            patch,
+            generated: Utc::now(),
+            notice: NOTICE.to_string(),
+        }
+    }
+
+    /// The provenance as comment lines, each starting with `prefix`, for exports that aren't
+    /// JSON.
+    pub fn comment(&self, prefix: &str) -> String {
+        let mut out = String::new();
+        let patch = self.patch.as_deref().unwrap_or("unknown");
+        let _ = writeln!(
+            out,
+            "{prefix} source: {} ({})",
+            self.source, self.source_url
+        );
+        let _ = writeln!(out, "{prefix} patch: {patch}");
+        let _ = writeln!(out, "{prefix} generated: {}", self.generated.to_rfc3339());
+        let _ = writeln!(out, "{prefix} notice: {}", self.notice);
+        out
+    }
+}
+
+impl CDragon {
+    /// The provenance of the cached data, without fetching anything.
+    pub fn provenance(&self) -> Provenance {
+        let patch = self
+            .load_obj::<PatchVersion>(CacheFile::ContentMetadata)
+            .ok()
+            .map(|patch| patch.patch().to_string());
+        Provenance::new(patch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use color_eyre::Result;
+
+    #[test]
+    fn patch_from_cache() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        assert_eq!(cdrag.provenance().patch, None);
+        let patch = PatchVersion {
+            version: "15.1.6463155+branch.releases-15-1.code.public.content.release".to_string(),
+            released: None,
+        };
+        cdrag.cache_obj(&patch, CacheFile::ContentMetadata)?;
+        let provenance = cdrag.provenance();
+        assert_eq!(provenance.patch.as_deref(), Some("15.1"));
+        let comment = provenance.comment("//");
+        assert!(comment.starts_with("// source: CommunityDragon (https://communitydragon.org)\n"));
+        assert!(comment.contains("// patch: 15.1\n"));
+        assert!(comment.lines().all(|line| line.starts_with("// ")));
+        Ok(())
+    }
+}
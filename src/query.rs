@@ -0,0 +1,320 @@
+//! A small query language for finding champions, e.g.
+//! `role:support dmg:magic diff:<2 skinline:"star guardian" lux`.
+//!
+//! A query is a list of terms separated by spaces, and a champion has to match every one of
+//! them. Double quotes keep a phrase together. Terms are either free text, matched
+//! [fuzzily](crate::fuzzy) against the champion's name, alias, title and nicknames, by word
+//! against its bio, and the voice actor and any other text in its
+//! [community metadata](crate::community) where the overlay has them, and as is
+//! against its [names in other locales](crate::names), or `key:value` filters:
+//!
+//! - `role:` a class role, e.g. `role:support`
+//! - `dmg:` the damage type, `magic`, `physical` or `mixed`
+//! - `diff:` the difficulty from 1 to 3, exactly or with `<`, `<=`, `>` or `>=`, e.g. `diff:<2`
+//! - `pos:` a lane from the community metadata, e.g. `pos:jungle`
+//! - `skinline:` part of the name of a skin line the champion has a skin in
+//!
+//! Results are ranked by how well the free text matched, e.g. a name ahead of a word in a bio.
+
+use std::{cmp::Ordering, str::FromStr};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+
+use serde_json::Value;
+
+use crate::{
+    cdragon::{Champion, SkinLine},
+    community::Position,
+    fuzzy,
+    names::{fold, LocalizedNames},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Text(String),
+    Role(String),
+    Damage(String),
+    /// Matches when the difficulty compares to the value as one of the orderings
+    Difficulty(Vec<Ordering>, u64),
+    Position(Position),
+    SkinLine(String),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+}
+
+/// Splits on spaces outside of double quotes, dropping the quotes.
+fn tokens(query: &str) -> Result<Vec<String>> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut quoted = false;
+    for c in query.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+    if quoted {
+        return Err(eyre!("unclosed quote in {query}"));
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn parse_difficulty(value: &str) -> Result<Filter> {
+    let (orderings, number) = if let Some(number) = value.strip_prefix("<=") {
+        (vec![Ordering::Less, Ordering::Equal], number)
+    } else if let Some(number) = value.strip_prefix(">=") {
+        (vec![Ordering::Greater, Ordering::Equal], number)
+    } else if let Some(number) = value.strip_prefix('<') {
+        (vec![Ordering::Less], number)
+    } else if let Some(number) = value.strip_prefix('>') {
+        (vec![Ordering::Greater], number)
+    } else {
+        (
+            vec![Ordering::Equal],
+            value.strip_prefix('=').unwrap_or(value),
+        )
+    };
+    let number = number
+        .parse()
+        .with_context(|| format!("{value} isn't a difficulty, try one like 2 or <3"))?;
+    Ok(Filter::Difficulty(orderings, number))
+}
+
+impl FromStr for Query {
+    type Err = color_eyre::Report;
+
+    fn from_str(query: &str) -> Result<Self> {
+        let filters = tokens(query)?
+            .into_iter()
+            .map(|token| {
+                let Some((key, value)) = token.split_once(':') else {
+                    return Ok(Filter::Text(fold(&token)));
+                };
+                let value = value.to_lowercase();
+                Ok(match key.to_lowercase().as_str() {
+                    "role" => Filter::Role(value),
+                    "dmg" | "damage" => Filter::Damage(value),
+                    "diff" | "difficulty" => parse_difficulty(&value)?,
+                    "pos" | "position" => Filter::Position(
+                        serde_json::from_value(Value::String(value.clone()))
+                            .with_context(|| format!("{value} isn't a position"))?,
+                    ),
+                    "skinline" => Filter::SkinLine(value),
+                    _ => return Err(eyre!("{key} isn't something to search by")),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { filters })
+    }
+}
+
+impl Filter {
+    /// How well free text matched, the best of the champion's fields. Any other filter scores 0
+    /// when it matches.
+    fn score(
+        &self,
+        champion: &Champion,
+        skin_lines: &[SkinLine],
+        names: &LocalizedNames,
+    ) -> Option<u32> {
+        let Filter::Text(text) = self else {
+            return self.matches(champion, skin_lines, names).then_some(0);
+        };
+        [&champion.name, &champion.alias]
+            .into_iter()
+            .chain(&champion.community.nicknames)
+            .filter_map(|field| fuzzy::score(text, field))
+            .chain(fuzzy::score(text, &champion.title).map(|score| score / 2))
+            .chain(fuzzy::word_score(text, &champion.short_bio))
+            .chain(
+                champion
+                    .community
+                    .voice_actor
+                    .as_deref()
+                    .into_iter()
+                    .chain(champion.community.extra_text())
+                    .filter_map(|field| fuzzy::word_score(text, field)),
+            )
+            .chain(
+                names
+                    .contains(champion.id, text)
+                    .then_some(fuzzy::SUBSTRING),
+            )
+            .max()
+    }
+
+    fn matches(
+        &self,
+        champion: &Champion,
+        skin_lines: &[SkinLine],
+        names: &LocalizedNames,
+    ) -> bool {
+        match self {
+            Filter::Text(_) => self.score(champion, skin_lines, names).is_some(),
+            Filter::Role(role) => champion.roles.iter().any(|r| r.eq_ignore_ascii_case(role)),
+            Filter::Damage(damage) => champion
+                .tactical_info
+                .damage_type
+                .trim_start_matches('k')
+                .eq_ignore_ascii_case(damage),
+            Filter::Difficulty(orderings, value) => {
+                orderings.contains(&champion.tactical_info.difficulty.cmp(value))
+            }
+            Filter::Position(position) => champion.community.positions.contains(position),
+            Filter::SkinLine(name) => skin_lines
+                .iter()
+                .filter(|line| line.name.to_lowercase().contains(name))
+                .any(|line| {
+                    champion
+                        .skins
+                        .iter()
+                        .any(|skin| skin.skin_lines.contains(&line.id))
+                }),
+        }
+    }
+}
+
+impl Query {
+    /// Whether the query needs the skin lines to be matched against.
+    pub fn uses_skin_lines(&self) -> bool {
+        self.filters
+            .iter()
+            .any(|filter| matches!(filter, Filter::SkinLine(_)))
+    }
+
+    pub fn matches(
+        &self,
+        champion: &Champion,
+        skin_lines: &[SkinLine],
+        names: &LocalizedNames,
+    ) -> bool {
+        self.score(champion, skin_lines, names).is_some()
+    }
+
+    /// How well the champion matches the free text of the query, or `None` when it doesn't
+    /// match the query at all.
+    pub fn score(
+        &self,
+        champion: &Champion,
+        skin_lines: &[SkinLine],
+        names: &LocalizedNames,
+    ) -> Option<u32> {
+        self.filters
+            .iter()
+            .map(|filter| filter.score(champion, skin_lines, names))
+            .sum()
+    }
+
+    /// The champions matching the query, best matches first and otherwise by name.
+    pub fn search<'a>(
+        &self,
+        champions: impl IntoIterator<Item = &'a Champion>,
+        skin_lines: &[SkinLine],
+        names: &LocalizedNames,
+    ) -> Vec<&'a Champion> {
+        let mut found: Vec<(u32, &Champion)> = champions
+            .into_iter()
+            .filter_map(|champion| Some((self.score(champion, skin_lines, names)?, champion)))
+            .collect();
+        found.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.cmp(a_score).then_with(|| a.name.cmp(&b.name))
+        });
+        found.into_iter().map(|(_, champion)| champion).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn parse_and_match() -> Result<()> {
+        let query: Query = r#"role:mage  dmg:magic diff:<2 skinline:"lunar revel" dark"#.parse()?;
+        assert_eq!(
+            query.filters,
+            [
+                Filter::Role("mage".to_string()),
+                Filter::Damage("magic".to_string()),
+                Filter::Difficulty(vec![Ordering::Less], 2),
+                Filter::SkinLine("lunar revel".to_string()),
+                Filter::Text("dark".to_string()),
+            ]
+        );
+        assert!(query.uses_skin_lines());
+        assert!("role:".parse::<Query>().is_ok());
+        assert!("tier:s".parse::<Query>().is_err());
+        assert!("diff:easy".parse::<Query>().is_err());
+        assert_eq!(
+            "pos:adc".parse::<Query>()?.filters,
+            [Filter::Position(Position::Bottom)]
+        );
+        assert!(r#"skinline:"star guardian"#.parse::<Query>().is_err());
+
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let lines = [SkinLine {
+            id: 54,
+            name: "Lunar Revel".to_string(),
+            description: String::new(),
+        }];
+        let names = LocalizedNames::default();
+        assert!(query.matches(&annie, &lines, &names));
+        assert!(!query.matches(&annie, &[], &names));
+        for miss in ["diff:>=2", "dmg:physical", "role:tank", "jinx"] {
+            let query: Query = miss.parse()?;
+            assert!(!query.matches(&annie, &lines, &names), "{miss}");
+        }
+        assert!("anie".parse::<Query>()?.matches(&annie, &[], &names));
+        assert!("pyromantic".parse::<Query>()?.matches(&annie, &[], &names));
+        let mut voiced = Champion {
+            name: "Ahri".to_string(),
+            ..Default::default()
+        };
+        voiced.community.voice_actor = Some("Laura Post".to_string());
+        voiced
+            .community
+            .extra
+            .insert("region".to_string(), "Ionia".into());
+        voiced
+            .community
+            .extra
+            .insert("rating".to_string(), 5.into());
+        assert!("post".parse::<Query>()?.matches(&voiced, &[], &names));
+        assert!("ionia".parse::<Query>()?.matches(&voiced, &[], &names));
+
+        let mut tibbers = Champion {
+            id: 2,
+            name: "Tibbers".to_string(),
+            short_bio: "Annie's bear".to_string(),
+            ..Default::default()
+        };
+        tibbers.tactical_info.difficulty = 1;
+        let found = "annie"
+            .parse::<Query>()?
+            .search([&tibbers, &annie], &[], &names);
+        let found: Vec<&str> = found.iter().map(|champ| champ.name.as_str()).collect();
+        assert_eq!(found, ["Annie", "Tibbers"]);
+
+        let korean = LocalizedNames::from_tables([BTreeMap::from([(1, "애니".to_string())])]);
+        assert!("애니 role:mage"
+            .parse::<Query>()?
+            .matches(&annie, &[], &korean));
+        Ok(())
+    }
+}
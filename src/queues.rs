@@ -0,0 +1,219 @@
+//! Queues, maps and game modes, the reference data match histories refer to by id.
+//!
+//! A map is listed once per game mode played on it, e.g. Summoner's Rift for both `CLASSIC`
+//! and `URF`, so the game modes are read off the maps.
+
+use std::collections::BTreeMap;
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::Display;
+
+use crate::{
+    cdragon::{asset_url, parse_json, CDragon, CacheFile},
+    http,
+};
+
+/// Ids of well known queues, as in a match's `queueId`.
+pub mod queue {
+    pub const CUSTOM: u64 = 0;
+    pub const NORMAL_DRAFT: u64 = 400;
+    pub const RANKED_SOLO: u64 = 420;
+    pub const RANKED_FLEX: u64 = 440;
+    pub const ARAM: u64 = 450;
+    pub const SWIFTPLAY: u64 = 480;
+    pub const ARENA: u64 = 1700;
+}
+
+/// Ids of well known maps, as in a match's `mapId`.
+pub mod map {
+    pub const SUMMONERS_RIFT: u64 = 11;
+    pub const HOWLING_ABYSS: u64 = 12;
+    pub const CONVERGENCE: u64 = 22;
+    pub const RINGS_OF_WRATH: u64 = 30;
+}
+
+/// The game mode as in a match's `gameMode`.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum GameMode {
+    Classic,
+    Aram,
+    Urf,
+    OneForAll,
+    NexusBlitz,
+    UltBook,
+    /// Arena
+    Cherry,
+    Swiftplay,
+    Tutorial,
+    PracticeTool,
+    Tft,
+    /// A mode blitzadex doesn't know yet
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Queue {
+    pub name: String,
+    #[serde(default)]
+    pub short_name: String,
+    /// Usually the map, e.g. "Summoner's Rift"
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub detailed_description: String,
+    #[serde(default)]
+    pub game_mode: Option<GameMode>,
+    #[serde(default)]
+    pub map_id: Option<u64>,
+}
+
+/// Queues keyed by id.
+pub type Queues = BTreeMap<u64, Queue>;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Map {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// e.g. "SR"
+    pub map_string_id: String,
+    pub game_mode: GameMode,
+    pub game_mode_name: String,
+    #[serde(default)]
+    pub game_mode_short_name: String,
+    #[serde(default)]
+    pub game_mode_description: String,
+    /// Whether the mode is a rotating game mode
+    #[serde(default, rename = "isRGM")]
+    pub is_rgm: bool,
+}
+
+/// A game mode with what the client calls it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameModeInfo {
+    pub mode: GameMode,
+    pub name: String,
+    pub description: String,
+    /// The maps it's played on
+    pub map_ids: Vec<u64>,
+}
+
+/// The game modes played on `maps`, in [`GameMode`] order.
+pub fn game_modes(maps: &[Map]) -> Vec<GameModeInfo> {
+    let mut modes: BTreeMap<GameMode, GameModeInfo> = BTreeMap::new();
+    for map in maps {
+        let info = modes.entry(map.game_mode).or_insert_with(|| GameModeInfo {
+            mode: map.game_mode,
+            name: map.game_mode_name.clone(),
+            description: map.game_mode_description.clone(),
+            map_ids: vec![],
+        });
+        if !info.map_ids.contains(&map.id) {
+            info.map_ids.push(map.id);
+        }
+    }
+    modes.into_values().collect()
+}
+
+/// Parses the queues keyed by id, or as a list of queues with their ids.
+fn parse_queues(bytes: &[u8]) -> Result<Queues> {
+    let file: Value = parse_json(bytes)?;
+    let queues = match file {
+        Value::Array(queues) => queues
+            .into_iter()
+            .map(|queue| {
+                let id = queue.get("id").and_then(Value::as_u64).unwrap_or_default();
+                Ok((id, serde_json::from_value(queue)?))
+            })
+            .collect::<Result<Queues>>()?,
+        file => serde_json::from_value(file)?,
+    };
+    Ok(queues)
+}
+
+impl CDragon {
+    /// Loads the cached queues, fetching them when they aren't cached yet.
+    pub async fn queues(&self) -> Result<Queues> {
+        if let Ok(queues) = self.load_obj(CacheFile::Queues) {
+            return Ok(queues);
+        }
+        let queues = self.fetch_queues().await?;
+        self.cache_obj(&queues, CacheFile::Queues)?;
+        Ok(queues)
+    }
+
+    /// Loads the cached maps, fetching them when they aren't cached yet.
+    pub async fn maps(&self) -> Result<Vec<Map>> {
+        if let Ok(maps) = self.load_obj(CacheFile::Maps) {
+            return Ok(maps);
+        }
+        let maps = self.fetch_maps().await?;
+        self.cache_obj(&maps, CacheFile::Maps)?;
+        Ok(maps)
+    }
+
+    /// The game modes of the [maps](CDragon::maps).
+    pub async fn game_modes(&self) -> Result<Vec<GameModeInfo>> {
+        Ok(game_modes(&self.maps().await?))
+    }
+
+    pub async fn fetch_queues(&self) -> Result<Queues> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/queues.json"))
+            .await
+            .with_context(|| "couldn't fetch the queues")?;
+        parse_queues(&bytes)
+    }
+
+    pub async fn fetch_maps(&self) -> Result<Vec<Map>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/maps.json"))
+            .await
+            .with_context(|| "couldn't fetch the maps")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn queues_maps_and_modes() -> Result<()> {
+        let queues = parse_queues(&std::fs::read("testing/queues.json")?)?;
+        let solo = &queues[&queue::RANKED_SOLO];
+        assert_eq!(solo.game_mode, Some(GameMode::Classic));
+        assert_eq!(queues[&queue::ARENA].map_id, Some(map::RINGS_OF_WRATH));
+        let listed = parse_queues(
+            br#"[{"id": 450, "name": "ARAM", "gameMode": "ARAM"},
+                {"id": 2400, "name": "New", "gameMode": "BRAWL"}]"#,
+        )?;
+        assert_eq!(listed[&queue::ARAM].name, "ARAM");
+        assert_eq!(listed[&2400].game_mode, Some(GameMode::Other));
+
+        let maps: Vec<Map> = serde_json::from_str(&std::fs::read_to_string("testing/maps.json")?)?;
+        let modes = game_modes(&maps);
+        let names: Vec<(GameMode, &str)> = modes
+            .iter()
+            .map(|info| (info.mode, info.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (GameMode::Classic, "Summoner's Rift"),
+                (GameMode::Aram, "ARAM"),
+                (GameMode::Urf, "Ultra Rapid Fire"),
+                (GameMode::Cherry, "Arena")
+            ]
+        );
+        assert_eq!(modes[2].map_ids, [map::SUMMONERS_RIFT]);
+        assert_eq!(GameMode::Cherry.to_string(), "CHERRY");
+        Ok(())
+    }
+}
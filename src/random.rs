@@ -0,0 +1,213 @@
+//! Random champion picks for custom games, from the whole roster or the champions matching some
+//! filters. Picks are never repeated within a draw, so teams drawn together don't share anyone.
+
+use std::collections::HashMap;
+
+use color_eyre::{eyre::eyre, Result};
+use serde::{
+    de::{value::Error, IntoDeserializer},
+    Deserialize,
+};
+
+use crate::{cdragon::Champion, community::Position};
+
+/// How many players a team has
+pub const TEAM_SIZE: usize = 5;
+/// The lanes of a team of five, in the order they're listed
+const LANES: [Position; TEAM_SIZE] = [
+    Position::Top,
+    Position::Jungle,
+    Position::Middle,
+    Position::Bottom,
+    Position::Support,
+];
+
+/// What players call a role: either a class role from the champion data like "mage", or a lane
+/// like "mid".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Role {
+    Class(String),
+    Lane(Position),
+}
+
+impl Role {
+    pub fn parse(role: &str) -> Role {
+        let role = role.to_lowercase();
+        let lane: Result<Position, Error> =
+            Position::deserialize(role.as_str().into_deserializer());
+        match lane {
+            Ok(lane) => Role::Lane(lane),
+            Err(_) => Role::Class(role),
+        }
+    }
+
+    pub fn matches(&self, champion: &Champion) -> bool {
+        match self {
+            Role::Class(role) => champion.roles.iter().any(|r| r.eq_ignore_ascii_case(role)),
+            Role::Lane(position) => champion.plays(*position),
+        }
+    }
+}
+
+/// The kind of damage a champion mostly deals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Damage {
+    #[cfg_attr(feature = "cli", value(alias = "magic"))]
+    Ap,
+    #[cfg_attr(feature = "cli", value(alias = "physical"))]
+    Ad,
+    Mixed,
+}
+
+impl Damage {
+    /// The damage type as it's written in the champion data, minus the "k"
+    fn name(self) -> &'static str {
+        match self {
+            Damage::Ap => "magic",
+            Damage::Ad => "physical",
+            Damage::Mixed => "mixed",
+        }
+    }
+
+    fn matches(self, champion: &Champion) -> bool {
+        champion
+            .tactical_info
+            .damage_type
+            .trim_start_matches('k')
+            .eq_ignore_ascii_case(self.name())
+    }
+}
+
+/// Which champions can be picked.
+#[derive(Debug, Default)]
+pub struct Filters {
+    pub role: Option<Role>,
+    pub damage: Option<Damage>,
+    /// Ids of the champions never to pick
+    pub exclude: Vec<u64>,
+}
+
+impl Filters {
+    /// The champions that can be picked, by id so that a seed always draws the same picks.
+    pub fn pool<'a>(&self, champions: &'a HashMap<u64, Champion>) -> Vec<&'a Champion> {
+        let mut pool: Vec<&Champion> = champions
+            .values()
+            .filter(|champ| self.role.as_ref().is_none_or(|role| role.matches(champ)))
+            .filter(|champ| self.damage.is_none_or(|damage| damage.matches(champ)))
+            .filter(|champ| !self.exclude.contains(&champ.id))
+            .collect();
+        pool.sort_by_key(|champ| champ.id);
+        pool
+    }
+}
+
+#[derive(Debug)]
+pub struct Pick<'a> {
+    pub champion: &'a Champion,
+    /// The lane the champion was picked for, when drawing a team
+    pub lane: Option<Position>,
+}
+
+/// Draws `count` champions out of `pool`.
+pub fn draw<'a>(
+    pool: &mut Vec<&'a Champion>,
+    count: usize,
+    rng: &mut fastrand::Rng,
+) -> Result<Vec<Pick<'a>>> {
+    if pool.len() < count {
+        return Err(eyre!(
+            "only {} champions match, not enough to pick {count}",
+            pool.len()
+        ));
+    }
+    Ok((0..count)
+        .map(|_| Pick {
+            champion: pool.swap_remove(rng.usize(..pool.len())),
+            lane: None,
+        })
+        .collect())
+}
+
+/// Draws a champion for each lane out of `pool`, only picking champions usually played there.
+pub fn draw_team<'a>(
+    pool: &mut Vec<&'a Champion>,
+    rng: &mut fastrand::Rng,
+) -> Result<Vec<Pick<'a>>> {
+    // The lanes with the fewest candidates go first, so they aren't left without any
+    let mut lanes = LANES;
+    lanes.sort_by_key(|lane| pool.iter().filter(|champ| champ.plays(*lane)).count());
+    let mut team = vec![];
+    for lane in lanes {
+        let candidates: Vec<usize> = (0..pool.len()).filter(|i| pool[*i].plays(lane)).collect();
+        let Some(i) = rng.choice(candidates) else {
+            return Err(eyre!("no champion left to play {lane}"));
+        };
+        team.push(Pick {
+            champion: pool.swap_remove(i),
+            lane: Some(lane),
+        });
+    }
+    team.sort_by_key(|pick| LANES.iter().position(|lane| Some(*lane) == pick.lane));
+    Ok(team)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn draws_without_repeats() -> Result<()> {
+        let annie = std::fs::read_to_string("testing/annie.json")?;
+        let lanes = [
+            Position::Top,
+            Position::Jungle,
+            Position::Middle,
+            Position::Middle,
+            Position::Bottom,
+            Position::Support,
+        ];
+        let mut champions = HashMap::new();
+        for (i, lane) in lanes.iter().enumerate() {
+            let mut champ: Champion = serde_json::from_str(&annie)?;
+            champ.id = i as u64 + 1;
+            champ.community.positions = vec![*lane];
+            champions.insert(champ.id, champ);
+        }
+
+        assert_eq!(Role::parse("Mid"), Role::Lane(Position::Middle));
+        assert_eq!(Role::parse("mage"), Role::Class("mage".to_string()));
+        let mids = Filters {
+            role: Some(Role::parse("mid")),
+            damage: Some(Damage::Ap),
+            exclude: vec![3],
+        };
+        let pool = mids.pool(&champions);
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool[0].id, 4);
+        let physical = Filters {
+            damage: Some(Damage::Ad),
+            ..Default::default()
+        };
+        assert!(physical.pool(&champions).is_empty());
+
+        let mut rng = fastrand::Rng::with_seed(7);
+        let mut pool = Filters::default().pool(&champions);
+        let team = draw_team(&mut pool, &mut rng)?;
+        let drawn: Vec<Option<Position>> = team.iter().map(|pick| pick.lane).collect();
+        assert_eq!(drawn, LANES.map(Some));
+        assert_eq!(pool.len(), 1);
+        assert!(draw_team(&mut pool, &mut rng).is_err());
+
+        let mut pool = Filters::default().pool(&champions);
+        let mut ids: Vec<u64> = draw(&mut pool, 6, &mut rng)?
+            .iter()
+            .map(|pick| pick.champion.id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 6);
+        assert!(draw(&mut pool, 1, &mut rng).is_err());
+        Ok(())
+    }
+}
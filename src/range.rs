@@ -0,0 +1,68 @@
+use std::fmt::Write;
+
+use crate::cdragon::Champion;
+
+const BAR_WIDTH: usize = 40;
+/// Ranges beyond this are effectively global (e.g. Ezreal's R) and would flatten every other bar.
+const GLOBAL_RANGE: f64 = 5000.;
+
+/// Familiar distances to size a champion's spells against.
+pub const REFERENCES: [(&str, f64); 2] = [("Flash", 400.), ("Turret", 750.)];
+
+/// Renders a champion's spell ranges next to the [`REFERENCES`] as a horizontal bar chart.
+///
+/// Spells without a range (self casts and the like) are skipped, and global ranges are drawn as a
+/// full bar.
+pub fn render_chart(champion: &Champion) -> String {
+    let mut rows: Vec<(String, f64)> = champion
+        .spells
+        .iter()
+        .filter_map(|spell| {
+            let range = spell.max_range().filter(|range| *range > 0.)?;
+            let label = format!("{} {}", spell.spell_key.to_uppercase(), spell.name);
+            Some((label, range))
+        })
+        .collect();
+    rows.extend(
+        REFERENCES
+            .iter()
+            .map(|(name, range)| (name.to_string(), *range)),
+    );
+
+    let scale = rows
+        .iter()
+        .map(|(_, range)| *range)
+        .filter(|range| *range < GLOBAL_RANGE)
+        .fold(1., f64::max);
+    let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    for (label, range) in rows {
+        let len = ((range / scale).min(1.) * BAR_WIDTH as f64).round() as usize;
+        let bar = "█".repeat(len.max(1));
+        let range = if range >= GLOBAL_RANGE {
+            "global".to_string()
+        } else {
+            format!("{range:.0}")
+        };
+        let _ = writeln!(out, "{label:<label_width$} {range:>6} {bar}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn annie_ranges() -> color_eyre::Result<()> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("testing/annie.json")?)?;
+        let chart = render_chart(&annie);
+        let q = chart.lines().next().unwrap();
+        assert!(q.starts_with("Q Disintegrate"));
+        assert!(q.contains(" 625 "));
+        assert!(chart.contains("Turret"));
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+//! Champion recommendations derived from the ratings in the champion data, without any win rates.
+
+use crate::{cdragon::Champion, community::Position, random::Role};
+
+#[derive(Debug)]
+pub struct Recommendation<'a> {
+    pub champion: &'a Champion,
+    /// From 0 to 1, higher is friendlier
+    pub score: f64,
+    /// Why the champion scored well, in order of weight
+    pub reasons: Vec<&'static str>,
+}
+
+/// How friendly a champion is to someone new to the game, from 0 to 1.
+///
+/// Difficulty counts the most. Durability and crowd control make mistakes cheaper to recover
+/// from, while mobility usually means dashes and blinks that take practice to use well, so it
+/// counts against the champion.
+pub fn newbie_friendliness(champion: &Champion) -> (f64, Vec<&'static str>) {
+    let play = &champion.playstyle_info;
+    // Difficulty goes from 1 to 3, the playstyle ratings from 0 to 3
+    let ease = (3. - champion.tactical_info.difficulty.clamp(1, 3) as f64) / 2.;
+    let durability = play.durability.min(3) as f64 / 3.;
+    let crowd_control = play.crowd_control.min(3) as f64 / 3.;
+    let simplicity = (3. - play.mobility.min(3) as f64) / 3.;
+    let score = 0.5 * ease + 0.2 * durability + 0.15 * crowd_control + 0.15 * simplicity;
+
+    let mut reasons = vec![];
+    if champion.tactical_info.difficulty <= 1 {
+        reasons.push("low difficulty");
+    }
+    if play.durability >= 2 {
+        reasons.push("durable enough to survive mistakes");
+    }
+    if play.crowd_control >= 2 {
+        reasons.push("reliable crowd control");
+    }
+    if play.mobility <= 1 {
+        reasons.push("few mobility spells to master");
+    }
+    (score, reasons)
+}
+
+/// The `count` friendliest champions for a new player, optionally only those with `role` or
+/// played in `position`.
+pub fn for_new_player<'a>(
+    champions: impl IntoIterator<Item = &'a Champion>,
+    role: Option<&Role>,
+    position: Option<Position>,
+    count: usize,
+) -> Vec<Recommendation<'a>> {
+    let mut recs: Vec<Recommendation> = champions
+        .into_iter()
+        .filter(|champ| role.is_none_or(|role| role.matches(champ)))
+        .filter(|champ| position.is_none_or(|position| champ.plays(position)))
+        .map(|champion| {
+            let (score, reasons) = newbie_friendliness(champion);
+            Recommendation {
+                champion,
+                score,
+                reasons,
+            }
+        })
+        .collect();
+    recs.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.champion.name.cmp(&b.champion.name))
+    });
+    recs.truncate(count);
+    recs
+}
+
+/// How well `partner` complements `champion` as an Arena duo, from 0 to 1.
+///
+/// Arena rounds are short two on two fights, so a duo wants a frontline, some crowd control to
+/// set up kills and damage of both kinds, which makes armor or magic resist alone a poor answer
+/// to them. The partner scores for each of those it brings that `champion` lacks.
+pub fn duo_complementarity(champion: &Champion, partner: &Champion) -> (f64, Vec<&'static str>) {
+    let (ours, theirs) = (&champion.playstyle_info, &partner.playstyle_info);
+    // How much of a 0 to 3 rating the partner adds on top of the champion's
+    let fills = |ours: u64, theirs: u64| theirs.min(3).saturating_sub(ours.min(3)) as f64 / 3.;
+    let frontline = match ours.durability.max(theirs.durability) >= 2 {
+        true => 1.,
+        false => 0.,
+    };
+    let mixed_damage = match (
+        champion.tactical_info.damage_type.as_str(),
+        partner.tactical_info.damage_type.as_str(),
+    ) {
+        ("kPhysical", "kMagic") | ("kMagic", "kPhysical") => 1.,
+        (ours, theirs) if ours == "kMixed" || theirs == "kMixed" => 0.5,
+        _ => 0.,
+    };
+    let score = 0.25 * frontline
+        + 0.25 * mixed_damage
+        + 0.2 * fills(ours.crowd_control, theirs.crowd_control)
+        + 0.15 * fills(ours.damage, theirs.damage)
+        + 0.15 * fills(ours.utility, theirs.utility);
+
+    let mut reasons = vec![];
+    if ours.durability < 2 && theirs.durability >= 2 {
+        reasons.push("adds a frontline");
+    }
+    if mixed_damage == 1. {
+        reasons.push("mixes damage types");
+    }
+    if ours.crowd_control < 2 && theirs.crowd_control >= 2 {
+        reasons.push("brings crowd control to set up kills");
+    }
+    if ours.damage < 2 && theirs.damage >= 2 {
+        reasons.push("adds damage");
+    }
+    if ours.utility < 2 && theirs.utility >= 2 {
+        reasons.push("supports with utility");
+    }
+    if frontline == 0. {
+        reasons.push("neither can frontline");
+    }
+    (score, reasons)
+}
+
+/// The `count` best Arena duo partners for `champion`, optionally only those with `role` or
+/// played in `position`.
+pub fn arena_duo<'a>(
+    champions: impl IntoIterator<Item = &'a Champion>,
+    champion: &Champion,
+    role: Option<&Role>,
+    position: Option<Position>,
+    count: usize,
+) -> Vec<Recommendation<'a>> {
+    let mut recs: Vec<Recommendation> = champions
+        .into_iter()
+        .filter(|partner| partner.id != champion.id)
+        .filter(|champ| role.is_none_or(|role| role.matches(champ)))
+        .filter(|champ| position.is_none_or(|position| champ.plays(position)))
+        .map(|partner| {
+            let (score, reasons) = duo_complementarity(champion, partner);
+            Recommendation {
+                champion: partner,
+                score,
+                reasons,
+            }
+        })
+        .collect();
+    recs.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.champion.name.cmp(&b.champion.name))
+    });
+    recs.truncate(count);
+    recs
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::cdragon::{PlaystyleInfo, TactialInfo};
+
+    fn champ(id: u64, name: &str, role: &str, difficulty: u64, mobility: u64) -> Champion {
+        Champion {
+            id,
+            name: name.to_string(),
+            roles: vec![role.to_string()],
+            tactical_info: TactialInfo {
+                difficulty,
+                ..Default::default()
+            },
+            playstyle_info: PlaystyleInfo {
+                durability: 3,
+                mobility,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn easy_picks_first() {
+        let mut champions: HashMap<u64, Champion> = [
+            champ(1, "Garen", "fighter", 1, 1),
+            champ(2, "Riven", "fighter", 3, 3),
+            champ(3, "Malphite", "tank", 1, 1),
+            champ(4, "Irelia", "fighter", 2, 3),
+        ]
+        .into_iter()
+        .map(|champ| (champ.id, champ))
+        .collect();
+        let recs = for_new_player(champions.values(), Some(&Role::parse("Fighter")), None, 5);
+        let names: Vec<&str> = recs.iter().map(|r| r.champion.name.as_str()).collect();
+        assert_eq!(names, ["Garen", "Irelia", "Riven"]);
+        assert!(recs[0].reasons.contains(&"low difficulty"));
+        assert!(recs[2].score < recs[1].score);
+
+        champions.get_mut(&3).unwrap().community.positions = vec![Position::Top];
+        let tops = for_new_player(champions.values(), Some(&Role::parse("top")), None, 5);
+        assert_eq!(tops.len(), 1);
+        assert_eq!(tops[0].champion.name, "Malphite");
+    }
+
+    #[test]
+    fn duo_partners_complement() {
+        let with = |mut champ: Champion, damage_type: &str, durability, crowd_control| {
+            champ.tactical_info.damage_type = damage_type.to_string();
+            champ.playstyle_info.durability = durability;
+            champ.playstyle_info.crowd_control = crowd_control;
+            champ
+        };
+        let jinx = || with(champ(1, "Jinx", "marksman", 2, 1), "kPhysical", 0, 1);
+        let champions: HashMap<u64, Champion> = [
+            jinx(),
+            with(champ(2, "Leona", "tank", 1, 1), "kMagic", 3, 3),
+            with(champ(3, "Caitlyn", "marksman", 1, 1), "kPhysical", 0, 1),
+            with(champ(4, "Lux", "mage", 1, 1), "kMagic", 0, 2),
+        ]
+        .into_iter()
+        .map(|champ| (champ.id, champ))
+        .collect();
+        let recs = arena_duo(champions.values(), &jinx(), None, None, 5);
+        let names: Vec<&str> = recs.iter().map(|r| r.champion.name.as_str()).collect();
+        assert_eq!(names, ["Leona", "Lux", "Caitlyn"]);
+        assert!(recs[0].reasons.contains(&"adds a frontline"));
+        assert!(recs[2].reasons.contains(&"neither can frontline"));
+    }
+}
@@ -0,0 +1,91 @@
+//! A minimal live round trip through CommunityDragon and the cache, run in a scratch directory so
+//! the real cache is never touched.
+
+use std::{collections::HashMap, fmt::Display, fs};
+
+use color_eyre::{eyre::eyre, Result};
+
+use crate::cdragon::{CDragon, CacheFile, Champion};
+
+/// Annie, who has been champion 1 since the beginning
+const CHAMPION_ID: u64 = 1;
+
+const STEPS: [&str; 4] = [
+    "fetch plugins",
+    "fetch a champion",
+    "write the cache",
+    "read the cache",
+];
+
+#[derive(Debug)]
+pub enum Outcome {
+    Pass(String),
+    Fail(String),
+    /// An earlier step failed
+    Skipped,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass(detail) => write!(f, "pass  {detail}"),
+            Outcome::Fail(error) => write!(f, "FAIL  {error}"),
+            Outcome::Skipped => write!(f, "skip"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Step {
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+impl Step {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Pass(_))
+    }
+}
+
+/// Runs every step in a fresh scratch directory, skipping the rest once one fails.
+pub async fn run() -> Vec<Step> {
+    let dir = std::env::temp_dir().join(format!("blitzadex-selftest-{}", std::process::id()));
+    let mut passed = vec![];
+    let result = round_trip(&CDragon::in_dir(&dir), &mut passed).await;
+    fs::remove_dir_all(&dir).ok();
+
+    let mut error = result.err();
+    STEPS
+        .iter()
+        .enumerate()
+        .map(|(i, &name)| {
+            let outcome = match passed.get(i) {
+                Some(detail) => Outcome::Pass(detail.clone()),
+                None => match error.take() {
+                    Some(err) => Outcome::Fail(format!("{err:#}")),
+                    None => Outcome::Skipped,
+                },
+            };
+            Step { name, outcome }
+        })
+        .collect()
+}
+
+/// Pushes a detail onto `passed` for each step that succeeds.
+async fn round_trip(cdrag: &CDragon, passed: &mut Vec<String>) -> Result<()> {
+    let plugins = cdrag.fetch_plugins().await?;
+    passed.push(format!("{} plugins", plugins.len()));
+
+    let champ = cdrag.fetch_champion(CHAMPION_ID).await?;
+    passed.push(champ.name.clone());
+
+    cdrag.cache_obj(&HashMap::from([(champ.id, &champ)]), CacheFile::Champions)?;
+    passed.push(cdrag.cache_dir.display().to_string());
+
+    let cached: HashMap<u64, Champion> = cdrag.load_obj(CacheFile::Champions)?;
+    if cached.get(&champ.id) != Some(&champ) {
+        return Err(eyre!("the cached champion differs from what was written"));
+    }
+    passed.push("matches what was written".to_string());
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! Nearest neighbours over the playstyle and tactical ratings, for finding champions that feel
+//! alike.
+
+use std::collections::HashMap;
+
+use strum::Display;
+
+use crate::cdragon::Champion;
+
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[strum(serialize_all = "lowercase")]
+pub enum Metric {
+    /// Compares the shape of the ratings, ignoring how high they are overall
+    #[default]
+    Cosine,
+    /// Compares the ratings directly, scored as 1 / (1 + distance)
+    Euclidean,
+}
+
+/// A champion's ratings as a vector.
+///
+/// The playstyle ratings and difficulty go from 0 to 3, the tactical style from 0 (all basic
+/// attacks) to 10 (all spells), so the style is scaled down to keep it from dominating.
+pub fn features(champion: &Champion) -> [f64; 7] {
+    let play = &champion.playstyle_info;
+    let tactics = &champion.tactical_info;
+    [
+        play.damage as f64,
+        play.durability as f64,
+        play.crowd_control as f64,
+        play.mobility as f64,
+        play.utility as f64,
+        tactics.difficulty as f64,
+        tactics.style as f64 * 0.3,
+    ]
+}
+
+/// How alike two champions are, where higher is more similar and 1 is identical.
+pub fn score(a: &Champion, b: &Champion, metric: Metric) -> f64 {
+    let (a, b) = (features(a), features(b));
+    match metric {
+        Metric::Cosine => {
+            let dot: f64 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+            let norms = a.iter().map(|x| x * x).sum::<f64>().sqrt()
+                * b.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norms == 0. {
+                0.
+            } else {
+                dot / norms
+            }
+        }
+        Metric::Euclidean => {
+            let distance = a
+                .iter()
+                .zip(&b)
+                .map(|(x, y)| (x - y).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            1. / (1. + distance)
+        }
+    }
+}
+
+/// The `count` champions most similar to `target`, most similar first.
+pub fn most_similar<'a>(
+    target: &Champion,
+    champions: &'a HashMap<u64, Champion>,
+    metric: Metric,
+    count: usize,
+) -> Vec<(&'a Champion, f64)> {
+    let mut scored: Vec<(&Champion, f64)> = champions
+        .values()
+        .filter(|champ| champ.id != target.id)
+        .map(|champ| (champ, score(target, champ, metric)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.name.cmp(&b.0.name)));
+    scored.truncate(count);
+    scored
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cdragon::PlaystyleInfo;
+
+    fn champ(id: u64, name: &str, damage: u64, durability: u64) -> Champion {
+        Champion {
+            id,
+            name: name.to_string(),
+            playstyle_info: PlaystyleInfo {
+                damage,
+                durability,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn nearest_first() {
+        let target = champ(1, "Ahri", 3, 1);
+        let champions: HashMap<u64, Champion> = [
+            champ(1, "Ahri", 3, 1),
+            champ(2, "Syndra", 3, 1),
+            champ(3, "Malphite", 1, 3),
+        ]
+        .into_iter()
+        .map(|champ| (champ.id, champ))
+        .collect();
+        for metric in [Metric::Cosine, Metric::Euclidean] {
+            let similar = most_similar(&target, &champions, metric, 5);
+            let names: Vec<&str> = similar.iter().map(|(c, _)| c.name.as_str()).collect();
+            assert_eq!(names, ["Syndra", "Malphite"]);
+            assert!((similar[0].1 - 1.).abs() < 1e-9);
+        }
+    }
+}
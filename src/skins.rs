@@ -0,0 +1,224 @@
+//! Every skin in the game from skins.json, for tools that care about skins rather than
+//! champions. The skins are the same as the ones in each [`Champion`](crate::cdragon::Champion).
+//!
+//! Skins belong to skin lines, e.g. Lunar Beast, which universes.json groups into universes,
+//! e.g. Lunar Revel.
+
+use std::collections::BTreeMap;
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, parse_json, CDragon, CacheFile, Skin, SkinLine},
+    http,
+};
+
+/// Skin ids to skins.
+pub type Skins = BTreeMap<u64, Skin>;
+
+/// A group of skin lines sharing a theme, e.g. Star Guardian.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Universe {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The ids of its [`SkinLine`]s
+    #[serde(default)]
+    pub skin_sets: Vec<u64>,
+}
+
+impl Universe {
+    /// The universe's skin lines, skipping unknown ids.
+    pub fn skin_lines<'a>(&self, skin_lines: &'a [SkinLine]) -> Vec<&'a SkinLine> {
+        skin_lines
+            .iter()
+            .filter(|line| self.skin_sets.contains(&line.id))
+            .collect()
+    }
+}
+
+/// The skins of a champion, base skin first.
+pub fn of_champion(skins: &Skins, champion_id: u64) -> Vec<&Skin> {
+    // Skin ids are the champion's id times a thousand plus the skin's number
+    skins
+        .range(champion_id * 1000..(champion_id + 1) * 1000)
+        .map(|(_, skin)| skin)
+        .collect()
+}
+
+/// The skins in a skin line, e.g. Star Guardian.
+pub fn in_skin_line(skins: &Skins, skin_line: u64) -> Vec<&Skin> {
+    skins
+        .values()
+        .filter(|skin| skin.skin_lines.contains(&skin_line))
+        .collect()
+}
+
+/// The skins in any of a universe's skin lines.
+pub fn in_universe<'a>(skins: &'a Skins, universe: &Universe) -> Vec<&'a Skin> {
+    skins
+        .values()
+        .filter(|skin| {
+            skin.skin_lines
+                .iter()
+                .any(|line| universe.skin_sets.contains(line))
+        })
+        .collect()
+}
+
+/// The universe a skin line belongs to.
+pub fn universe_of(universes: &[Universe], skin_line: u64) -> Option<&Universe> {
+    universes
+        .iter()
+        .find(|universe| universe.skin_sets.contains(&skin_line))
+}
+
+/// Finds a skin line by its name, or by part of it when that's unambiguous, e.g. "project" for
+/// PROJECT.
+pub fn skin_line_named<'a>(skin_lines: &'a [SkinLine], name: &str) -> Result<&'a SkinLine> {
+    let name = name.to_lowercase();
+    if let Some(exact) = skin_lines
+        .iter()
+        .find(|line| line.name.to_lowercase() == name)
+    {
+        return Ok(exact);
+    }
+    let partial: Vec<&SkinLine> = skin_lines
+        .iter()
+        .filter(|line| line.name.to_lowercase().contains(&name))
+        .collect();
+    match partial[..] {
+        [line] => Ok(line),
+        [] => Err(eyre!("no skin line named {name}")),
+        _ => Err(eyre!(
+            "{name} could be any of {}",
+            partial
+                .iter()
+                .map(|line| line.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+impl CDragon {
+    /// Loads the cached skins, fetching them when they aren't cached yet.
+    pub async fn skins(&self) -> Result<Skins> {
+        if let Ok(skins) = self.load_obj(CacheFile::Skins) {
+            return Ok(skins);
+        }
+        let skins = self.fetch_skins().await?;
+        self.cache_obj(&skins, CacheFile::Skins)?;
+        Ok(skins)
+    }
+
+    /// Loads the cached skin lines, fetching them when they aren't cached yet.
+    pub async fn skin_lines(&self) -> Result<Vec<SkinLine>> {
+        if let Ok(skin_lines) = self.load_obj(CacheFile::SkinLines) {
+            return Ok(skin_lines);
+        }
+        let skin_lines = self.fetch_skin_lines().await?;
+        self.cache_obj(&skin_lines, CacheFile::SkinLines)?;
+        Ok(skin_lines)
+    }
+
+    /// Loads the cached universes, fetching them when they aren't cached yet.
+    pub async fn universes(&self) -> Result<Vec<Universe>> {
+        if let Ok(universes) = self.load_obj(CacheFile::Universes) {
+            return Ok(universes);
+        }
+        let universes = self.fetch_universes().await?;
+        self.cache_obj(&universes, CacheFile::Universes)?;
+        Ok(universes)
+    }
+
+    pub async fn fetch_universes(&self) -> Result<Vec<Universe>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/universes.json"))
+            .await
+            .with_context(|| "couldn't fetch the universes")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    pub async fn fetch_skin_lines(&self) -> Result<Vec<SkinLine>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/skinlines.json"))
+            .await
+            .with_context(|| "couldn't fetch the skin lines")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    pub async fn fetch_skins(&self) -> Result<Skins> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/skins.json"))
+            .await
+            .with_context(|| "couldn't fetch the skins")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cdragon::Rarity;
+
+    #[test]
+    fn skins_catalog() -> Result<()> {
+        let skins: Skins = serde_json::from_str(&std::fs::read_to_string("testing/skins.json")?)?;
+        let lunar = &skins[&1013];
+        assert_eq!(lunar.name, "Lunar Beast Annie");
+        assert_eq!(lunar.champion_id(), 1);
+        assert_eq!(lunar.rarity, Rarity::KEpic);
+        assert_eq!(lunar.chromas.len(), 7);
+        assert!(lunar
+            .tile_path
+            .starts_with("assets/characters/annie/skins/"));
+
+        let annie: Vec<&str> = of_champion(&skins, 1)
+            .iter()
+            .map(|skin| skin.name.as_str())
+            .collect();
+        assert_eq!(annie, ["Annie", "Goth Annie", "Lunar Beast Annie"]);
+        assert!(of_champion(&skins, 2).is_empty());
+        assert_eq!(in_skin_line(&skins, 110)[0].name, "Goth Annie");
+
+        let line = |id, name: &str| SkinLine {
+            id,
+            name: name.to_string(),
+            description: String::new(),
+        };
+        let lines = [
+            line(110, "Gothic"),
+            line(142, "Lunar Beast"),
+            line(18, "PROJECT"),
+            line(93, "Lunar Revel"),
+        ];
+        assert_eq!(skin_line_named(&lines, "project")?.id, 18);
+        assert_eq!(skin_line_named(&lines, "goth")?.id, 110);
+        assert!(skin_line_named(&lines, "lunar").is_err());
+
+        let universes: Vec<Universe> =
+            serde_json::from_str(&std::fs::read_to_string("testing/universes.json")?)?;
+        let lunar_revel = &universes[1];
+        let members: Vec<&str> = lunar_revel
+            .skin_lines(&lines)
+            .iter()
+            .map(|line| line.name.as_str())
+            .collect();
+        assert_eq!(members, ["Lunar Beast", "Lunar Revel"]);
+        let skins_in: Vec<u64> = in_universe(&skins, lunar_revel)
+            .iter()
+            .map(|skin| skin.id)
+            .collect();
+        assert_eq!(skins_in, [1013]);
+        assert_eq!(universe_of(&universes, 110).map(|u| u.id), Some(8));
+        assert!(in_universe(&skins, &universes[2]).is_empty());
+
+        let cached: Skins = serde_json::from_str(&serde_json::to_string(&skins)?)?;
+        assert_eq!(cached, skins);
+        Ok(())
+    }
+}
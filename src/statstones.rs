@@ -0,0 +1,164 @@
+//! Eternals, called statstones in the game data: stats tracked per champion, in series, with
+//! milestones to reach.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+/// The champion an eternal tracks.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoundChampion {
+    /// The champion's id
+    pub item_id: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Statstone {
+    pub name: String,
+    /// The id match and mastery data refer to
+    pub content_id: String,
+    #[serde(default)]
+    pub item_id: u64,
+    /// What's tracked
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub is_epic: bool,
+    #[serde(default)]
+    pub is_retired: bool,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_asset_path")]
+    pub icon_unlocked: String,
+    /// The values to reach, lowest first
+    #[serde(default, deserialize_with = "deserialize_milestones")]
+    pub milestones: Vec<u64>,
+    /// None for the starter series, which tracks every champion
+    #[serde(default)]
+    pub bound_champion: Option<BoundChampion>,
+}
+
+/// Milestones are listed as numbers, or as objects with a threshold.
+fn deserialize_milestones<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let milestones = Option::<Vec<Value>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(milestones
+        .iter()
+        .filter_map(|milestone| {
+            milestone
+                .as_u64()
+                .or_else(|| milestone.get("threshold")?.as_u64())
+        })
+        .collect())
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct StatstoneSeries {
+    pub name: String,
+    pub statstones: Vec<Statstone>,
+}
+
+impl StatstoneSeries {
+    /// Whether the series is about `champion_id` rather than every champion.
+    pub fn is_of_champion(&self, champion_id: u64) -> bool {
+        self.statstones.iter().any(|statstone| {
+            statstone
+                .bound_champion
+                .as_ref()
+                .is_some_and(|bound| bound.item_id == champion_id)
+        })
+    }
+}
+
+/// Finds an eternal by its content id.
+pub fn by_content_id<'a>(series: &'a [StatstoneSeries], content_id: &str) -> Option<&'a Statstone> {
+    series
+        .iter()
+        .flat_map(|series| &series.statstones)
+        .find(|statstone| statstone.content_id.eq_ignore_ascii_case(content_id))
+}
+
+/// The eternals of a champion, without the starter series.
+pub fn of_champion(series: &[StatstoneSeries], champion_id: u64) -> Vec<&Statstone> {
+    series
+        .iter()
+        .flat_map(|series| &series.statstones)
+        .filter(|statstone| {
+            statstone
+                .bound_champion
+                .as_ref()
+                .is_some_and(|bound| bound.item_id == champion_id)
+        })
+        .collect()
+}
+
+/// Parses the statstones file as published, which also holds the packs they're sold in, or as
+/// cached, which holds only the series.
+fn parse(bytes: &[u8]) -> Result<Vec<StatstoneSeries>> {
+    let mut file: Value = parse_json(bytes)?;
+    let series = match file.get_mut("statstoneData") {
+        Some(series) => series.take(),
+        None => file,
+    };
+    serde_json::from_value(series).with_context(|| "unexpected statstones file")
+}
+
+impl CDragon {
+    /// Loads the cached eternals, fetching them when they aren't cached yet.
+    pub async fn statstones(&self) -> Result<Vec<StatstoneSeries>> {
+        if let Ok(series) = self.load_obj(CacheFile::Statstones) {
+            return Ok(series);
+        }
+        let series = self.fetch_statstones().await?;
+        self.cache_obj(&series, CacheFile::Statstones)?;
+        Ok(series)
+    }
+
+    pub async fn fetch_statstones(&self) -> Result<Vec<StatstoneSeries>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/statstones.json"))
+            .await
+            .with_context(|| "couldn't fetch the statstones")?;
+        parse(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn eternals() -> Result<()> {
+        let series = parse(&std::fs::read("testing/statstones.json")?)?;
+        assert_eq!(series.len(), 2);
+        assert!(!series[0].is_of_champion(1));
+        assert!(series[1].is_of_champion(1));
+
+        let annie: Vec<&str> = of_champion(&series, 1)
+            .iter()
+            .map(|statstone| statstone.name.as_str())
+            .collect();
+        assert_eq!(annie, ["Enemies stunned by Pyromania", "Tibbers damage"]);
+        let stuns = by_content_id(&series, "7B8D3E2F-55A1-4A55-9C1E-8A7C8F1E2B10").unwrap();
+        assert_eq!(stuns.milestones, [5, 25]);
+        assert_eq!(
+            stuns.icon_unlocked,
+            "assets/loot/statstones/icons/annie_stuns.png"
+        );
+        assert_eq!(series[0].statstones[0].milestones, [10, 50, 100, 250, 500]);
+        assert!(by_content_id(&series, "nope").is_none());
+
+        let cached = serde_json::to_vec(&series)?;
+        assert_eq!(parse(&cached)?, series);
+        Ok(())
+    }
+}
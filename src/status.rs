@@ -0,0 +1,367 @@
+//! A summary of what's in the cache, like `git status` for the dex: how many entities each
+//! catalog holds, how much room it takes and which version of it is cached, and how fresh each
+//! catalog is compared to CommunityDragon's copy.
+
+use std::{fmt::Write, fs};
+
+use chrono::{DateTime, Utc};
+use color_eyre::{eyre::Context, Result};
+use serde::Serialize;
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+use crate::{
+    cdragon::{CDragon, CacheFile, PluginName, Status},
+    listing::DirEntry,
+    patch::PatchVersion,
+    update::{human_bytes, GAME_DATA_V1},
+};
+
+/// blitzadex only fetches the game data's default locale
+pub const LOCALE: &str = "en_us";
+
+/// The team planner is the one catalog from another plugin than the game data
+const TEAM_PLANNER_DIR: &str = "latest/plugins/rcp-fe-lol-tft-team-planner/global/default";
+
+#[derive(Debug, Serialize)]
+pub struct CatalogStatus {
+    pub catalog: CacheFile,
+    pub entities: usize,
+    pub bytes: u64,
+    /// The version recorded in the catalog's history, see [`crate::history`]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheStatus {
+    /// When the cached game data was published
+    pub game_data: Option<DateTime<Utc>>,
+    /// The patch it's for, e.g. "15.1"
+    pub patch: Option<String>,
+    pub locale: &'static str,
+    pub catalogs: Vec<CatalogStatus>,
+    pub missing: Vec<CacheFile>,
+    /// Files in the asset store
+    pub assets: usize,
+}
+
+/// How many entities a cached catalog holds. Catalogs keyed by set or kind, like the team
+/// planner's units and the banners, count what's in each group.
+fn count_entities(catalog: &Value) -> usize {
+    match catalog {
+        Value::Array(entities) => entities.len(),
+        Value::Object(map) if map.values().all(Value::is_array) => {
+            map.values().filter_map(Value::as_array).map(Vec::len).sum()
+        }
+        Value::Object(map) => map.len(),
+        _ => 0,
+    }
+}
+
+/// How a cached catalog compares to its source upstream.
+#[derive(Debug, Serialize)]
+pub struct CatalogFreshness {
+    pub catalog: CacheFile,
+    /// When the catalog was last written to the cache, `None` when it isn't cached
+    pub cached: Option<DateTime<Utc>>,
+    /// When its source last changed upstream, `None` when the listing doesn't have it
+    pub upstream: Option<DateTime<Utc>>,
+    pub status: Status,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Freshness {
+    /// The status of the game data plugin as a whole, see [`CDragon::status`]
+    pub game_data: Status,
+    pub catalogs: Vec<CatalogFreshness>,
+}
+
+/// Where the source of a catalog is listed upstream: the directory and the entry's name in it.
+/// Catalogs blitzadex puts together itself are dated by what they're made of.
+pub(crate) fn upstream_entry(catalog: CacheFile) -> (&'static str, String) {
+    match catalog {
+        CacheFile::ContentMetadata => ("latest", catalog.to_string()),
+        CacheFile::TftTeamPlanner => (TEAM_PLANNER_DIR, catalog.to_string()),
+        CacheFile::Champions => (GAME_DATA_V1, "champions".to_string()),
+        CacheFile::TftAugments => (GAME_DATA_V1, CacheFile::TftItems.to_string()),
+        _ => (GAME_DATA_V1, catalog.to_string()),
+    }
+}
+
+/// Uninitialized when nothing is cached, out of date when the source changed since it was.
+fn compare(cached: Option<DateTime<Utc>>, upstream: Option<DateTime<Utc>>) -> Status {
+    match (cached, upstream) {
+        (None, _) => Status::Uninitialized,
+        (Some(cached), Some(upstream)) if cached < upstream => Status::OutOfDate,
+        _ => Status::UpToDate,
+    }
+}
+
+fn format_date(date: Option<DateTime<Utc>>) -> String {
+    date.map_or("never".to_string(), |date| {
+        date.format("%Y-%m-%d %H:%M").to_string()
+    })
+}
+
+/// Renders the freshness as a short report, one line per catalog.
+pub fn render_freshness(freshness: &Freshness) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "game data {}", freshness.game_data);
+    let _ = writeln!(
+        out,
+        "  {:<32}{:<18}{:<18}status",
+        "catalog", "cached", "upstream"
+    );
+    for catalog in &freshness.catalogs {
+        let upstream = match catalog.upstream {
+            Some(_) => format_date(catalog.upstream),
+            None => "unknown".to_string(),
+        };
+        let _ = writeln!(
+            out,
+            "  {:<32}{:<18}{upstream:<18}{}",
+            catalog.catalog.to_string(),
+            format_date(catalog.cached),
+            catalog.status
+        );
+    }
+    let stale = freshness
+        .catalogs
+        .iter()
+        .filter(|catalog| catalog.status == Status::OutOfDate)
+        .count();
+    if stale > 0 {
+        let _ = writeln!(out, "{stale} out of date, run `blitzadex update`");
+    }
+    out
+}
+
+/// Renders the status as a short report, one line per catalog.
+pub fn render(status: &CacheStatus) -> String {
+    let mut out = String::new();
+    let published = format_date(status.game_data);
+    let patch = status
+        .patch
+        .as_ref()
+        .map(|patch| format!(" for patch {patch}"))
+        .unwrap_or_default();
+    let _ = writeln!(
+        out,
+        "game data published {published}{patch}, locale {}",
+        status.locale
+    );
+    for catalog in &status.catalogs {
+        let _ = writeln!(
+            out,
+            "  {:<32}{:>7} entries {:>9}  {}",
+            catalog.catalog.to_string(),
+            catalog.entities,
+            human_bytes(catalog.bytes),
+            catalog.version.as_deref().unwrap_or_default()
+        );
+    }
+    let total: u64 = status.catalogs.iter().map(|catalog| catalog.bytes).sum();
+    let _ = writeln!(out, "{} cached in total", human_bytes(total));
+    if !status.missing.is_empty() {
+        let missing: Vec<String> = status.missing.iter().map(ToString::to_string).collect();
+        let _ = writeln!(out, "not cached: {}", missing.join(", "));
+    }
+    let _ = writeln!(out, "{} assets downloaded", status.assets);
+    out
+}
+
+impl CDragon {
+    /// Takes stock of the cache without fetching anything.
+    pub async fn cache_status(&self) -> Result<CacheStatus> {
+        let mut catalogs = vec![];
+        let mut missing = vec![];
+        for catalog in CacheFile::iter() {
+            let path = self.cache_dir.join(catalog.to_string());
+            let (Ok(metadata), Ok(value)) = (fs::metadata(&path), self.load_obj::<Value>(catalog))
+            else {
+                missing.push(catalog);
+                continue;
+            };
+            // The content metadata is a single record about the rest
+            let entities = match catalog {
+                CacheFile::ContentMetadata => 1,
+                _ => count_entities(&value),
+            };
+            catalogs.push(CatalogStatus {
+                catalog,
+                entities,
+                bytes: metadata.len(),
+                version: self.history(catalog)?.current,
+            });
+        }
+        Ok(CacheStatus {
+            game_data: self
+                .cached_plugin_updated_date(&PluginName::RcpBeLolGameData)
+                .await,
+            patch: self
+                .load_obj::<PatchVersion>(CacheFile::ContentMetadata)
+                .ok()
+                .map(|patch| patch.patch().to_string()),
+            locale: LOCALE,
+            catalogs,
+            missing,
+            assets: self.asset_store()?.entries().count(),
+        })
+    }
+
+    /// When `catalog` was last cached, from its file's modification time.
+    fn cached_at(&self, catalog: CacheFile) -> Option<DateTime<Utc>> {
+        let path = self.cache_dir.join(catalog.to_string());
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(DateTime::from)
+    }
+
+    /// Compares every catalog in the cache to its source upstream. Only directory listings are
+    /// fetched, one for each directory the sources are in.
+    pub async fn freshness(&self) -> Result<Freshness> {
+        let game_data = self.status(PluginName::RcpBeLolGameData).await?;
+        let plugins = self.fetch_plugins().await?;
+        let mut listings: Vec<(&str, Vec<DirEntry>)> = vec![];
+        for dir in ["latest", TEAM_PLANNER_DIR, GAME_DATA_V1] {
+            let entries = self
+                .list_dir(dir)
+                .await
+                .with_context(|| format!("couldn't check {dir} for updates"))?;
+            listings.push((dir, entries));
+        }
+
+        let catalogs = CacheFile::iter()
+            .map(|catalog| {
+                let upstream = match catalog {
+                    // The listing changes whenever any plugin does
+                    CacheFile::Plugins => plugins.iter().map(|plugin| plugin.mtime).max(),
+                    _ => {
+                        let (dir, name) = upstream_entry(catalog);
+                        listings
+                            .iter()
+                            .filter(|(listed, _)| *listed == dir)
+                            .flat_map(|(_, entries)| entries)
+                            .find(|entry| entry.name == name)
+                            .map(|entry| entry.mtime)
+                    }
+                };
+                let cached = self.cached_at(catalog);
+                CatalogFreshness {
+                    catalog,
+                    cached,
+                    upstream,
+                    status: compare(cached, upstream),
+                }
+            })
+            .collect();
+        Ok(Freshness {
+            game_data,
+            catalogs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn counts() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        let items: Value = serde_json::from_str(&fs::read_to_string("testing/items.json")?)?;
+        let banners: Value =
+            serde_json::from_str(&fs::read_to_string("testing/summoner-banners.json")?)?;
+        cdrag.cache_versioned(&items, CacheFile::Items, "14.23")?;
+        cdrag.cache_obj(&banners, CacheFile::SummonerBanners)?;
+
+        let status = cdrag.cache_status().await?;
+        assert_eq!(status.game_data, None);
+        let cached: Vec<(CacheFile, usize)> = status
+            .catalogs
+            .iter()
+            .map(|catalog| (catalog.catalog, catalog.entities))
+            .collect();
+        let banner_count = ["BannerFlags", "BannerFrames"]
+            .iter()
+            .filter_map(|key| banners[key].as_array())
+            .map(Vec::len)
+            .sum();
+        assert_eq!(
+            cached,
+            [
+                (CacheFile::SummonerBanners, banner_count),
+                (CacheFile::Items, items.as_array().unwrap().len())
+            ]
+        );
+        let items_status = &status.catalogs[1];
+        assert_eq!(items_status.version.as_deref(), Some("14.23"));
+        assert!(status.missing.contains(&CacheFile::Champions));
+
+        let report = render(&status);
+        assert!(report.starts_with("game data published never, locale en_us\n"));
+        assert!(report.contains("not cached: plugins.json, champions.json"));
+
+        let patch = PatchVersion {
+            version: "15.1.6463155+branch.releases-15-1.code.public.content.release".to_string(),
+            released: None,
+        };
+        cdrag.cache_obj(&patch, CacheFile::ContentMetadata)?;
+        let status = cdrag.cache_status().await?;
+        assert_eq!(status.patch.as_deref(), Some("15.1"));
+        assert_eq!(status.catalogs[0].entities, 1);
+        assert!(render(&status).starts_with("game data published never for patch 15.1,"));
+        Ok(())
+    }
+
+    #[test]
+    fn freshness() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        assert_eq!(cdrag.cached_at(CacheFile::Items), None);
+        cdrag.cache_obj(&Vec::<Value>::new(), CacheFile::Items)?;
+        let cached = cdrag.cached_at(CacheFile::Items);
+        assert!(cached.is_some());
+
+        let earlier = cached.map(|date| date - chrono::Duration::days(1));
+        let later = cached.map(|date| date + chrono::Duration::days(1));
+        assert_eq!(compare(None, later), Status::Uninitialized);
+        assert_eq!(compare(cached, earlier), Status::UpToDate);
+        assert_eq!(compare(cached, None), Status::UpToDate);
+        assert_eq!(compare(cached, later), Status::OutOfDate);
+        assert_eq!(
+            upstream_entry(CacheFile::TftAugments),
+            (GAME_DATA_V1, "tftitems.json".to_string())
+        );
+
+        let freshness = Freshness {
+            game_data: Status::OutOfDate,
+            catalogs: vec![
+                CatalogFreshness {
+                    catalog: CacheFile::Items,
+                    cached,
+                    upstream: later,
+                    status: Status::OutOfDate,
+                },
+                CatalogFreshness {
+                    catalog: CacheFile::Perks,
+                    cached: None,
+                    upstream: None,
+                    status: Status::Uninitialized,
+                },
+            ],
+        };
+        let report = render_freshness(&freshness);
+        assert!(report.starts_with("game data OutOfDate\n"));
+        assert!(report.contains(
+            "perks.json                      never             unknown           Uninitialized"
+        ));
+        assert!(report.ends_with("1 out of date, run `blitzadex update`\n"));
+        let json = serde_json::to_value(&freshness)?;
+        assert_eq!(json["catalogs"][0]["catalog"], "items.json");
+        assert_eq!(json["catalogs"][1]["status"], "Uninitialized");
+        Ok(())
+    }
+}
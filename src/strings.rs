@@ -0,0 +1,108 @@
+//! The client's UI strings, which each front-end plugin ships per locale as a flat trans.json of
+//! keys to strings, e.g. the loading screen tips in rcp-fe-lol-loading-screen.
+
+use std::{collections::BTreeMap, fs};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde_json::Value;
+
+use crate::{
+    cdragon::{parse_json, CDragon},
+    http,
+};
+
+const PLUGINS_URL: &str = "https://raw.communitydragon.org/latest/plugins";
+/// Under the cache directory, one file per plugin and locale
+const STRINGS: &str = "strings";
+
+/// String keys to strings in one locale.
+pub type Strings = BTreeMap<String, String>;
+
+/// The directory of `locale` in a plugin, e.g. "ko_kr". The client's own locale, American
+/// English, is the plugin's default.
+pub(crate) fn locale_dir(locale: &str) -> Result<String> {
+    let locale = locale.to_lowercase().replace('-', "_");
+    if !locale.chars().all(|c| c.is_ascii_alphabetic() || c == '_') {
+        return Err(eyre!(
+            "{locale} isn't a locale, expected one like en_us or ko_kr"
+        ));
+    }
+    Ok(match locale.as_str() {
+        "en_us" => "default".to_string(),
+        _ => locale,
+    })
+}
+
+/// Keeps the string values, some bundles also hold a few numbers and flags.
+fn strings_of(bundle: BTreeMap<String, Value>) -> Strings {
+    bundle
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            Value::String(string) => Some((key, string)),
+            _ => None,
+        })
+        .collect()
+}
+
+impl CDragon {
+    /// The UI strings of `plugin`, e.g. "rcp-fe-lol-loading-screen", in `locale`, e.g. "en_us"
+    /// or "ko_kr". Fetched the first time and cached per plugin and locale after that.
+    pub async fn strings(&self, plugin: &str, locale: &str) -> Result<Strings> {
+        if !plugin
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            return Err(eyre!("{plugin} isn't a plugin name"));
+        }
+        let locale = locale_dir(locale)?;
+        let cached = self
+            .cache_dir
+            .join(STRINGS)
+            .join(format!("{plugin}.{locale}.json"));
+        if let Ok(bytes) = fs::read(&cached) {
+            return Ok(parse_json(&bytes)?);
+        }
+        let url = format!("{PLUGINS_URL}/{plugin}/global/{locale}/trans.json");
+        let bytes = http::get(&self.http_client, &url)
+            .await
+            .with_context(|| format!("couldn't fetch the {locale} strings of {plugin}"))?;
+        let strings = strings_of(parse_json(&bytes)?);
+        fs::create_dir_all(self.cache_dir.join(STRINGS))?;
+        fs::write(cached, serde_json::to_string(&strings)?)?;
+        Ok(strings)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn cached_strings() -> Result<()> {
+        assert_eq!(locale_dir("en_US")?, "default");
+        assert_eq!(locale_dir("ko-KR")?, "ko_kr");
+        assert!(locale_dir("../../etc").is_err());
+
+        let bundle: BTreeMap<String, Value> = serde_json::from_str(
+            r#"{"tip_1": "Wards reveal the map.", "tip_count": 2, "tip_2": "Press Tab."}"#,
+        )?;
+        let strings = strings_of(bundle);
+        assert_eq!(strings.len(), 2);
+
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        let cached = dir.path().join("cache").join(STRINGS);
+        fs::create_dir_all(&cached)?;
+        fs::write(
+            cached.join("rcp-fe-lol-loading-screen.ko_kr.json"),
+            serde_json::to_string(&strings)?,
+        )?;
+        let loaded = cdrag.strings("rcp-fe-lol-loading-screen", "ko_KR").await?;
+        assert_eq!(loaded["tip_1"], "Wards reveal the map.");
+        assert!(cdrag.strings("../secrets", "ko_kr").await.is_err());
+        Ok(())
+    }
+}
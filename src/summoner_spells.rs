@@ -0,0 +1,75 @@
+//! Summoner spells, the two spells picked alongside a champion like Flash or Ignite.
+
+use color_eyre::{eyre::Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_asset_path, parse_json, CDragon, CacheFile},
+    http,
+};
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpell {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    /// In seconds
+    pub cooldown: f64,
+    /// The game modes the spell can be picked in, e.g. "CLASSIC" or "ARAM"
+    pub game_modes: Vec<String>,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub icon_path: String,
+}
+
+impl SummonerSpell {
+    pub fn available_in(&self, game_mode: &str) -> bool {
+        self.game_modes
+            .iter()
+            .any(|mode| mode.eq_ignore_ascii_case(game_mode))
+    }
+}
+
+/// Finds a summoner spell by the id match histories and the client refer to it by.
+pub fn by_id(spells: &[SummonerSpell], id: u64) -> Option<&SummonerSpell> {
+    spells.iter().find(|spell| spell.id == id)
+}
+
+impl CDragon {
+    /// Loads the cached summoner spells, fetching them when they aren't cached yet.
+    pub async fn summoner_spells(&self) -> Result<Vec<SummonerSpell>> {
+        if let Ok(spells) = self.load_obj(CacheFile::SummonerSpells) {
+            return Ok(spells);
+        }
+        let spells = self.fetch_summoner_spells().await?;
+        self.cache_obj(&spells, CacheFile::SummonerSpells)?;
+        Ok(spells)
+    }
+
+    pub async fn fetch_summoner_spells(&self) -> Result<Vec<SummonerSpell>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/summoner-spells.json"))
+            .await
+            .with_context(|| "couldn't fetch the summoner spells")?;
+        Ok(parse_json(&bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_ids() -> Result<()> {
+        let spells: Vec<SummonerSpell> =
+            serde_json::from_str(&std::fs::read_to_string("testing/summoner-spells.json")?)?;
+        let flash = by_id(&spells, 4).unwrap();
+        assert_eq!(flash.name, "Flash");
+        assert_eq!(flash.cooldown, 300.);
+        assert_eq!(flash.icon_path, "data/spells/icons2d/summoner_flash.png");
+        let mark = by_id(&spells, 32).unwrap();
+        assert!(mark.available_in("aram") && !mark.available_in("CLASSIC"));
+        assert!(by_id(&spells, 999).is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,560 @@
+//! Teamfight Tactics data, including the cosmetics: Little Legends, called companions in the
+//! game data, and arena skins.
+//!
+//! ## Team planner codes
+//! The client shares team plans as text codes like `02001019000...000TFTSet13`: the format
+//! version `02`, then one slot per unit as three hex digits holding the unit's
+//! `team_planner_code` (`000` for an empty slot), padded to [`PLANNER_SLOTS`] slots, then the set
+//! the units are from. The codes of each set's units come from the team planner plugin.
+
+use std::collections::BTreeMap;
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cdragon::{asset_url, deserialize_optional_asset_path, parse_json, CDragon, CacheFile},
+    http,
+    lock::CacheLock,
+};
+
+const TEAM_PLANNER_URL: &str = "https://raw.communitydragon.org/latest/plugins/rcp-fe-lol-tft-team-planner/global/default/tftchampions-teamplanner.json";
+const PLANNER_VERSION: &str = "02";
+pub const PLANNER_SLOTS: usize = 10;
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TeamPlannerUnit {
+    /// e.g. "TFT13_Jinx"
+    pub character_id: String,
+    pub display_name: String,
+    pub team_planner_code: u32,
+}
+
+/// The units of every set, keyed by set name, e.g. "TFTSet13".
+pub type TeamPlannerSets = BTreeMap<String, Vec<TeamPlannerUnit>>;
+
+/// A decoded team planner code.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TeamPlan {
+    pub set: String,
+    /// The planner codes of the units, without the empty slots
+    pub codes: Vec<u32>,
+}
+
+impl TeamPlan {
+    pub fn decode(code: &str) -> Result<Self> {
+        let code = code.trim();
+        let rest = code
+            .strip_prefix(PLANNER_VERSION)
+            .ok_or_else(|| eyre!("not a team planner code, or one from another version"))?;
+        let set_start = rest
+            .find("TFT")
+            .ok_or_else(|| eyre!("the code doesn't say which set it's for"))?;
+        let (slots, set) = rest.split_at(set_start);
+        if slots.len() % 3 != 0 || !slots.is_ascii() {
+            return Err(eyre!("the code's unit slots are malformed"));
+        }
+        let codes = (0..slots.len())
+            .step_by(3)
+            .map(|i| u32::from_str_radix(&slots[i..i + 3], 16))
+            .filter(|slot| *slot != Ok(0))
+            .collect::<Result<Vec<u32>, _>>()
+            .with_context(|| "the code's unit slots aren't hex")?;
+        Ok(Self {
+            set: set.to_string(),
+            codes,
+        })
+    }
+
+    pub fn encode(&self) -> Result<String> {
+        if self.codes.len() > PLANNER_SLOTS {
+            return Err(eyre!("a team plan holds at most {PLANNER_SLOTS} units"));
+        }
+        let mut out = PLANNER_VERSION.to_string();
+        for code in &self.codes {
+            if *code == 0 || *code > 0xfff {
+                return Err(eyre!("{code} isn't a valid planner code"));
+            }
+            out.push_str(&format!("{code:03x}"));
+        }
+        out.push_str(&"000".repeat(PLANNER_SLOTS - self.codes.len()));
+        out.push_str(&self.set);
+        Ok(out)
+    }
+
+    /// Looks the units of the plan up in its set.
+    pub fn units<'a>(&self, sets: &'a TeamPlannerSets) -> Result<Vec<&'a TeamPlannerUnit>> {
+        let units = sets
+            .get(&self.set)
+            .ok_or_else(|| eyre!("no team planner data for {}", self.set))?;
+        self.codes
+            .iter()
+            .map(|code| {
+                units
+                    .iter()
+                    .find(|unit| unit.team_planner_code == *code)
+                    .ok_or_else(|| eyre!("no unit in {} has planner code {code}", self.set))
+            })
+            .collect()
+    }
+
+    /// Builds a plan from unit names or character ids, e.g. "Jinx" or "TFT13_Jinx".
+    pub fn from_units(sets: &TeamPlannerSets, set: &str, names: &[String]) -> Result<Self> {
+        let units = sets
+            .get(set)
+            .ok_or_else(|| eyre!("no team planner data for {set}"))?;
+        let codes = names
+            .iter()
+            .map(|name| {
+                units
+                    .iter()
+                    .find(|unit| {
+                        unit.display_name.eq_ignore_ascii_case(name)
+                            || unit.character_id.eq_ignore_ascii_case(name)
+                    })
+                    .map(|unit| unit.team_planner_code)
+                    .ok_or_else(|| eyre!("no unit named {name} in {set}"))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            set: set.to_string(),
+            codes,
+        })
+    }
+}
+
+/// The latest set in the team planner data, by set number.
+pub fn latest_set(sets: &TeamPlannerSets) -> Option<&str> {
+    let number = |set: &str| {
+        set.trim_start_matches("TFTSet")
+            .parse::<f64>()
+            .unwrap_or(f64::MIN)
+    };
+    sets.keys()
+        .max_by(|a, b| number(a).total_cmp(&number(b)))
+        .map(String::as_str)
+}
+
+/// A unit, from the `character_record` of tftchampions.json.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TftChampion {
+    /// e.g. "TFT13_Jinx"
+    pub character_id: String,
+    pub display_name: String,
+    /// One less than the unit's cost
+    pub rarity: u32,
+    #[serde(default)]
+    pub traits: Vec<TftTraitRef>,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(
+        default,
+        rename = "squareIconPath",
+        deserialize_with = "deserialize_optional_asset_path"
+    )]
+    pub square_icon_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TftTraitRef {
+    pub name: String,
+    /// The [`TftTrait::trait_id`]
+    pub id: String,
+}
+
+impl TftChampion {
+    /// The gold the unit costs in the shop.
+    pub fn cost(&self) -> u32 {
+        self.rarity + 1
+    }
+
+    /// The set the unit is from, by its character id, e.g. 13 for "TFT13_Jinx".
+    pub fn set_number(&self) -> Option<u32> {
+        self.character_id
+            .strip_prefix("TFT")?
+            .split('_')
+            .next()?
+            .parse()
+            .ok()
+    }
+}
+
+#[derive(Deserialize)]
+struct TftChampionEntry {
+    character_record: TftChampion,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct TftTrait {
+    /// e.g. "TFT13_Rebel"
+    pub trait_id: String,
+    pub display_name: String,
+    /// e.g. "TFTSet13"
+    pub set: String,
+    #[serde(default)]
+    pub tooltip_text: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub icon_path: Option<String>,
+    #[serde(default)]
+    pub conditional_trait_sets: Vec<TraitBreakpoint>,
+}
+
+/// How many units of a trait activate one of its tiers.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TraitBreakpoint {
+    pub min_units: u32,
+    /// None for the last tier
+    #[serde(default)]
+    pub max_units: Option<u32>,
+    /// e.g. "kBronze" or "kGold"
+    #[serde(default)]
+    pub style_name: String,
+}
+
+impl TftTrait {
+    /// The unit counts at which the trait's tiers start.
+    pub fn breakpoints(&self) -> Vec<u32> {
+        self.conditional_trait_sets
+            .iter()
+            .map(|tier| tier.min_units)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TftItem {
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub name: String,
+    /// e.g. "TFT_Item_InfinityEdge"
+    pub name_id: String,
+    /// The `name_id`s of the components a completed item is built from
+    #[serde(default)]
+    pub composition: Vec<String>,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub square_icon_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TftAugment {
+    pub name: String,
+    /// e.g. "TFT9_Augment_CyberneticImplants1"
+    pub name_id: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub square_icon_path: Option<String>,
+}
+
+/// A Little Legend at one star level. Each species, e.g. Silverwing, comes at up to three.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Companion {
+    pub content_id: String,
+    pub item_id: u64,
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub loadouts_icon: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// The star level, from 1
+    pub level: u32,
+    pub species_name: String,
+    pub species_id: u64,
+    /// e.g. "Epic"
+    #[serde(default)]
+    pub rarity: String,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// An arena skin, the board a TFT match is played on.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TftMapSkin {
+    pub content_id: String,
+    pub item_id: u64,
+    pub name: String,
+    /// [Normalized Path](crate::cdragon::Skin)
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub loadouts_icon: Option<String>,
+    #[serde(default)]
+    pub rarity: String,
+    /// e.g. "Event Arenas"
+    #[serde(default)]
+    pub group_name: String,
+}
+
+/// The companions grouped by species id, each species by star level.
+pub fn species(companions: &[Companion]) -> BTreeMap<u64, Vec<&Companion>> {
+    let mut species: BTreeMap<u64, Vec<&Companion>> = BTreeMap::new();
+    for companion in companions {
+        species
+            .entry(companion.species_id)
+            .or_default()
+            .push(companion);
+    }
+    for levels in species.values_mut() {
+        levels.sort_by_key(|companion| companion.level);
+    }
+    species
+}
+
+fn is_augment(name_id: &str) -> bool {
+    name_id.contains("_Augment_")
+}
+
+/// Splits tftitems.json into the items and the augments.
+fn split_items(entries: Vec<serde_json::Value>) -> Result<(Vec<TftItem>, Vec<TftAugment>)> {
+    let (augments, items): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| {
+        entry
+            .get("nameId")
+            .and_then(serde_json::Value::as_str)
+            .is_some_and(is_augment)
+    });
+    Ok((
+        serde_json::from_value(items.into())?,
+        serde_json::from_value(augments.into())?,
+    ))
+}
+
+impl CDragon {
+    /// Fetches the TFT champions, traits, items, augments, companions and arena skins into the
+    /// cache. They aren't part of
+    /// [`CDragon::update`], only those who use them pay for fetching them.
+    pub async fn update_tft(&self) -> Result<()> {
+        let _lock = CacheLock::acquire(&self.cache_dir, self.wait_for_lock).await?;
+        let version = self.data_version().await?;
+
+        let champions = self
+            .fetch_tft_champions()
+            .await
+            .with_context(|| "failed to update TFT champions")?;
+        self.cache_versioned(&champions, CacheFile::TftChampions, &version)
+            .with_context(|| "failed to cache the updated TFT champions")?;
+
+        let traits = self
+            .fetch_tft_traits()
+            .await
+            .with_context(|| "failed to update TFT traits")?;
+        self.cache_versioned(&traits, CacheFile::TftTraits, &version)
+            .with_context(|| "failed to cache the updated TFT traits")?;
+
+        let (items, augments) = self
+            .fetch_tft_items()
+            .await
+            .with_context(|| "failed to update TFT items")?;
+        self.cache_versioned(&items, CacheFile::TftItems, &version)
+            .with_context(|| "failed to cache the updated TFT items")?;
+        self.cache_versioned(&augments, CacheFile::TftAugments, &version)
+            .with_context(|| "failed to cache the updated TFT augments")?;
+
+        let companions = self
+            .fetch_tft_companions()
+            .await
+            .with_context(|| "failed to update TFT companions")?;
+        self.cache_versioned(&companions, CacheFile::TftCompanions, &version)
+            .with_context(|| "failed to cache the updated TFT companions")?;
+
+        let map_skins = self
+            .fetch_tft_map_skins()
+            .await
+            .with_context(|| "failed to update TFT arena skins")?;
+        self.cache_versioned(&map_skins, CacheFile::TftMapSkins, &version)
+            .with_context(|| "failed to cache the updated TFT arena skins")?;
+        Ok(())
+    }
+
+    /// Loads the cached TFT champions, see [`CDragon::update_tft`].
+    pub fn tft_champions(&self) -> Result<Vec<TftChampion>> {
+        self.load_obj(CacheFile::TftChampions)
+            .with_context(|| "no TFT champions cached, run `blitzadex update --tft` first")
+    }
+
+    /// Loads the cached TFT traits, see [`CDragon::update_tft`].
+    pub fn tft_traits(&self) -> Result<Vec<TftTrait>> {
+        self.load_obj(CacheFile::TftTraits)
+            .with_context(|| "no TFT traits cached, run `blitzadex update --tft` first")
+    }
+
+    /// Loads the cached TFT items, see [`CDragon::update_tft`].
+    pub fn tft_items(&self) -> Result<Vec<TftItem>> {
+        self.load_obj(CacheFile::TftItems)
+            .with_context(|| "no TFT items cached, run `blitzadex update --tft` first")
+    }
+
+    /// Loads the cached TFT augments, see [`CDragon::update_tft`].
+    pub fn tft_augments(&self) -> Result<Vec<TftAugment>> {
+        self.load_obj(CacheFile::TftAugments)
+            .with_context(|| "no TFT augments cached, run `blitzadex update --tft` first")
+    }
+
+    /// Loads the cached Little Legends, see [`CDragon::update_tft`].
+    pub fn tft_companions(&self) -> Result<Vec<Companion>> {
+        self.load_obj(CacheFile::TftCompanions)
+            .with_context(|| "no TFT companions cached, run `blitzadex update --tft` first")
+    }
+
+    /// Loads the cached arena skins, see [`CDragon::update_tft`].
+    pub fn tft_map_skins(&self) -> Result<Vec<TftMapSkin>> {
+        self.load_obj(CacheFile::TftMapSkins)
+            .with_context(|| "no TFT arena skins cached, run `blitzadex update --tft` first")
+    }
+
+    pub async fn fetch_tft_companions(&self) -> Result<Vec<Companion>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/companions.json"))
+            .await
+            .with_context(|| "couldn't fetch the TFT companions")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    pub async fn fetch_tft_map_skins(&self) -> Result<Vec<TftMapSkin>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/tftmapskins.json"))
+            .await
+            .with_context(|| "couldn't fetch the TFT arena skins")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    pub async fn fetch_tft_champions(&self) -> Result<Vec<TftChampion>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/tftchampions.json"))
+            .await
+            .with_context(|| "couldn't fetch the TFT champions")?;
+        let entries: Vec<TftChampionEntry> = parse_json(&bytes)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| entry.character_record)
+            .collect())
+    }
+
+    pub async fn fetch_tft_traits(&self) -> Result<Vec<TftTrait>> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/tfttraits.json"))
+            .await
+            .with_context(|| "couldn't fetch the TFT traits")?;
+        Ok(parse_json(&bytes)?)
+    }
+
+    /// The items and the augments.
+    pub async fn fetch_tft_items(&self) -> Result<(Vec<TftItem>, Vec<TftAugment>)> {
+        let bytes = http::get(&self.http_client, &asset_url("v1/tftitems.json"))
+            .await
+            .with_context(|| "couldn't fetch the TFT items")?;
+        split_items(parse_json(&bytes)?)
+    }
+
+    /// Loads the cached team planner data, fetching it when it isn't cached yet.
+    pub async fn team_planner_sets(&self) -> Result<TeamPlannerSets> {
+        if let Ok(sets) = self.load_obj(CacheFile::TftTeamPlanner) {
+            return Ok(sets);
+        }
+        let bytes = http::get(&self.http_client, TEAM_PLANNER_URL)
+            .await
+            .with_context(|| "couldn't fetch the team planner data")?;
+        let sets: TeamPlannerSets = parse_json(&bytes)?;
+        self.cache_obj(&sets, CacheFile::TftTeamPlanner)?;
+        Ok(sets)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sets() -> TeamPlannerSets {
+        let unit = |id: &str, name: &str, code| TeamPlannerUnit {
+            character_id: id.to_string(),
+            display_name: name.to_string(),
+            team_planner_code: code,
+        };
+        BTreeMap::from([(
+            "TFTSet13".to_string(),
+            vec![
+                unit("TFT13_Jinx", "Jinx", 0x01a),
+                unit("TFT13_Vi", "Vi", 0x02b),
+            ],
+        )])
+    }
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let sets = sets();
+        let plan = TeamPlan::from_units(&sets, "TFTSet13", &["jinx".into(), "TFT13_Vi".into()])?;
+        let code = plan.encode()?;
+        assert_eq!(code, format!("0201a02b{}TFTSet13", "000".repeat(8)));
+        let decoded = TeamPlan::decode(&code)?;
+        assert_eq!(decoded, plan);
+        let names: Vec<&str> = decoded
+            .units(&sets)?
+            .iter()
+            .map(|unit| unit.display_name.as_str())
+            .collect();
+        assert_eq!(names, ["Jinx", "Vi"]);
+        assert!(TeamPlan::decode("01abcTFTSet13").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn catalogs() -> Result<()> {
+        let entries: Vec<TftChampionEntry> =
+            serde_json::from_str(&std::fs::read_to_string("testing/tftchampions.json")?)?;
+        let jinx = &entries[0].character_record;
+        assert_eq!(jinx.display_name, "Jinx");
+        assert_eq!((jinx.cost(), jinx.set_number()), (5, Some(13)));
+        assert_eq!(jinx.traits[0].id, "TFT13_Rebel");
+        assert_eq!(
+            jinx.square_icon_path.as_deref(),
+            Some("assets/characters/tft13_jinx/hud/tft13_jinx_square.tft_set13.png")
+        );
+
+        let traits: Vec<TftTrait> =
+            serde_json::from_str(&std::fs::read_to_string("testing/tfttraits.json")?)?;
+        assert_eq!(traits[0].breakpoints(), [3, 5, 7, 10]);
+
+        let (items, augments) = split_items(serde_json::from_str(&std::fs::read_to_string(
+            "testing/tftitems.json",
+        )?)?)?;
+        let names: Vec<&str> = items.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, ["B.F. Sword", "Infinity Edge"]);
+        assert_eq!(
+            items[1].composition,
+            ["TFT_Item_BFSword", "TFT_Item_SparringGloves"]
+        );
+        assert_eq!(augments[0].name, "Cybernetic Implants I");
+        Ok(())
+    }
+
+    #[test]
+    fn latest() {
+        let mut sets = sets();
+        sets.insert("TFTSet9".to_string(), vec![]);
+        sets.insert("TFTSet9.5".to_string(), vec![]);
+        assert_eq!(latest_set(&sets), Some("TFTSet13"));
+    }
+
+    #[test]
+    fn cosmetics() -> Result<()> {
+        let companions: Vec<Companion> =
+            serde_json::from_str(&std::fs::read_to_string("testing/companions.json")?)?;
+        let species = species(&companions);
+        let silverwing: Vec<(&str, u32)> = species[&11]
+            .iter()
+            .map(|companion| (companion.name.as_str(), companion.level))
+            .collect();
+        assert_eq!(silverwing, [("Silverwing", 1), ("Elite Silverwing", 3)]);
+        assert!(species[&1][0].is_default);
+        assert_eq!(
+            companions[0].loadouts_icon.as_deref(),
+            Some("assets/loadouts/companions/tooltip_featherknight_base_tier1.png")
+        );
+
+        let map_skins: Vec<TftMapSkin> =
+            serde_json::from_str(&std::fs::read_to_string("testing/tftmapskins.json")?)?;
+        assert_eq!(map_skins[1].group_name, "Event Arenas");
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Smaller or re-encoded copies of downloaded art, since raw splashes are far larger than most
+//! interfaces show them. Copies are kept in the [`AssetStore`](crate::assets::AssetStore) next to
+//! the originals, under `derived/<width>/`, and made once.
+
+use std::io::Cursor;
+
+use color_eyre::{eyre::Context, Result};
+use image::{imageops::FilterType, DynamicImage};
+use strum::Display;
+
+use crate::cdragon::CDragon;
+
+/// Under the asset store, the copies by width
+const DERIVED: &str = "derived";
+
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[strum(serialize_all = "lowercase")]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    /// Smaller, without transparency
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+        }
+    }
+}
+
+/// A copy of an asset at most `width` pixels wide, in `format`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Derivative {
+    /// None keeps the original size
+    pub width: Option<u32>,
+    pub format: ImageFormat,
+}
+
+impl Derivative {
+    /// Where the copy of the asset at `asset_path` goes in the asset store.
+    pub fn store_path(&self, asset_path: &str) -> String {
+        let stem = asset_path
+            .rsplit_once('.')
+            .map_or(asset_path, |(stem, _)| stem);
+        let size = self
+            .width
+            .map_or("full".to_string(), |width| width.to_string());
+        format!("{DERIVED}/{size}/{stem}.{}", self.format.extension())
+    }
+}
+
+/// Scales the image in `bytes` down to the derivative's width, keeping its aspect ratio, and
+/// encodes it in the derivative's format. Images already narrow enough keep their size.
+pub fn transcode(bytes: &[u8], derivative: &Derivative) -> Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes).with_context(|| "couldn't decode the image")?;
+    let image = match derivative.width {
+        Some(width) if width < image.width() => {
+            let height = (image.height() as u64 * width as u64 / image.width() as u64).max(1);
+            image.resize_exact(width, height as u32, FilterType::Lanczos3)
+        }
+        _ => image,
+    };
+    let mut out = Cursor::new(vec![]);
+    match derivative.format {
+        ImageFormat::Png => image.write_to(&mut out, image::ImageFormat::Png)?,
+        ImageFormat::Jpeg => {
+            DynamicImage::from(image.to_rgb8()).write_to(&mut out, image::ImageFormat::Jpeg)?
+        }
+    }
+    Ok(out.into_inner())
+}
+
+impl CDragon {
+    /// The bytes of a [`Derivative`] of the asset at `asset_path`, made from the
+    /// [stored asset](CDragon::stored_asset) the first time.
+    pub async fn derived_asset(
+        &self,
+        asset_path: &str,
+        derivative: &Derivative,
+    ) -> Result<Vec<u8>> {
+        let path = derivative.store_path(asset_path);
+        if let Some(stored) = self.asset_store()?.get(&path) {
+            return Ok(std::fs::read(stored)?);
+        }
+        let original = self.stored_asset(asset_path).await?;
+        let bytes = transcode(&original, derivative)
+            .with_context(|| format!("couldn't transcode {asset_path}"))?;
+        let mut store = self.asset_store()?;
+        store.put(&path, &bytes)?;
+        store.flush()?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use image::{GenericImageView, Rgba, RgbaImage};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn derivatives() -> Result<()> {
+        let mut png = Cursor::new(vec![]);
+        DynamicImage::from(RgbaImage::from_pixel(40, 30, Rgba([200, 80, 20, 128])))
+            .write_to(&mut png, image::ImageFormat::Png)?;
+        let png = png.into_inner();
+
+        let thumb = Derivative {
+            width: Some(8),
+            format: ImageFormat::Jpeg,
+        };
+        assert_eq!(
+            thumb.store_path("assets/characters/annie/skins/base/annie_splash.png"),
+            "derived/8/assets/characters/annie/skins/base/annie_splash.jpg"
+        );
+        let bytes = transcode(&png, &thumb)?;
+        assert_eq!(image::guess_format(&bytes)?, image::ImageFormat::Jpeg);
+        assert_eq!(image::load_from_memory(&bytes)?.dimensions(), (8, 6));
+        let same = transcode(&png, &Derivative::default())?;
+        assert_eq!(image::load_from_memory(&same)?.dimensions(), (40, 30));
+
+        // Made from the stored original, then kept
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        cdrag.asset_store()?.put("assets/splash.png", &png)?;
+        let derived = cdrag.derived_asset("assets/splash.png", &thumb).await?;
+        assert_eq!(derived, bytes);
+        assert!(cdrag
+            .asset_store()?
+            .get("derived/8/assets/splash.jpg")
+            .is_some());
+        Ok(())
+    }
+}
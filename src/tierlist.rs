@@ -0,0 +1,216 @@
+//! Tier lists of champions, rendered as an image of square portraits in S/A/B/C rows for
+//! sharing.
+//!
+//! A tier list file maps tiers to champion names:
+//!
+//! ```json
+//! {
+//!   "S": ["Jinx", "Vi"],
+//!   "A": ["Annie"]
+//! }
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use image::{imageops, Rgba, RgbaImage};
+
+use crate::{
+    cdragon::{CDragon, Champion},
+    profile::{Completion, Profile},
+    whatis::normalize,
+};
+
+/// Top to bottom
+pub const TIERS: [&str; 4] = ["S", "A", "B", "C"];
+/// The side of a portrait in the image, CDragon's are 120px
+const TILE: u32 = 64;
+const GAP: u32 = 2;
+const PER_ROW: u32 = 10;
+const BACKGROUND: Rgba<u8> = Rgba([26, 26, 26, 255]);
+const LABEL_COLORS: [Rgba<u8>; 4] = [
+    Rgba([255, 127, 127, 255]),
+    Rgba([255, 191, 127, 255]),
+    Rgba([255, 223, 127, 255]),
+    Rgba([191, 255, 127, 255]),
+];
+/// 5x7 glyphs of the tier letters, a row per byte with the leftmost pixel in the high bit
+const GLYPHS: [[u8; 7]; 4] = [
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e],
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11],
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e],
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e],
+];
+const GLYPH_SCALE: u32 = 6;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Format {
+    /// A Markdown list
+    #[default]
+    Text,
+    /// An image of the champions' portraits
+    Png,
+}
+
+/// Tiers to the names of the champions in them.
+pub type TierMap = BTreeMap<String, Vec<String>>;
+
+/// Reads a tier list file, see the [module docs](self).
+pub fn read(path: &Path) -> Result<TierMap> {
+    let ser = fs::read_to_string(path)
+        .with_context(|| format!("couldn't read the tier list {}", path.display()))?;
+    let tiers: TierMap = serde_json::from_str(&ser)
+        .with_context(|| format!("{} isn't a tier list", path.display()))?;
+    if let Some(tier) = tiers.keys().find(|tier| !TIERS.contains(&tier.as_str())) {
+        return Err(eyre!("{tier} isn't a tier, expected one of {TIERS:?}"));
+    }
+    Ok(tiers)
+}
+
+/// A tier list of the user's dex progress: their mastered champions in S and the ones they've
+/// played in A.
+pub fn from_profile(champions: &HashMap<u64, Champion>, profile: &Profile) -> TierMap {
+    let mut champions: Vec<&Champion> = champions.values().collect();
+    champions.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut tiers = TierMap::new();
+    for champion in champions {
+        let tier = match profile.completion(champion.id) {
+            Some(Completion::Mastered) => "S",
+            Some(Completion::Played) => "A",
+            None => continue,
+        };
+        tiers
+            .entry(tier.to_string())
+            .or_default()
+            .push(champion.name.clone());
+    }
+    tiers
+}
+
+/// The tier list as Markdown, one line per tier.
+pub fn markdown(tiers: &TierMap) -> String {
+    let mut out = String::new();
+    for tier in TIERS {
+        let names = tiers.get(tier).map(|names| names.join(", "));
+        let _ = writeln!(out, "- **{tier}**: {}", names.unwrap_or_default());
+    }
+    out
+}
+
+fn draw_label(image: &mut RgbaImage, tier: usize, top: u32, height: u32) {
+    for y in top..top + height {
+        for x in 0..TILE {
+            image.put_pixel(x, y, LABEL_COLORS[tier]);
+        }
+    }
+    let left = (TILE - 5 * GLYPH_SCALE) / 2;
+    let glyph_top = top + (height - 7 * GLYPH_SCALE) / 2;
+    for (row, bits) in GLYPHS[tier].iter().enumerate() {
+        for col in 0..5 {
+            if bits & (0x10 >> col) == 0 {
+                continue;
+            }
+            for dy in 0..GLYPH_SCALE {
+                for dx in 0..GLYPH_SCALE {
+                    image.put_pixel(
+                        left + col * GLYPH_SCALE + dx,
+                        glyph_top + row as u32 * GLYPH_SCALE + dy,
+                        Rgba([0, 0, 0, 255]),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Lays the portraits of each tier out in a row, in [`TIERS`] order, wrapping long tiers onto
+/// more lines. Portraits are scaled to fit.
+pub fn render(portraits: &[Vec<RgbaImage>; 4]) -> RgbaImage {
+    let lines: Vec<u32> = portraits
+        .iter()
+        .map(|row| (row.len() as u32).div_ceil(PER_ROW).max(1))
+        .collect();
+    let width = TILE + PER_ROW * (TILE + GAP);
+    let height = lines.iter().map(|lines| lines * TILE + GAP).sum::<u32>();
+    let mut image = RgbaImage::from_pixel(width, height, BACKGROUND);
+    let mut top = 0;
+    for (tier, row) in portraits.iter().enumerate() {
+        draw_label(&mut image, tier, top, lines[tier] * TILE);
+        for (i, portrait) in row.iter().enumerate() {
+            let i = i as u32;
+            let tile = imageops::resize(portrait, TILE, TILE, imageops::FilterType::Triangle);
+            let x = TILE + GAP + (i % PER_ROW) * (TILE + GAP);
+            let y = top + (i / PER_ROW) * TILE;
+            imageops::overlay(&mut image, &tile, x.into(), y.into());
+        }
+        top += lines[tier] * TILE + GAP;
+    }
+    image
+}
+
+impl CDragon {
+    /// Renders `tiers` with the champions' square portraits, see [`CDragon::stored_asset`].
+    pub async fn render_tierlist(&self, tiers: &TierMap) -> Result<RgbaImage> {
+        let mut portraits: [Vec<RgbaImage>; 4] = Default::default();
+        for (row, tier) in portraits.iter_mut().zip(TIERS) {
+            for name in tiers.get(tier).into_iter().flatten() {
+                let champion = self
+                    .champion_by_name(name)
+                    .ok_or_else(|| eyre!("no champion named {name}"))?;
+                let bytes = self
+                    .stored_asset(&normalize(&champion.square_portrait_path))
+                    .await?;
+                let portrait = image::load_from_memory(&bytes)
+                    .with_context(|| format!("the portrait of {name} isn't an image"))?;
+                row.push(portrait.to_rgba8());
+            }
+        }
+        Ok(render(&portraits))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn layout() -> Result<()> {
+        let portrait = RgbaImage::from_pixel(120, 120, Rgba([0, 0, 255, 255]));
+        let portraits = [vec![portrait.clone(); 12], vec![portrait], vec![], vec![]];
+        let image = render(&portraits);
+        // S wraps onto a second line, the others take one line each
+        assert_eq!(
+            image.dimensions(),
+            (TILE + PER_ROW * (TILE + GAP), 5 * TILE + 4 * GAP)
+        );
+        assert_eq!(image.get_pixel(0, 0), &LABEL_COLORS[0]);
+        assert_eq!(image.get_pixel(0, 2 * TILE + GAP), &LABEL_COLORS[1]);
+        let second_line = image.get_pixel(TILE + GAP + 1, TILE + 1);
+        assert_eq!(second_line, &Rgba([0, 0, 255, 255]));
+        assert_eq!(
+            image.get_pixel(TILE + 3 * (TILE + GAP) + 1, TILE + 1),
+            &BACKGROUND
+        );
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("tiers.json");
+        fs::write(&path, r#"{"S": ["Jinx"], "B": ["Annie", "Vi"]}"#)?;
+        let tiers = read(&path)?;
+        assert_eq!(
+            markdown(&tiers),
+            "- **S**: Jinx\n- **A**: \n- **B**: Annie, Vi\n- **C**: \n"
+        );
+        fs::write(&path, r#"{"F": ["Teemo"]}"#)?;
+        assert!(read(&path).is_err());
+        Ok(())
+    }
+}
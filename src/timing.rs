@@ -0,0 +1,100 @@
+//! `--timing`: where a command spent its time, collected from the library's tracing spans.
+//!
+//! The spans are named after what they time, "network", "parse" or "cache". Each span is timed
+//! from creation to close, and overlapping spans of a category are merged, so the champions being
+//! fetched in parallel count their wall time once rather than once per champion.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{span, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+const CATEGORIES: [&str; 3] = ["network", "parse", "cache"];
+
+type Intervals = BTreeMap<&'static str, Vec<(Instant, Instant)>>;
+
+struct Started(Instant);
+
+/// A tracing layer collecting how long the spans were open, for the command line to install
+#[derive(Debug, Clone, Default)]
+pub struct Timings(Arc<Mutex<Intervals>>);
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Timings {
+    fn on_new_span(&self, _attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(Started(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(start) = span.extensions().get::<Started>().map(|started| started.0) else {
+            return;
+        };
+        if let Ok(mut intervals) = self.0.lock() {
+            intervals
+                .entry(span.name())
+                .or_default()
+                .push((start, Instant::now()));
+        }
+    }
+}
+
+/// The total length of `intervals` with overlaps counted once.
+fn merged(mut intervals: Vec<(Instant, Instant)>) -> Duration {
+    intervals.sort();
+    let mut total = Duration::ZERO;
+    let mut current: Option<(Instant, Instant)> = None;
+    for (start, end) in intervals {
+        current = match current {
+            Some((cur_start, cur_end)) if start <= cur_end => Some((cur_start, cur_end.max(end))),
+            Some((cur_start, cur_end)) => {
+                total += cur_end - cur_start;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    total + current.map_or(Duration::ZERO, |(start, end)| end - start)
+}
+
+impl Timings {
+    /// A breakdown of `total` by category. Whatever no span covered, like the command's own
+    /// work and rendering its output, is reported as "other".
+    pub fn report(&self, total: Duration) -> String {
+        let intervals = self.0.lock().map(|i| i.clone()).unwrap_or_default();
+        let mut out = String::new();
+        for category in CATEGORIES {
+            let time = merged(intervals.get(category).cloned().unwrap_or_default());
+            let _ = writeln!(out, "{category:<8}{:>9.1} ms", time.as_secs_f64() * 1000.);
+        }
+        let covered = merged(intervals.into_values().flatten().collect());
+        let other = total.saturating_sub(covered);
+        let _ = writeln!(out, "{:<8}{:>9.1} ms", "other", other.as_secs_f64() * 1000.);
+        let _ = writeln!(out, "{:<8}{:>9.1} ms", "total", total.as_secs_f64() * 1000.);
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overlaps_count_once() {
+        let t = Instant::now();
+        let ms = Duration::from_millis;
+        let intervals = vec![
+            (t, t + ms(10)),
+            (t + ms(5), t + ms(15)),
+            (t + ms(20), t + ms(25)),
+        ];
+        assert_eq!(merged(intervals), ms(20));
+        assert_eq!(merged(vec![]), Duration::ZERO);
+    }
+}
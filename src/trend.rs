@@ -0,0 +1,194 @@
+//! How a field of a champion changed across patches, from the champions backfilled into the
+//! per-patch cache (see [`crate::backfill`]) and the latest ones.
+//!
+//! Fields are named by their path in the champion data, e.g. `playstyleInfo.damage` or
+//! `spells.0.cooldownCoefficients.0`, and have to be numbers.
+
+use std::{collections::HashMap, fmt::Write};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde_json::Value;
+
+use crate::{
+    cdragon::{CDragon, CacheFile},
+    patch::PatchVersion,
+};
+
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The value of the field in a patch, `None` when the champion or the field wasn't in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Point {
+    pub patch: String,
+    pub value: Option<f64>,
+}
+
+/// The JSON Pointer of a field path like `playstyleInfo.damage`. Pointers are taken as they are.
+pub fn pointer(field: &str) -> String {
+    match field.starts_with('/') {
+        true => field.to_string(),
+        false => format!("/{}", field.replace('.', "/")),
+    }
+}
+
+/// The number at `pointer` in `champion`.
+fn value_at(champion: &Value, pointer: &str) -> Result<Option<f64>> {
+    match champion.pointer(pointer) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::Number(number)) => Ok(number.as_f64()),
+        Some(Value::Bool(value)) => Ok(Some(f64::from(u8::from(*value)))),
+        Some(_) => Err(eyre!("{pointer} isn't a number")),
+    }
+}
+
+/// A bar per patch, from the lowest value to the highest, blank where there's no value.
+pub fn sparkline(points: &[Point]) -> String {
+    let values = points.iter().filter_map(|point| point.value);
+    let min = values.clone().fold(f64::INFINITY, f64::min);
+    let max = values.fold(f64::NEG_INFINITY, f64::max);
+    points
+        .iter()
+        .map(|point| match point.value {
+            None => ' ',
+            Some(_) if max <= min => BARS[BARS.len() / 2],
+            Some(value) => {
+                let level = (value - min) / (max - min) * (BARS.len() - 1) as f64;
+                BARS[level.round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Renders the trend as a sparkline followed by the patches the value changed in.
+pub fn render(champion: &str, field: &str, points: &[Point]) -> String {
+    let mut out = String::new();
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (&first.patch, &last.patch),
+        _ => return out,
+    };
+    let _ = writeln!(out, "{field} of {champion}, {first} to {last}");
+    let _ = writeln!(out, "{}", sparkline(points));
+    let mut previous = None;
+    for point in points {
+        let Some(value) = point.value else { continue };
+        let change = match previous {
+            Some(previous) if previous == value => continue,
+            Some(previous) => format!(" ({:+})", value - previous),
+            None => String::new(),
+        };
+        let _ = writeln!(out, "{:<8}{value}{change}", point.patch);
+        previous = Some(value);
+    }
+    out
+}
+
+/// The trend as CSV, a row per patch with an empty value where there's none.
+pub fn csv(points: &[Point]) -> String {
+    let mut out = String::from("patch,value\n");
+    for point in points {
+        let value = point
+            .value
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        let _ = writeln!(out, "{},{value}", point.patch);
+    }
+    out
+}
+
+impl CDragon {
+    /// The value of `field` of the champion with `id` in each backfilled patch, oldest first,
+    /// then in the latest cached data when it's for a patch that isn't backfilled.
+    pub fn trend(&self, id: u64, field: &str) -> Result<Vec<Point>> {
+        let pointer = pointer(field);
+        let mut points = vec![];
+        for patch in self.cached_patches() {
+            let Ok(champions) =
+                self.load_patch_obj::<HashMap<String, Value>>(&patch, CacheFile::Champions)
+            else {
+                continue;
+            };
+            let value = match champions.get(&id.to_string()) {
+                Some(champion) => value_at(champion, &pointer)
+                    .with_context(|| format!("{field} in {patch} isn't a number"))?,
+                None => None,
+            };
+            points.push(Point { patch, value });
+        }
+        if points.is_empty() {
+            return Err(eyre!(
+                "no champions are backfilled, run `blitzadex backfill --from <patch>` first"
+            ));
+        }
+
+        let latest = self
+            .load_obj::<PatchVersion>(CacheFile::ContentMetadata)
+            .ok()
+            .map(|version| version.patch().to_string());
+        if let Some(latest) = latest.filter(|latest| !points.iter().any(|p| p.patch == *latest)) {
+            if let Ok(champion) = self.get_path(CacheFile::Champions, &format!("/{id}")) {
+                let value = value_at(&champion, &pointer)
+                    .with_context(|| format!("{field} in {latest} isn't a number"))?;
+                points.push(Point {
+                    patch: latest,
+                    value,
+                });
+            }
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn across_patches() -> Result<()> {
+        assert_eq!(pointer("playstyleInfo.damage"), "/playstyleInfo/damage");
+        assert_eq!(pointer("/spells/0/range"), "/spells/0/range");
+
+        let dir = tempfile::tempdir()?;
+        let cdrag = CDragon::in_dir(dir.path());
+        assert!(cdrag.trend(1, "playstyleInfo.damage").is_err());
+        let patches = [
+            ("13.1", Some(2)),
+            ("13.2", None),
+            ("13.10", Some(2)),
+            ("14.1", Some(3)),
+        ];
+        for (patch, damage) in patches {
+            let champions = match damage {
+                Some(damage) => json!({"1": {"playstyleInfo": {"damage": damage}}}),
+                None => json!({}),
+            };
+            cdrag.cache_patch_obj(patch, &champions, CacheFile::Champions)?;
+        }
+        let annie = std::fs::read_to_string("testing/annie.json")?;
+        let annie: Value = serde_json::from_str(&annie)?;
+        cdrag.cache_obj(&json!({"1": annie}), CacheFile::Champions)?;
+        let version = PatchVersion {
+            version: "15.1.6463155+branch.releases-15-1.code.public.content.release".to_string(),
+            released: None,
+        };
+        cdrag.cache_obj(&version, CacheFile::ContentMetadata)?;
+
+        let points = cdrag.trend(1, "playstyleInfo.damage")?;
+        let patches: Vec<&str> = points.iter().map(|p| p.patch.as_str()).collect();
+        assert_eq!(patches, ["13.1", "13.2", "13.10", "14.1", "15.1"]);
+        assert_eq!(points[4].value, annie["playstyleInfo"]["damage"].as_f64());
+        assert!(cdrag.trend(1, "playstyleInfo").is_err());
+
+        let points = &points[..4];
+        assert_eq!(sparkline(points), "▁ ▁█");
+        assert_eq!(
+            render("Annie", "playstyleInfo.damage", points),
+            "playstyleInfo.damage of Annie, 13.1 to 14.1\n▁ ▁█\n13.1    2\n14.1    3 (+1)\n"
+        );
+        assert_eq!(csv(points), "patch,value\n13.1,2\n13.2,\n13.10,2\n14.1,3\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+//! Working out what an update would fetch without fetching it.
+//!
+//! Only directory listings are requested: the plugin listing to check staleness, and the game
+//! data listings whose file sizes give the size of each catalog. An update fetches the same
+//! catalogs whether or not the cache is stale, so the plan lists them either way.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+
+use crate::{
+    cdragon::{CDragon, CacheFile, PluginName},
+    listing::DirEntry,
+};
+
+pub(crate) const GAME_DATA_V1: &str = "latest/plugins/rcp-be-lol-game-data/global/default/v1";
+
+/// The catalogs [`CDragon::update`] refreshes when they're cached, in the order it does.
+const REFRESHED: [CacheFile; 16] = [
+    CacheFile::Items,
+    CacheFile::Perks,
+    CacheFile::PerkStyles,
+    CacheFile::SummonerSpells,
+    CacheFile::Skins,
+    CacheFile::SkinLines,
+    CacheFile::Universes,
+    CacheFile::WardSkins,
+    CacheFile::SummonerIcons,
+    CacheFile::Regalia,
+    CacheFile::Queues,
+    CacheFile::Maps,
+    CacheFile::Loot,
+    CacheFile::Statstones,
+    CacheFile::MissionAssets,
+    CacheFile::Challenges,
+];
+
+#[derive(Debug)]
+pub struct PlannedFetch {
+    pub catalog: CacheFile,
+    /// How many plugins, champions, etc. the catalog holds, `None` when only fetching it would
+    /// tell, as for the items
+    pub entities: Option<usize>,
+    /// `None` when the listing doesn't say, as for the plugin listing itself
+    pub bytes: Option<u64>,
+}
+
+#[derive(Debug)]
+pub struct UpdatePlan {
+    /// When the cached game data was published, if there is any
+    pub cached: Option<DateTime<Utc>>,
+    pub upstream: Option<DateTime<Utc>>,
+    pub fetches: Vec<PlannedFetch>,
+}
+
+impl UpdatePlan {
+    /// Whether the game data was updated upstream since it was cached
+    pub fn is_stale(&self) -> bool {
+        !matches!((self.cached, self.upstream), (Some(cached), Some(upstream)) if cached >= upstream)
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.fetches.iter().filter_map(|fetch| fetch.bytes).sum()
+    }
+}
+
+/// Formats a byte count for people, e.g. "6.2 MB".
+pub fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000. && unit < UNITS.len() - 1 {
+        size /= 1000.;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{bytes} B"),
+        _ => format!("{size:.1} {}", UNITS[unit]),
+    }
+}
+
+/// Renders the plan as a short report of what would be fetched.
+pub fn render(plan: &UpdatePlan) -> String {
+    let date = |date: Option<DateTime<Utc>>| {
+        date.map_or("never".to_string(), |date| {
+            date.format("%Y-%m-%d %H:%M").to_string()
+        })
+    };
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "game data published {}, cached {}",
+        date(plan.upstream),
+        date(plan.cached)
+    );
+    if !plan.is_stale() {
+        let _ = writeln!(out, "up to date, but an update fetches everything again");
+    }
+    let _ = writeln!(out, "would fetch:");
+    for fetch in &plan.fetches {
+        let size = fetch.bytes.map(human_bytes).unwrap_or_default();
+        let entities = fetch
+            .entities
+            .map_or("?".to_string(), |entities| entities.to_string());
+        let _ = writeln!(
+            out,
+            "  {:<24}{entities:>5} entries {size:>9}",
+            fetch.catalog.to_string(),
+        );
+    }
+    let _ = writeln!(out, "about {} in total", human_bytes(plan.total_bytes()));
+    out
+}
+
+impl CDragon {
+    /// Works out what [`CDragon::update`] would fetch, and whether the game data plugin has been
+    /// updated upstream since it was cached.
+    pub async fn plan_update(&self) -> Result<UpdatePlan> {
+        let plugins = self.fetch_plugins().await?;
+        let upstream = plugins
+            .iter()
+            .find(|plugin| plugin.name == PluginName::RcpBeLolGameData)
+            .map(|plugin| plugin.mtime);
+        let cached = self
+            .cached_plugin_updated_date(&PluginName::RcpBeLolGameData)
+            .await;
+        let v1: Vec<DirEntry> = self.list_dir(GAME_DATA_V1).await?;
+        let champion_files: Vec<DirEntry> =
+            self.list_dir(&format!("{GAME_DATA_V1}/champions")).await?;
+        Ok(UpdatePlan {
+            cached,
+            upstream,
+            fetches: plan_fetches(plugins.len(), &v1, &champion_files, |catalog| {
+                self.is_cached(catalog)
+            }),
+        })
+    }
+}
+
+/// What [`CDragon::update`] fetches, sized from the game data listings. `is_cached` tells which
+/// of the [`REFRESHED`] catalogs it refreshes.
+fn plan_fetches(
+    plugins: usize,
+    v1: &[DirEntry],
+    champion_files: &[DirEntry],
+    is_cached: impl Fn(CacheFile) -> bool,
+) -> Vec<PlannedFetch> {
+    let listed_size = |catalog: CacheFile| {
+        v1.iter()
+            .find(|entry| entry.name == catalog.to_string())
+            .and_then(|entry| entry.size)
+    };
+    let champion_files: Vec<&DirEntry> = champion_files
+        .iter()
+        // -1.json is the placeholder "None" champion
+        .filter(|entry| !entry.is_dir() && entry.name.ends_with(".json"))
+        .filter(|entry| !entry.name.starts_with('-'))
+        .collect();
+    let mut fetches = vec![
+        PlannedFetch {
+            catalog: CacheFile::Plugins,
+            entities: Some(plugins),
+            bytes: None,
+        },
+        // Not in the game data listing
+        PlannedFetch {
+            catalog: CacheFile::ContentMetadata,
+            entities: None,
+            bytes: None,
+        },
+        PlannedFetch {
+            catalog: CacheFile::ChampionSummaries,
+            entities: Some(champion_files.len()),
+            bytes: listed_size(CacheFile::ChampionSummaries),
+        },
+        PlannedFetch {
+            catalog: CacheFile::Champions,
+            entities: Some(champion_files.len()),
+            bytes: Some(champion_files.iter().filter_map(|entry| entry.size).sum()),
+        },
+    ];
+    // Counting what's in these would mean fetching them
+    fetches.extend(
+        REFRESHED
+            .into_iter()
+            .filter(|catalog| is_cached(*catalog))
+            .map(|catalog| PlannedFetch {
+                catalog,
+                entities: None,
+                bytes: listed_size(catalog),
+            }),
+    );
+    fetches
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::listing::EntryType;
+
+    #[test]
+    fn plans_what_an_update_fetches() {
+        let file = |name: &str, size| DirEntry {
+            name: name.to_string(),
+            ty: EntryType::File,
+            mtime: Utc::now(),
+            size: Some(size),
+        };
+        let v1 = [
+            file("champion-summary.json", 30),
+            file("items.json", 200),
+            file("perks.json", 100),
+        ];
+        let champions = [file("-1.json", 1), file("1.json", 10), file("2.json", 20)];
+        let plan = UpdatePlan {
+            cached: None,
+            upstream: Some(Utc::now()),
+            fetches: plan_fetches(40, &v1, &champions, |catalog| catalog == CacheFile::Items),
+        };
+        let fetched: Vec<CacheFile> = plan.fetches.iter().map(|fetch| fetch.catalog).collect();
+        assert_eq!(
+            fetched,
+            [
+                CacheFile::Plugins,
+                CacheFile::ContentMetadata,
+                CacheFile::ChampionSummaries,
+                CacheFile::Champions,
+                CacheFile::Items
+            ]
+        );
+        assert_eq!(plan.fetches[3].entities, Some(2));
+        assert_eq!(plan.total_bytes(), 30 + 30 + 200);
+        assert!(plan.is_stale());
+
+        let up_to_date = UpdatePlan {
+            cached: plan.upstream,
+            ..plan
+        };
+        assert!(!up_to_date.is_stale());
+        assert!(render(&up_to_date).contains("items.json"));
+    }
+
+    #[test]
+    fn human_sizes() {
+        assert_eq!(human_bytes(512), "512 B");
+        assert_eq!(human_bytes(25_457), "25.5 KB");
+        assert_eq!(human_bytes(6_204_441), "6.2 MB");
+    }
+}
@@ -0,0 +1,178 @@
+//! Warming the cache ahead of time, so later commands don't wait on CommunityDragon.
+//!
+//! What gets fetched is picked by a named profile. blitzadex has three: `minimal` (the champion
+//! summaries and details), `full` (plus items, runes, summoner spells and skins) and `assets`
+//! (plus every champion's portrait). Profiles can be added or overridden in the `[warm]` table
+//! of `blitzadex.toml` in the config directory:
+//!
+//! ```toml
+//! [warm]
+//! minimal = ["summaries"]
+//! streaming = ["summaries", "champions", "portraits"]
+//! ```
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use color_eyre::{
+    eyre::{eyre, Context},
+    Result,
+};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{
+    cdragon::{CDragon, CacheFile},
+    dex::Order,
+    whatis::normalize,
+};
+
+pub const CONFIG_FILE: &str = "blitzadex.toml";
+
+/// Something a profile can fetch into the cache.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Target {
+    Summaries,
+    Champions,
+    Items,
+    /// The runes and their styles
+    Perks,
+    SummonerSpells,
+    /// The skins, skin lines and universes
+    Skins,
+    /// Every champion's square portrait, into the asset store
+    Portraits,
+}
+
+/// `blitzadex.toml`
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Profile names to what they fetch
+    #[serde(default)]
+    pub warm: BTreeMap<String, Vec<Target>>,
+    #[serde(default)]
+    pub dex: DexConfig,
+}
+
+/// The `[dex]` table of `blitzadex.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct DexConfig {
+    /// The order the dex is shown in when `--order` isn't given
+    #[serde(default)]
+    pub order: Order,
+}
+
+impl Config {
+    /// Reads `blitzadex.toml` from `config_dir`, a missing file is an empty config.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(CONFIG_FILE);
+        if !path.try_exists().unwrap_or(false) {
+            return Ok(Self::default());
+        }
+        let ser = fs::read_to_string(&path)?;
+        toml::from_str(&ser).with_context(|| format!("failed to read {}", path.display()))
+    }
+}
+
+/// The builtin profiles with the ones from `config` layered on top.
+pub fn profiles(config: &Config) -> BTreeMap<String, Vec<Target>> {
+    use Target::*;
+    let minimal = vec![Summaries, Champions];
+    let full = [&minimal[..], &[Items, Perks, SummonerSpells, Skins]].concat();
+    let assets = [&full[..], &[Portraits]].concat();
+    let mut profiles = BTreeMap::from([
+        ("minimal".to_string(), minimal),
+        ("full".to_string(), full),
+        ("assets".to_string(), assets),
+    ]);
+    profiles.extend(config.warm.clone());
+    profiles
+}
+
+impl CDragon {
+    /// The targets of the warm profile called `name`, see the [module docs](self).
+    pub fn warm_profile(&self, name: &str) -> Result<Vec<Target>> {
+        let profiles = profiles(&Config::load(&self.config_dir)?);
+        profiles.get(name).cloned().ok_or_else(|| {
+            let known: Vec<&str> = profiles.keys().map(String::as_str).collect();
+            eyre!(
+                "no warm profile named {name}, try one of {}",
+                known.join(", ")
+            )
+        })
+    }
+
+    /// Fetches whatever of `target` isn't cached yet.
+    pub async fn warm(&self, target: Target) -> Result<()> {
+        match target {
+            Target::Summaries => {
+                self.champion_summaries().await?;
+            }
+            Target::Champions => {
+                let cached = self.cache_dir.join(CacheFile::Champions.to_string());
+                if !cached.try_exists().unwrap_or(false) {
+                    let champions = self.fetch_all_champions().await?;
+                    self.cache_versioned(
+                        &champions,
+                        CacheFile::Champions,
+                        &self.data_version().await?,
+                    )?;
+                }
+            }
+            Target::Items => {
+                self.items().await?;
+            }
+            Target::Perks => {
+                self.perks().await?;
+                self.perk_styles().await?;
+            }
+            Target::SummonerSpells => {
+                self.summoner_spells().await?;
+            }
+            Target::Skins => {
+                self.skins().await?;
+                self.skin_lines().await?;
+                self.universes().await?;
+            }
+            Target::Portraits => {
+                for summary in self.champion_summaries().await? {
+                    self.stored_asset(&normalize(&summary.square_portrait_path))
+                        .await
+                        .with_context(|| format!("couldn't warm {}'s portrait", summary.name))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn config_profiles() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert!(Config::load(dir.path())?.warm.is_empty());
+        fs::write(
+            dir.path().join(CONFIG_FILE),
+            "[warm]\nminimal = [\"summaries\"]\nstreaming = [\"champions\", \"portraits\"]\n",
+        )?;
+        let profiles = profiles(&Config::load(dir.path())?);
+        assert_eq!(profiles["minimal"], [Target::Summaries]);
+        assert_eq!(
+            profiles["streaming"],
+            [Target::Champions, Target::Portraits]
+        );
+        assert_eq!(profiles["assets"].last(), Some(&Target::Portraits));
+        assert!(profiles["full"].contains(&Target::SummonerSpells));
+
+        fs::write(dir.path().join(CONFIG_FILE), "[dex]\norder = \"release\"\n")?;
+        assert_eq!(Config::load(dir.path())?.dex.order, Order::Release);
+
+        fs::write(dir.path().join(CONFIG_FILE), "[warm]\nbad = [\"runes\"]\n")?;
+        assert!(Config::load(dir.path()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+//! Reverse lookup from an asset path or CDN URL to the champion, skin or chroma it belongs to.
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::cdragon::{Champion, Chroma, Skin};
+
+/// Who an asset belongs to.
+#[derive(Debug, PartialEq)]
+pub enum Owner<'a> {
+    Champion {
+        champion: &'a Champion,
+        /// What the asset is, e.g. "portrait", if known
+        asset: Option<&'static str>,
+    },
+    Skin {
+        champion: &'a Champion,
+        skin: &'a Skin,
+        asset: Option<&'static str>,
+    },
+    Chroma {
+        champion: &'a Champion,
+        skin: &'a Skin,
+        chroma: &'a Chroma,
+    },
+}
+
+impl Display for Owner<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Owner::Champion { champion, asset } => {
+                write!(f, "champion {} ({})", champion.name, champion.id)?;
+                asset.map_or(Ok(()), |asset| write!(f, ", {asset}"))
+            }
+            Owner::Skin {
+                champion,
+                skin,
+                asset,
+            } => {
+                write!(f, "skin {} ({}) of {}", skin.name, skin.id, champion.name)?;
+                asset.map_or(Ok(()), |asset| write!(f, ", {asset}"))
+            }
+            Owner::Chroma {
+                champion,
+                skin,
+                chroma,
+            } => write!(
+                f,
+                "chroma {} ({}) of {}, {}",
+                chroma.name, chroma.id, skin.name, champion.name
+            ),
+        }
+    }
+}
+
+/// Turns a CDN URL, a game data path like "/lol-game-data/assets/ASSETS/..." or a path as found
+/// in the wild into the [Normalized Path](crate::cdragon::Skin) used by the cache and the
+/// [`AssetStore`](crate::assets::AssetStore).
+pub fn normalize(path_or_url: &str) -> String {
+    let lower = path_or_url.trim().to_lowercase();
+    let path = lower
+        .split_once("/global/default/")
+        .map(|(_, path)| path)
+        .or_else(|| {
+            lower
+                .split_once("/lol-game-data/assets/")
+                .map(|(_, path)| path)
+        })
+        .unwrap_or(&lower);
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    path.trim_start_matches('/').to_string()
+}
+
+fn skin_asset(skin: &Skin, path: &str) -> Option<&'static str> {
+    [
+        (&skin.splash_path, "splash"),
+        (&skin.uncentered_splash_path, "uncentered splash"),
+        (&skin.tile_path, "tile"),
+        (&skin.load_screen_path, "loading screen"),
+    ]
+    .into_iter()
+    .find(|(skin_path, _)| skin_path.as_str() == path)
+    .map(|(_, asset)| asset)
+}
+
+/// Finds the owner of an asset, first by exact path and then by the conventions of the game's
+/// file layout, e.g. `characters/<alias>/skins/skin05/` for the sixth skin of a champion.
+pub fn what_is<'a>(champions: &'a HashMap<u64, Champion>, path_or_url: &str) -> Option<Owner<'a>> {
+    let path = normalize(path_or_url);
+    if path.is_empty() {
+        return None;
+    }
+    for champion in champions.values() {
+        if normalize(&champion.square_portrait_path) == path {
+            return Some(Owner::Champion {
+                champion,
+                asset: Some("portrait"),
+            });
+        }
+        for skin in &champion.skins {
+            if let Some(asset) = skin_asset(skin, &path) {
+                return Some(Owner::Skin {
+                    champion,
+                    skin,
+                    asset: Some(asset),
+                });
+            }
+            let chroma = skin.chromas.iter().find(|chroma| {
+                chroma
+                    .chroma_path
+                    .as_deref()
+                    .is_some_and(|chroma_path| normalize(chroma_path) == path)
+            });
+            if let Some(chroma) = chroma {
+                return Some(Owner::Chroma {
+                    champion,
+                    skin,
+                    chroma,
+                });
+            }
+        }
+    }
+    by_layout(champions, &path)
+}
+
+fn by_layout<'a>(champions: &'a HashMap<u64, Champion>, path: &str) -> Option<Owner<'a>> {
+    let mut segments = path.split('/');
+    let champion = segments
+        .by_ref()
+        .skip_while(|segment| *segment != "characters")
+        .nth(1)
+        .and_then(|alias| {
+            champions
+                .values()
+                .find(|champ| champ.alias.eq_ignore_ascii_case(alias))
+        })?;
+    let skin_number = segments.find_map(|segment| match segment {
+        "base" => Some(0),
+        _ => segment.strip_prefix("skin")?.parse::<u64>().ok(),
+    });
+    let skin = skin_number.and_then(|number| {
+        champion
+            .skins
+            .iter()
+            .find(|skin| skin.id == champion.id * 1000 + number)
+    });
+    Some(match skin {
+        Some(skin) => Owner::Skin {
+            champion,
+            skin,
+            asset: None,
+        },
+        None => Owner::Champion {
+            champion,
+            asset: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn annie() -> Champion {
+        Champion {
+            id: 1,
+            name: "Annie".to_string(),
+            alias: "Annie".to_string(),
+            square_portrait_path: "/lol-game-data/assets/v1/champion-icons/1.png".to_string(),
+            skins: vec![
+                Skin {
+                    id: 1000,
+                    name: "Annie".to_string(),
+                    splash_path:
+                        "assets/characters/annie/skins/base/images/annie_splash_centered_0.jpg"
+                            .to_string(),
+                    ..Default::default()
+                },
+                Skin {
+                    id: 1005,
+                    name: "Frostfire Annie".to_string(),
+                    chromas: vec![Chroma {
+                        id: 1006,
+                        name: "Frostfire Annie (Ruby)".to_string(),
+                        chroma_path: Some(
+                            "/lol-game-data/assets/v1/champion-chroma-images/1/1006.png"
+                                .to_string(),
+                        ),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_owners() {
+        let champions = HashMap::from([(1, annie())]);
+        let owner = |path| what_is(&champions, path).map(|owner| owner.to_string());
+        assert_eq!(
+            owner("https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/champion-icons/1.png").unwrap(),
+            "champion Annie (1), portrait"
+        );
+        assert_eq!(
+            owner("/lol-game-data/assets/ASSETS/Characters/Annie/Skins/Base/Images/annie_splash_centered_0.jpg").unwrap(),
+            "skin Annie (1000) of Annie, splash"
+        );
+        assert_eq!(
+            owner("v1/champion-chroma-images/1/1006.png").unwrap(),
+            "chroma Frostfire Annie (Ruby) (1006) of Frostfire Annie, Annie"
+        );
+        assert_eq!(
+            owner("assets/sounds/wwise2016/vo/en_us/characters/annie/skins/skin05/annie_skin05_vo_audio.wem").unwrap(),
+            "skin Frostfire Annie (1005) of Annie"
+        );
+        assert_eq!(
+            owner("assets/items/icons2d/1001_class_t1_bootsofspeed.png"),
+            None
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! Sounds in the game data.
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths::{asset_url, normalize_asset_path};
+
+/// A sound in the game data, e.g. a champion's pick voice line. Serialized as its path.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub struct AudioAsset {
+    /// [Normalized Path](crate::Skin), empty when there's no such sound
+    pub path: String,
+}
+
+impl From<String> for AudioAsset {
+    fn from(path: String) -> Self {
+        Self {
+            path: normalize_asset_path(&path),
+        }
+    }
+}
+
+impl From<AudioAsset> for String {
+    fn from(asset: AudioAsset) -> Self {
+        asset.path
+    }
+}
+
+impl AudioAsset {
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    /// Where CDragon serves the sound.
+    pub fn url(&self) -> String {
+        asset_url(&self.path)
+    }
+
+    /// The MIME type of the sound, by its extension.
+    pub fn content_type(&self) -> &'static str {
+        let extension = self.path.rsplit_once('.').map(|(_, ext)| ext);
+        match extension {
+            Some("ogg") => "audio/ogg",
+            Some("mp3") => "audio/mpeg",
+            Some("wav") => "audio/wav",
+            _ => "application/octet-stream",
+        }
+    }
+}
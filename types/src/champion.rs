@@ -0,0 +1,192 @@
+//! Champions, their abilities and the summary index of them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::AudioAsset,
+    community::{CommunityMetadata, Position},
+    skin::Skin,
+    text::fold,
+};
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TactialInfo {
+    pub style: u64,
+    pub difficulty: u64,
+    pub damage_type: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaystyleInfo {
+    pub damage: u64,
+    pub durability: u64,
+    pub crowd_control: u64,
+    pub mobility: u64,
+    pub utility: u64,
+}
+
+/// One of a champion's four abilities.
+///
+/// `range`, `cost_coefficients` and `cooldown_coefficients` have an entry per rank, CDragon pads
+/// them out to six ranks.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Spell {
+    pub spell_key: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// The tooltip, with placeholders like `@TotalDamage@` for the numbers
+    #[serde(default)]
+    pub dynamic_description: String,
+    /// e.g. "@Cost@ @AbilityResourceName@"
+    #[serde(default)]
+    pub cost: String,
+    /// e.g. "@Cooldown@s"
+    #[serde(default)]
+    pub cooldown: String,
+    #[serde(default)]
+    pub range: Vec<f64>,
+    #[serde(default)]
+    pub cost_coefficients: Vec<f64>,
+    /// In seconds
+    #[serde(default)]
+    pub cooldown_coefficients: Vec<f64>,
+    #[serde(default)]
+    pub ability_icon_path: String,
+    /// Relative to the ability video host rather than the game data
+    #[serde(default)]
+    pub ability_video_path: String,
+    /// The video's poster, relative to the ability video host
+    #[serde(default)]
+    pub ability_video_image_path: String,
+}
+
+impl Spell {
+    /// The furthest range across every rank of the spell.
+    pub fn max_range(&self) -> Option<f64> {
+        self.range.iter().copied().reduce(f64::max)
+    }
+
+    /// The cooldown at `rank`, counting from 1.
+    pub fn cooldown_at(&self, rank: usize) -> Option<f64> {
+        self.cooldown_coefficients
+            .get(rank.checked_sub(1)?)
+            .copied()
+    }
+
+    /// The cost at `rank`, counting from 1.
+    pub fn cost_at(&self, rank: usize) -> Option<f64> {
+        self.cost_coefficients.get(rank.checked_sub(1)?).copied()
+    }
+}
+
+/// A champion's innate ability.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Passive {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub ability_icon_path: String,
+    /// Relative to the ability video host rather than the game data
+    #[serde(default)]
+    pub ability_video_path: String,
+    /// The video's poster, relative to the ability video host
+    #[serde(default)]
+    pub ability_video_image_path: String,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Champion {
+    pub id: u64,
+    pub name: String,
+    pub alias: String,
+    pub title: String,
+    pub short_bio: String,
+    pub tactical_info: TactialInfo,
+    pub playstyle_info: PlaystyleInfo,
+    pub square_portrait_path: String,
+    #[serde(default)]
+    pub stinger_sfx_path: AudioAsset,
+    #[serde(default)]
+    pub choose_vo_path: AudioAsset,
+    #[serde(default)]
+    pub ban_vo_path: AudioAsset,
+    pub roles: Vec<String>,
+    pub skins: Vec<Skin>,
+    #[serde(default)]
+    pub spells: Vec<Spell>,
+    #[serde(default)]
+    pub passive: Passive,
+    /// Filled in from the community overlay when the champions are loaded, never cached
+    #[serde(skip)]
+    pub community: CommunityMetadata,
+}
+
+impl Champion {
+    /// The lanes the champion is usually played in, from the community community overlay.
+    pub fn positions(&self) -> &[Position] {
+        &self.community.positions
+    }
+
+    pub fn plays(&self, position: Position) -> bool {
+        self.positions().contains(&position)
+    }
+}
+
+/// The entry for a champion in champion-summary.json.
+///
+/// It is a small fraction of the size of the full [`Champion`], so it is the index to reach for
+/// when only names and roles are needed.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionSummary {
+    pub id: u64,
+    pub name: String,
+    pub alias: String,
+    pub square_portrait_path: String,
+    pub roles: Vec<String>,
+}
+
+impl ChampionSummary {
+    /// Whether `query` names this champion, compared [folded](fold). Matches either the display
+    /// name or the alias, e.g. "wukong" and "monkeyking".
+    pub fn is_named(&self, query: &str) -> bool {
+        let query = fold(query);
+        fold(&self.name) == query || fold(&self.alias) == query
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::skin::Rarity;
+
+    #[test]
+    fn cached_champion_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let annie: Champion =
+            serde_json::from_str(&std::fs::read_to_string("../testing/annie.json")?)?;
+        let cached: Champion = serde_json::from_str(&serde_json::to_string(&annie)?)?;
+        assert_eq!(annie, cached);
+        assert_eq!(annie.passive.name, "Pyromania");
+        let lunar = annie.skins.iter().find(|skin| skin.id == 1013).unwrap();
+        assert_eq!(lunar.rarity, Rarity::KEpic);
+        assert_eq!(lunar.chromas[0].colors, ["#D33528", "#D33528"]);
+        assert_eq!(lunar.chromas[0].rarities[0].rarity, 2);
+        assert!(lunar.chromas[0].descriptions[0]
+            .description
+            .contains("bundle exclusive"));
+        let q = &annie.spells[0];
+        assert_eq!((q.cost_at(1), q.cost_at(5)), (Some(60.), Some(80.)));
+        assert_eq!((q.cooldown_at(1), q.cooldown_at(0)), (Some(4.), None));
+        assert_eq!(
+            q.ability_video_image_path,
+            "champion-abilities/0001/ability_0001_Q1.jpg"
+        );
+        Ok(())
+    }
+}
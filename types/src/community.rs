@@ -0,0 +1,93 @@
+//! Community maintained metadata that CDragon doesn't provide.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::text::fold;
+
+/// A lane, as opposed to the class [roles](crate::Champion::roles) in the champion
+/// data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Position {
+    Top,
+    Jungle,
+    #[serde(alias = "mid")]
+    #[cfg_attr(feature = "clap", value(alias = "mid"))]
+    Middle,
+    #[serde(alias = "bot", alias = "adc")]
+    #[cfg_attr(feature = "clap", value(alias = "bot", alias = "adc"))]
+    Bottom,
+    #[serde(alias = "utility")]
+    #[cfg_attr(feature = "clap", value(alias = "utility"))]
+    Support,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Position::Top => "top",
+            Position::Jungle => "jungle",
+            Position::Middle => "middle",
+            Position::Bottom => "bottom",
+            Position::Support => "support",
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityMetadata {
+    #[serde(default)]
+    pub nicknames: Vec<String>,
+    pub voice_actor: Option<String>,
+    pub release_patch: Option<String>,
+    /// Where the champion is usually played, most common first
+    #[serde(default)]
+    pub positions: Vec<Position>,
+    /// Any other fields, kept so the format can grow without code changes. They can hold any
+    /// JSON, e.g. a number or a list, not only text.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl CommunityMetadata {
+    /// Layers `other` on top of this metadata. Nicknames are combined, everything else in
+    /// `other` wins.
+    pub fn merge(&mut self, other: CommunityMetadata) {
+        for nickname in other.nicknames {
+            if !self.nicknames.contains(&nickname) {
+                self.nicknames.push(nickname);
+            }
+        }
+        if other.voice_actor.is_some() {
+            self.voice_actor = other.voice_actor;
+        }
+        if other.release_patch.is_some() {
+            self.release_patch = other.release_patch;
+        }
+        if !other.positions.is_empty() {
+            self.positions = other.positions;
+        }
+        self.extra.extend(other.extra);
+    }
+
+    /// The text in the other fields: string values, and the strings in lists.
+    pub fn extra_text(&self) -> impl Iterator<Item = &str> {
+        self.extra.values().flat_map(|value| match value {
+            Value::String(text) => vec![text.as_str()],
+            Value::Array(values) => values.iter().filter_map(Value::as_str).collect(),
+            _ => vec![],
+        })
+    }
+
+    pub fn has_nickname(&self, query: &str) -> bool {
+        let query = fold(query);
+        self.nicknames
+            .iter()
+            .any(|nickname| fold(nickname) == query)
+    }
+}
@@ -0,0 +1,105 @@
+//! Items from the game data plugin's items.json.
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths::deserialize_asset_path;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub id: u64,
+    pub name: String,
+    /// The tooltip, marked up with the client's tags like `<stats>` and `<attention>`
+    pub description: String,
+    pub active: bool,
+    pub in_store: bool,
+    /// The ids of the items it's built from
+    pub from: Vec<u64>,
+    /// The ids of the items it builds into
+    pub to: Vec<u64>,
+    /// The shop's tags, e.g. "Boots" or "AttackSpeed"
+    #[serde(rename = "categories")]
+    pub tags: Vec<String>,
+    /// The cost of the item on top of its components
+    pub price: u64,
+    /// The cost of the item with its components
+    pub price_total: u64,
+    /// Only that champion can buy the item, e.g. "Kalista", when not empty
+    #[serde(default)]
+    pub required_champion: String,
+    /// [Normalized Path](crate::Skin)
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub icon_path: String,
+}
+
+/// A stat an item grants, e.g. 25% attack speed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStat {
+    /// e.g. "Attack Speed"
+    pub name: String,
+    pub value: f64,
+    pub percent: bool,
+}
+
+impl Item {
+    /// The stats in the `<stats>` block of the description, which is the only place items.json
+    /// has them.
+    pub fn stats(&self) -> Vec<ItemStat> {
+        let Some((_, rest)) = self.description.split_once("<stats>") else {
+            return vec![];
+        };
+        let block = rest.split("</stats>").next().unwrap_or_default();
+        block
+            .split("<br>")
+            .filter_map(|line| {
+                let (value, name) = line
+                    .trim()
+                    .strip_prefix("<attention>")?
+                    .split_once("</attention>")?;
+                let percent = value.ends_with('%');
+                Some(ItemStat {
+                    name: name.trim().to_string(),
+                    value: value.trim_end_matches('%').trim().parse().ok()?,
+                    percent,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn items_and_stats() -> Result<(), Box<dyn std::error::Error>> {
+        let items: Vec<Item> =
+            serde_json::from_str(&std::fs::read_to_string("../testing/items.json")?)?;
+        let greaves = &items[1];
+        assert_eq!(greaves.from, [1001, 1042]);
+        assert_eq!(greaves.tags, ["AttackSpeed", "Boots"]);
+        assert_eq!(greaves.price_total, 1100);
+        assert_eq!(
+            greaves.icon_path,
+            "assets/items/icons2d/3006_class_t2_berserkersgreaves.png"
+        );
+        assert_eq!(
+            greaves.stats(),
+            [
+                ItemStat {
+                    name: "Attack Speed".to_string(),
+                    value: 25.,
+                    percent: true
+                },
+                ItemStat {
+                    name: "Move Speed".to_string(),
+                    value: 45.,
+                    percent: false
+                }
+            ]
+        );
+        let cached: Vec<Item> = serde_json::from_str(&serde_json::to_string(&items)?)?;
+        assert_eq!(cached, items);
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+//! The data models blitzadex reads from CommunityDragon: champions, their skins and abilities,
+//! items and the plugins the game data comes from.
+//!
+//! They only depend on serde (and chrono for timestamps), so servers and bots can share them
+//! with blitzadex without pulling in its HTTP client, runtime or cache.
+
+pub mod audio;
+pub mod champion;
+pub mod community;
+pub mod item;
+pub mod paths;
+pub mod plugin;
+pub mod skin;
+pub mod text;
+
+pub use audio::AudioAsset;
+pub use champion::{Champion, ChampionSummary, Passive, PlaystyleInfo, Spell, TactialInfo};
+pub use community::{CommunityMetadata, Position};
+pub use item::{Item, ItemStat};
+pub use plugin::{EntryType, Plugin, PluginName};
+pub use skin::{Chroma, Rarity, RegionalDescription, RegionalRarity, Skin, SkinLine, SkinType};
+pub use text::fold;
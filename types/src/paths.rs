@@ -0,0 +1,35 @@
+//! Asset paths in the game data, see [`Skin`](crate::Skin) for what normalizing them means.
+
+use serde::{Deserialize, Deserializer};
+
+pub const GAME_DATA_URL: &str =
+    "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default";
+const ASSETS: &str = "assets";
+
+/// The URL of an asset in the game data from its [Normalized Path](crate::Skin).
+pub fn asset_url(normalized_path: &str) -> String {
+    format!("{GAME_DATA_URL}/{normalized_path}")
+}
+
+/// Game data paths are either under ASSETS, like skin art, or straight under the plugin's
+/// directory, like "/lol-game-data/assets/v1/perk-images/...".
+pub fn normalize_asset_path(path: &str) -> String {
+    let path = path.replace("/lol-game-data/assets/ASSETS", ASSETS);
+    path.strip_prefix("/lol-game-data/assets/")
+        .unwrap_or(&path)
+        .to_lowercase()
+}
+
+pub fn deserialize_asset_path<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(normalize_asset_path(&String::deserialize(deserializer)?))
+}
+
+pub fn deserialize_optional_asset_path<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?.map(|path| normalize_asset_path(&path)))
+}
@@ -0,0 +1,139 @@
+//! The client plugins CommunityDragon serves the game data from.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PluginName {
+    #[default]
+    None,
+    RcpBeLolGameData,
+    RcpBeLolLicenseAgreement,
+    RcpBeSanitizer,
+    RcpFeAudio,
+    RcpFeCommonLibs,
+    RcpFeEmberLibs,
+    RcpFeLolCareerStats,
+    RcpFeLolChampSelect,
+    RcpFeLolChampionDetails,
+    RcpFeLolChampionStatistics,
+    RcpFeLolClash,
+    RcpFeLolCollections,
+    RcpFeLolEsportsSpectate,
+    RcpFeLolEventHub,
+    RcpFeLolEventShop,
+    RcpFeLolHighlights,
+    RcpFeLolHonor,
+    RcpFeLolKickout,
+    RcpFeLolL10n,
+    RcpFeLolLeagues,
+    RcpFeLolLockAndLoad,
+    RcpFeLolLoot,
+    RcpFeLolMatchHistory,
+    RcpFeLolNavigation,
+    RcpFeLolNewPlayerExperience,
+    RcpFeLolNpeRewards,
+    RcpFeLolParties,
+    RcpFeLolPaw,
+    RcpFeLolPft,
+    RcpFeLolPostgame,
+    RcpFeLolPremadeVoice,
+    RcpFeLolProfiles,
+    RcpFeLolSettings,
+    RcpFeLolSharedComponents,
+    RcpFeLolSkinsPicker,
+    RcpFeLolSocial,
+    RcpFeLolStartup,
+    RcpFeLolStaticAssets,
+    RcpFeLolStore,
+    RcpFeLolTft,
+    RcpFeLolTftTeamPlanner,
+    RcpFeLolTftTroves,
+    RcpFeLolTypekit,
+    RcpFeLolUikit,
+    RcpFeLolYourshop,
+    RcpFePluginRunner,
+    #[serde(other)]
+    PluginManifest,
+}
+
+/// Shown by variant name, e.g. "RcpBeLolGameData".
+impl fmt::Display for PluginName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Whether a directory entry is a file or a directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryType {
+    File,
+    Directory,
+}
+
+/// A directory entry of the plugins directory, with its name parsed into a [`PluginName`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Plugin {
+    pub name: PluginName,
+    #[serde(rename = "type")]
+    ty: EntryType,
+    #[serde(with = "mtime_format")]
+    pub mtime: DateTime<Utc>,
+    size: Option<u64>,
+}
+
+impl Plugin {
+    pub fn updated_since(&self, date: DateTime<Utc>) -> bool {
+        self.mtime > date
+    }
+}
+
+pub mod mtime_format {
+    use chrono::{DateTime, NaiveDateTime, Utc};
+    use serde::{self, Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%a, %d %b %Y %H:%M:%S %Z";
+
+    pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let s = format!("{}", date.format(FORMAT));
+        serializer.serialize_str(&s)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let dt = NaiveDateTime::parse_from_str(&s, FORMAT).map_err(serde::de::Error::custom)?;
+        Ok(DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plugins_and_mtimes() -> Result<(), Box<dyn std::error::Error>> {
+        let plugins: Vec<Plugin> =
+            serde_json::from_str(&std::fs::read_to_string("../testing/plugins.json")?)?;
+        let game_data = plugins
+            .iter()
+            .find(|plugin| plugin.name == PluginName::RcpBeLolGameData)
+            .unwrap();
+        assert_eq!(game_data.mtime.to_rfc3339(), "2024-11-20T07:10:52+00:00");
+        assert!(game_data.updated_since(game_data.mtime - chrono::Duration::seconds(1)));
+        assert!(!game_data.updated_since(game_data.mtime));
+        let cached: Vec<Plugin> = serde_json::from_str(&serde_json::to_string(&plugins)?)?;
+        let mtimes = |plugins: &[Plugin]| plugins.iter().map(|p| p.mtime).collect::<Vec<_>>();
+        assert_eq!(mtimes(&cached), mtimes(&plugins));
+        Ok(())
+    }
+}
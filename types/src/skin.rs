@@ -0,0 +1,181 @@
+//! Skins, their chromas and the skin lines they belong to.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::paths::{deserialize_asset_path, deserialize_optional_asset_path};
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Rarity {
+    KEpic,
+    KLegendary,
+    KMythic,
+    #[default]
+    KNoRarity,
+    KRare,
+    KTranscendent,
+    KUltimate,
+    KExalted,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum SkinType {
+    Ultimate,
+    #[default]
+    #[serde(other)]
+    None,
+}
+
+/// The information and asset paths for a [`Skin`]
+///
+///
+/// [`splash_path`] - [Normalized Path] to the splash art centered on the skin
+/// [`uncentered_splash_path`] - [Normalized Path] to normal splash art for the skin. May overlap
+/// with splash arts for skins in the same [`skin_lines`] because a single splash art includes all
+/// of the champions.
+/// tile_path
+/// load_screen_path
+/// [`load_screen_vintage_path`] - [Normalized Path] to the older style of loading screen art, only
+/// some skins have one
+///
+/// ## [Normalized Path]
+/// Paths returned by the Cdragon api's json cannot be used to directly navigate to an asset. To
+/// [Normalize] this path we strip the first two path parts and cast to lowercase.
+///
+/// This normalization will allow us to construct the actual path to the asset by doing the following:
+/// ```ignore
+/// let cdragon = CDragon::new().await.unwrap();
+/// let akshan_skin_splash = cdragon.champions
+/// format!("{GAME_DATA_URL}/{ASSETS}/")
+/// ```
+///
+/// For example:
+/// ```text
+///     From the Cdragon json:
+///     /lol-game-data/assets/**ASSETS**/Characters/Akshan/Skins/Base/Images/akshan_splash_uncentered_0.jpg
+///
+///     The path to the actual asset:
+///     https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/assets/characters/akshan/skins/base/images/akshan_splash_uncentered_0.jpg
+///
+///     [Normalized Path]:
+///     assets/characters/akshan/skins/base/images/akshan_splash_uncentered_0.jpg
+/// ```
+///
+///
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Skin {
+    pub id: u64,
+    pub is_base: bool,
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub splash_path: String,
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub uncentered_splash_path: String,
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub tile_path: String,
+    #[serde(deserialize_with = "deserialize_asset_path")]
+    pub load_screen_path: String,
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub load_screen_vintage_path: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_optional_asset_path")]
+    pub splash_video_path: Option<String>,
+    pub skin_type: SkinType,
+    pub rarity: Rarity,
+    pub is_legacy: bool,
+    #[serde(deserialize_with = "deserialize_skin_lines")]
+    pub skin_lines: Vec<u64>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub chromas: Vec<Chroma>,
+}
+
+impl Skin {
+    /// The id of the skin's champion, which skin ids are built from.
+    pub fn champion_id(&self) -> u64 {
+        self.id / 1000
+    }
+}
+
+/// A recolor of a [`Skin`].
+///
+/// `colors` are the hex codes the client uses for the chroma's swatch, usually two.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Chroma {
+    pub id: u64,
+    pub name: String,
+    pub chroma_path: Option<String>,
+    #[serde(default)]
+    pub colors: Vec<String>,
+    /// How the chroma was released, e.g. in a bundle
+    #[serde(default)]
+    pub descriptions: Vec<RegionalDescription>,
+    #[serde(default)]
+    pub rarities: Vec<RegionalRarity>,
+}
+
+/// Text that differs per region, "riot" is the default.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RegionalDescription {
+    pub region: String,
+    pub description: String,
+}
+
+/// A rarity that differs per region, "riot" is the default.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RegionalRarity {
+    pub region: String,
+    /// 0 for none, up to 5 for ultimate
+    pub rarity: u64,
+}
+
+/// A skin line as the game data refers to it, or its id as blitzadex caches it.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SkinLineRef {
+    Id(u64),
+    Line { id: u64 },
+}
+
+fn deserialize_skin_lines<'de, D>(deserializer: D) -> Result<Vec<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let lines = Option::<Vec<SkinLineRef>>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(lines
+        .into_iter()
+        .map(|line| match line {
+            SkinLineRef::Id(id) | SkinLineRef::Line { id } => id,
+        })
+        .collect())
+}
+
+/// A themed set of skins across champions, e.g. PROJECT.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct SkinLine {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Lines {
+        #[serde(deserialize_with = "deserialize_skin_lines")]
+        lines: Vec<u64>,
+    }
+
+    #[test]
+    fn skin_lines_as_fetched_or_cached() -> serde_json::Result<()> {
+        let parse = |json| serde_json::from_str::<Lines>(json).map(|lines| lines.lines);
+        assert_eq!(parse(r#"{"lines": [{"id": 54}, {"id": 7}]}"#)?, [54, 7]);
+        assert_eq!(parse(r#"{"lines": [54, 7]}"#)?, [54, 7]);
+        assert!(parse(r#"{"lines": null}"#)?.is_empty());
+        assert!(parse(r#"{"lines": [{"name": "PROJECT"}]}"#).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Comparing names the way players type them.
+
+/// The Latin letters with diacritics or ligatures used in the client's locales, and what they
+/// fold to.
+const FOLDED: [(&str, &str); 25] = [
+    ("àáâãäåāăą", "a"),
+    ("æ", "ae"),
+    ("çćč", "c"),
+    ("ď", "d"),
+    ("èéêëēęě", "e"),
+    ("ğ", "g"),
+    ("ìíîïīı", "i"),
+    ("ł", "l"),
+    ("ñńň", "n"),
+    ("òóôõöøōő", "o"),
+    ("œ", "oe"),
+    ("ř", "r"),
+    ("śşš", "s"),
+    ("ß", "ss"),
+    ("ţť", "t"),
+    ("ùúûüūůű", "u"),
+    ("ýÿ", "y"),
+    ("źżž", "z"),
+    ("ð", "d"),
+    ("þ", "th"),
+    ("ș", "s"),
+    ("ț", "t"),
+    ("ơ", "o"),
+    ("ư", "u"),
+    ("đ", "d"),
+];
+
+/// Folds a name for comparing: lowercased, without diacritics, with "&" spelled out and
+/// without spaces or punctuation. "Kai'Sa", "kaisa" and "Kaï Sa" all fold to "kaisa".
+pub fn fold(name: &str) -> String {
+    let mut folded = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c == '&' {
+            folded.push_str("and");
+        } else if let Some((_, base)) = FOLDED.iter().find(|(marked, _)| marked.contains(c)) {
+            folded.push_str(base);
+        } else if c.is_alphanumeric() {
+            folded.push(c);
+        }
+    }
+    folded
+}